@@ -0,0 +1,386 @@
+//! Concurrent batches of requests to a single method, with results streamed as they complete.
+//!
+//! [`RiotApi::batch`](crate::RiotApi::batch) sends a request for each of a list of inputs, and
+//! returns a [`Batch`] stream of each input with its result, in order of completion. Failed
+//! requests are yielded like any other result and do not stop the batch.
+//!
+//! New requests are only started once the route and method have rate limit capacity, using
+//! [`RiotApi::try_reserve`](crate::RiotApi::try_reserve), so the number of requests in flight
+//! adapts to the current rate limits. [`Batch::set_max_concurrency`] sets an upper bound.
+//!
+//! A [`BatchHandle`] reports progress and can cancel the batch from another task:
+//! ```ignore
+//! use futures::StreamExt;
+//!
+//! let route = RegionalRoute::EUROPE;
+//! let mut batch = riot_api.batch(route.into(), "match-v5.getMatch", match_ids, |match_id| {
+//!     riot_api.match_v5().get_match(route, match_id)
+//! });
+//! let handle = batch.handle();
+//! while let Some((match_id, result)) = batch.next().await {
+//!     println!("{}: {:?} ({:?})", match_id, result.is_ok(), handle.progress());
+//! }
+//! ```
+use std::future::Future;
+use std::iter::Peekable;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use futures::future::{AbortHandle, Abortable};
+use futures::{FutureExt, Stream, StreamExt};
+#[cfg(feature = "tracing")]
+use tracing as log;
+
+use crate::time::Duration;
+use crate::timer::Timer;
+use crate::{Reservation, Result, RiotApi};
+
+/// Implemented for all types on wasm, and for [`Send`] types elsewhere. Bounds the inputs,
+/// request functions, and results of a [`Batch`].
+#[cfg(not(target_family = "wasm"))]
+pub trait MaybeSend: Send {}
+#[cfg(not(target_family = "wasm"))]
+impl<T: Send> MaybeSend for T {}
+/// Implemented for all types on wasm, and for [`Send`] types elsewhere. Bounds the inputs,
+/// request functions, and results of a [`Batch`].
+#[cfg(target_family = "wasm")]
+pub trait MaybeSend {}
+#[cfg(target_family = "wasm")]
+impl<T> MaybeSend for T {}
+
+/// A request of a [`Batch`], resolving to its input and result.
+#[cfg(not(target_family = "wasm"))]
+type BatchFuture<'a, I, T> = futures::future::BoxFuture<'a, (I, Result<T>)>;
+/// A request of a [`Batch`], resolving to its input and result.
+#[cfg(target_family = "wasm")]
+type BatchFuture<'a, I, T> = futures::future::LocalBoxFuture<'a, (I, Result<T>)>;
+
+/// The results of a [`Batch`].
+#[cfg(not(target_family = "wasm"))]
+type BatchStream<'a, I, T> = futures::stream::BoxStream<'a, (I, Result<T>)>;
+/// The results of a [`Batch`].
+#[cfg(target_family = "wasm")]
+type BatchStream<'a, I, T> = futures::stream::LocalBoxStream<'a, (I, Result<T>)>;
+
+/// A stream of each input of a batch with its result, see the [module docs](self).
+///
+/// Dropping the stream cancels the batch, including requests in flight.
+pub struct Batch<'a, I, T> {
+    stream: Abortable<BatchStream<'a, I, T>>,
+    handle: BatchHandle,
+}
+
+impl<'a, I: MaybeSend + 'a, T: MaybeSend + 'a> Batch<'a, I, T> {
+    /// Creates a batch which sends `request(&input)` for each of the `inputs`, see
+    /// [`RiotApi::batch`].
+    pub(crate) fn new<It, F, Fut>(
+        riot_api: &'a RiotApi,
+        timer: Arc<dyn Timer>,
+        region_platform: &'static str,
+        method_id: &'static str,
+        inputs: It,
+        request: F,
+    ) -> Self
+    where
+        It: IntoIterator<Item = I>,
+        It::IntoIter: MaybeSend + 'a,
+        F: FnMut(&I) -> Fut + MaybeSend + 'a,
+        Fut: Future<Output = Result<T>> + MaybeSend + 'a,
+    {
+        let (abort_handle, abort_registration) = AbortHandle::new_pair();
+        let handle = BatchHandle {
+            shared: Arc::new(Shared {
+                max_concurrency: AtomicUsize::new(Batch::<I, T>::DEFAULT_MAX_CONCURRENCY),
+                started: AtomicUsize::new(0),
+                succeeded: AtomicUsize::new(0),
+                failed: AtomicUsize::new(0),
+            }),
+            abort_handle,
+        };
+        let state = State {
+            riot_api,
+            timer,
+            region_platform,
+            method_id,
+            inputs: inputs.into_iter().peekable(),
+            request,
+            in_flight: futures::stream::FuturesUnordered::new(),
+            shared: Arc::clone(&handle.shared),
+        };
+        let stream = boxed_stream(futures::stream::unfold(state, State::next));
+        Self {
+            stream: Abortable::new(stream, abort_registration),
+            handle,
+        }
+    }
+}
+
+impl<I, T> Batch<'_, I, T> {
+    /// Default for [`Self::set_max_concurrency`].
+    pub const DEFAULT_MAX_CONCURRENCY: usize = 64;
+
+    /// Sets the maximum number of requests in flight at once. Fewer requests will be in
+    /// flight if the rate limits do not have capacity.
+    ///
+    /// # Panics
+    /// If `max_concurrency` is zero.
+    ///
+    /// # Returns
+    /// `self`, for chaining.
+    pub fn set_max_concurrency(self, max_concurrency: usize) -> Self {
+        assert!(0 < max_concurrency, "max_concurrency must be positive.");
+        self.handle
+            .shared
+            .max_concurrency
+            .store(max_concurrency, Ordering::Relaxed);
+        self
+    }
+
+    /// Returns a handle to report progress and cancel this batch.
+    pub fn handle(&self) -> BatchHandle {
+        self.handle.clone()
+    }
+}
+
+impl<I, T> Stream for Batch<'_, I, T> {
+    type Item = (I, Result<T>);
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.stream.poll_next_unpin(cx)
+    }
+}
+
+/// Handle to a [`Batch`], for reporting progress and canceling it, possibly from another
+/// task.
+#[derive(Debug, Clone)]
+pub struct BatchHandle {
+    shared: Arc<Shared>,
+    abort_handle: AbortHandle,
+}
+
+impl BatchHandle {
+    /// Returns the current progress of the batch.
+    pub fn progress(&self) -> BatchProgress {
+        BatchProgress {
+            started: self.shared.started.load(Ordering::Relaxed),
+            succeeded: self.shared.succeeded.load(Ordering::Relaxed),
+            failed: self.shared.failed.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Cancels the batch. No more requests are started, and the stream ends the next time it
+    /// is polled. Requests in flight are dropped with the stream.
+    pub fn cancel(&self) {
+        log::debug!("Batch canceled.");
+        self.abort_handle.abort();
+    }
+
+    /// If [`Self::cancel`] was called.
+    pub fn is_cancelled(&self) -> bool {
+        self.abort_handle.is_aborted()
+    }
+}
+
+/// Progress of a [`Batch`], see [`BatchHandle::progress`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct BatchProgress {
+    started: usize,
+    succeeded: usize,
+    failed: usize,
+}
+
+impl BatchProgress {
+    /// Number of requests started.
+    pub fn started(&self) -> usize {
+        self.started
+    }
+    /// Number of results yielded which were `Ok`.
+    pub fn succeeded(&self) -> usize {
+        self.succeeded
+    }
+    /// Number of results yielded which were `Err`.
+    pub fn failed(&self) -> usize {
+        self.failed
+    }
+    /// Number of requests started whose results have not yet been yielded.
+    pub fn in_flight(&self) -> usize {
+        self.started - self.succeeded - self.failed
+    }
+}
+
+/// State shared between a [`Batch`] and its [`BatchHandle`]s.
+#[derive(Debug)]
+struct Shared {
+    max_concurrency: AtomicUsize,
+    started: AtomicUsize,
+    succeeded: AtomicUsize,
+    failed: AtomicUsize,
+}
+
+/// State of a [`Batch`] stream.
+struct State<'a, I, T, It: Iterator<Item = I>, F> {
+    riot_api: &'a RiotApi,
+    timer: Arc<dyn Timer>,
+    region_platform: &'static str,
+    method_id: &'static str,
+    /// Inputs not yet started.
+    inputs: Peekable<It>,
+    /// Creates the request future for an input.
+    request: F,
+    /// Requests in flight, each resolving to its input and result.
+    in_flight: futures::stream::FuturesUnordered<BatchFuture<'a, I, T>>,
+    shared: Arc<Shared>,
+}
+
+impl<'a, I, T, It, F, Fut> State<'a, I, T, It, F>
+where
+    I: MaybeSend + 'a,
+    T: MaybeSend + 'a,
+    It: Iterator<Item = I>,
+    F: FnMut(&I) -> Fut,
+    Fut: Future<Output = Result<T>> + MaybeSend + 'a,
+{
+    /// Gets the next result, starting requests as rate limits allow.
+    async fn next(mut self) -> Option<((I, Result<T>), Self)> {
+        loop {
+            let delay = self.start_requests().await;
+            if self.in_flight.is_empty() && delay.is_none() {
+                // All inputs are done.
+                return None;
+            }
+            let sleep = match delay {
+                Some(delay) => self.timer.sleep(delay).left_future(),
+                None => futures::future::pending().right_future(),
+            };
+            let next = match self.in_flight.is_empty() {
+                true => futures::future::pending().left_future(),
+                false => self.in_flight.next().right_future(),
+            };
+            let item = futures::select_biased! {
+                item = next.fuse() => item,
+                _ = sleep.fuse() => None,
+            };
+            if let Some((input, result)) = item {
+                let counter = match &result {
+                    Ok(_) => &self.shared.succeeded,
+                    Err(_) => &self.shared.failed,
+                };
+                counter.fetch_add(1, Ordering::Relaxed);
+                return Some(((input, result), self));
+            }
+        }
+    }
+
+    /// Starts requests while there are inputs, the rate limits have capacity, and the maximum
+    /// concurrency is not reached.
+    ///
+    /// # Returns
+    /// The delay until the rate limits may have capacity, if that is what stopped requests
+    /// from being started.
+    async fn start_requests(&mut self) -> Option<Duration> {
+        while self.in_flight.len() < self.shared.max_concurrency.load(Ordering::Relaxed)
+            && self.inputs.peek().is_some()
+        {
+            let reservation = match self
+                .riot_api
+                .try_reserve(self.region_platform, self.method_id)
+                .await
+            {
+                Ok(reservation) => Some(reservation),
+                Err(Some(delay)) => return Some(delay),
+                // Higher priority requests are waiting, let the rate limits queue one request
+                // to keep the batch moving.
+                Err(None) if self.in_flight.is_empty() => None,
+                Err(None) => return None,
+            };
+            let input = self.inputs.next().expect("peeked input");
+            self.start(input, reservation);
+        }
+        None
+    }
+
+    /// Starts the request for `input`, using `reservation` if given.
+    fn start(&mut self, input: I, reservation: Option<Reservation>) {
+        let future = (self.request)(&input);
+        let future = async move {
+            let result = match reservation {
+                Some(reservation) => reservation.scope(future).await,
+                None => future.await,
+            };
+            (input, result)
+        };
+        self.shared.started.fetch_add(1, Ordering::Relaxed);
+        self.in_flight.push(boxed_future(future));
+    }
+}
+
+/// Boxes a future, with `Send` except on wasm.
+#[cfg(not(target_family = "wasm"))]
+fn boxed_future<'a, Fut: Future + Send + 'a>(
+    future: Fut,
+) -> futures::future::BoxFuture<'a, Fut::Output> {
+    future.boxed()
+}
+/// Boxes a future, with `Send` except on wasm.
+#[cfg(target_family = "wasm")]
+fn boxed_future<'a, Fut: Future + 'a>(
+    future: Fut,
+) -> futures::future::LocalBoxFuture<'a, Fut::Output> {
+    future.boxed_local()
+}
+
+/// Boxes a stream, with `Send` except on wasm.
+#[cfg(not(target_family = "wasm"))]
+fn boxed_stream<'a, St: Stream + Send + 'a>(
+    stream: St,
+) -> futures::stream::BoxStream<'a, St::Item> {
+    stream.boxed()
+}
+/// Boxes a stream, with `Send` except on wasm.
+#[cfg(target_family = "wasm")]
+fn boxed_stream<'a, St: Stream + 'a>(stream: St) -> futures::stream::LocalBoxStream<'a, St::Item> {
+    stream.boxed_local()
+}
+
+#[cfg(all(test, feature = "tokio", not(target_family = "wasm")))]
+mod test {
+    use reqwest::{Method, StatusCode};
+
+    use super::*;
+    use crate::transport::{MockResponse, MockTransport};
+    use crate::RiotApiConfig;
+
+    #[tokio::test]
+    async fn batch() {
+        let transport = Arc::new(MockTransport::new());
+        for body in ["1", "2"] {
+            transport.push_response(MockResponse::new(StatusCode::OK).set_body(body));
+        }
+        transport.push_response(MockResponse::new(StatusCode::BAD_REQUEST));
+        let riot_api =
+            RiotApi::new(RiotApiConfig::with_key("RGAPI-test").set_transport(transport.clone()));
+        let mut batch = riot_api.batch("NA1", "test.method", ["a", "b", "c"], |path| {
+            let request = riot_api.request(Method::GET, "na1", &format!("/{}", path));
+            riot_api.execute_val::<u32>("test.method", "NA1", request)
+        });
+        let handle = batch.handle();
+        let mut results = Vec::new();
+        while let Some((input, result)) = batch.next().await {
+            results.push((input, result.ok()));
+        }
+        results.sort();
+        assert_eq!(vec![("a", Some(1)), ("b", Some(2)), ("c", None)], results);
+        let progress = handle.progress();
+        assert_eq!(
+            (3, 2, 1, 0),
+            (
+                progress.started(),
+                progress.succeeded(),
+                progress.failed(),
+                progress.in_flight()
+            )
+        );
+    }
+}
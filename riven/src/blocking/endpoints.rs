@@ -0,0 +1,1838 @@
+#![cfg_attr(rustfmt, rustfmt_skip)]
+///////////////////////////////////////////////
+//                                           //
+//                     !                     //
+//   This file is automatically generated!   //
+//           Do not directly edit!           //
+//                                           //
+///////////////////////////////////////////////
+
+// http://www.mingweisamuel.com/riotapi-schema/tool/
+// Version 09d8c1d0fc1a3876de2513931c9cb27a616c8768
+
+//! Automatically generated blocking endpoint handles.
+#![allow(clippy::result_large_err, clippy::too_many_arguments)]
+
+use crate::models::*;
+
+use std::vec::Vec;
+
+use crate::Result;
+use crate::consts::{ RegionalRoute, PlatformRoute, ValPlatformRoute };
+use crate::blocking::RiotApi;
+
+impl RiotApi {
+    /// Returns a handle for accessing blocking [AccountV1](crate::blocking::endpoints::AccountV1) endpoints.
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/apis#account-v1" target="_blank">`account-v1`</a>
+    ///
+    /// Note: this method is automatically generated.
+    #[inline]
+    pub fn account_v1(&self) -> AccountV1<'_> {
+        AccountV1 { base: self }
+    }
+    /// Returns a handle for accessing blocking [ChampionMasteryV4](crate::blocking::endpoints::ChampionMasteryV4) endpoints.
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/apis#champion-mastery-v4" target="_blank">`champion-mastery-v4`</a>
+    ///
+    /// Note: this method is automatically generated.
+    #[inline]
+    pub fn champion_mastery_v4(&self) -> ChampionMasteryV4<'_> {
+        ChampionMasteryV4 { base: self }
+    }
+    /// Returns a handle for accessing blocking [ChampionV3](crate::blocking::endpoints::ChampionV3) endpoints.
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/apis#champion-v3" target="_blank">`champion-v3`</a>
+    ///
+    /// Note: this method is automatically generated.
+    #[inline]
+    pub fn champion_v3(&self) -> ChampionV3<'_> {
+        ChampionV3 { base: self }
+    }
+    /// Returns a handle for accessing blocking [ClashV1](crate::blocking::endpoints::ClashV1) endpoints.
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/apis#clash-v1" target="_blank">`clash-v1`</a>
+    ///
+    /// Note: this method is automatically generated.
+    #[inline]
+    pub fn clash_v1(&self) -> ClashV1<'_> {
+        ClashV1 { base: self }
+    }
+    /// Returns a handle for accessing blocking [LeagueExpV4](crate::blocking::endpoints::LeagueExpV4) endpoints.
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/apis#league-exp-v4" target="_blank">`league-exp-v4`</a>
+    ///
+    /// Note: this method is automatically generated.
+    #[inline]
+    pub fn league_exp_v4(&self) -> LeagueExpV4<'_> {
+        LeagueExpV4 { base: self }
+    }
+    /// Returns a handle for accessing blocking [LeagueV4](crate::blocking::endpoints::LeagueV4) endpoints.
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/apis#league-v4" target="_blank">`league-v4`</a>
+    ///
+    /// Note: this method is automatically generated.
+    #[inline]
+    pub fn league_v4(&self) -> LeagueV4<'_> {
+        LeagueV4 { base: self }
+    }
+    /// Returns a handle for accessing blocking [LolChallengesV1](crate::blocking::endpoints::LolChallengesV1) endpoints.
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/apis#lol-challenges-v1" target="_blank">`lol-challenges-v1`</a>
+    ///
+    /// Note: this method is automatically generated.
+    #[inline]
+    pub fn lol_challenges_v1(&self) -> LolChallengesV1<'_> {
+        LolChallengesV1 { base: self }
+    }
+    /// Returns a handle for accessing blocking [LolStatusV3](crate::blocking::endpoints::LolStatusV3) endpoints.
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/apis#lol-status-v3" target="_blank">`lol-status-v3`</a>
+    ///
+    /// Note: this method is automatically generated.
+    #[inline]
+    pub fn lol_status_v3(&self) -> LolStatusV3<'_> {
+        LolStatusV3 { base: self }
+    }
+    /// Returns a handle for accessing blocking [LolStatusV4](crate::blocking::endpoints::LolStatusV4) endpoints.
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/apis#lol-status-v4" target="_blank">`lol-status-v4`</a>
+    ///
+    /// Note: this method is automatically generated.
+    #[inline]
+    pub fn lol_status_v4(&self) -> LolStatusV4<'_> {
+        LolStatusV4 { base: self }
+    }
+    /// Returns a handle for accessing blocking [LorDeckV1](crate::blocking::endpoints::LorDeckV1) endpoints.
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/apis#lor-deck-v1" target="_blank">`lor-deck-v1`</a>
+    ///
+    /// Note: this method is automatically generated.
+    #[inline]
+    pub fn lor_deck_v1(&self) -> LorDeckV1<'_> {
+        LorDeckV1 { base: self }
+    }
+    /// Returns a handle for accessing blocking [LorInventoryV1](crate::blocking::endpoints::LorInventoryV1) endpoints.
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/apis#lor-inventory-v1" target="_blank">`lor-inventory-v1`</a>
+    ///
+    /// Note: this method is automatically generated.
+    #[inline]
+    pub fn lor_inventory_v1(&self) -> LorInventoryV1<'_> {
+        LorInventoryV1 { base: self }
+    }
+    /// Returns a handle for accessing blocking [LorMatchV1](crate::blocking::endpoints::LorMatchV1) endpoints.
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/apis#lor-match-v1" target="_blank">`lor-match-v1`</a>
+    ///
+    /// Note: this method is automatically generated.
+    #[inline]
+    pub fn lor_match_v1(&self) -> LorMatchV1<'_> {
+        LorMatchV1 { base: self }
+    }
+    /// Returns a handle for accessing blocking [LorRankedV1](crate::blocking::endpoints::LorRankedV1) endpoints.
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/apis#lor-ranked-v1" target="_blank">`lor-ranked-v1`</a>
+    ///
+    /// Note: this method is automatically generated.
+    #[inline]
+    pub fn lor_ranked_v1(&self) -> LorRankedV1<'_> {
+        LorRankedV1 { base: self }
+    }
+    /// Returns a handle for accessing blocking [LorStatusV1](crate::blocking::endpoints::LorStatusV1) endpoints.
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/apis#lor-status-v1" target="_blank">`lor-status-v1`</a>
+    ///
+    /// Note: this method is automatically generated.
+    #[inline]
+    pub fn lor_status_v1(&self) -> LorStatusV1<'_> {
+        LorStatusV1 { base: self }
+    }
+    /// Returns a handle for accessing blocking [MatchV5](crate::blocking::endpoints::MatchV5) endpoints.
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/apis#match-v5" target="_blank">`match-v5`</a>
+    ///
+    /// Note: this method is automatically generated.
+    #[inline]
+    pub fn match_v5(&self) -> MatchV5<'_> {
+        MatchV5 { base: self }
+    }
+    /// Returns a handle for accessing blocking [SpectatorTftV5](crate::blocking::endpoints::SpectatorTftV5) endpoints.
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/apis#spectator-tft-v5" target="_blank">`spectator-tft-v5`</a>
+    ///
+    /// Note: this method is automatically generated.
+    #[inline]
+    pub fn spectator_tft_v5(&self) -> SpectatorTftV5<'_> {
+        SpectatorTftV5 { base: self }
+    }
+    /// Returns a handle for accessing blocking [SpectatorV4](crate::blocking::endpoints::SpectatorV4) endpoints.
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/apis#spectator-v4" target="_blank">`spectator-v4`</a>
+    ///
+    /// Note: this method is automatically generated.
+    #[inline]
+    pub fn spectator_v4(&self) -> SpectatorV4<'_> {
+        SpectatorV4 { base: self }
+    }
+    /// Returns a handle for accessing blocking [SpectatorV5](crate::blocking::endpoints::SpectatorV5) endpoints.
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/apis#spectator-v5" target="_blank">`spectator-v5`</a>
+    ///
+    /// Note: this method is automatically generated.
+    #[inline]
+    pub fn spectator_v5(&self) -> SpectatorV5<'_> {
+        SpectatorV5 { base: self }
+    }
+    /// Returns a handle for accessing blocking [SummonerV4](crate::blocking::endpoints::SummonerV4) endpoints.
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/apis#summoner-v4" target="_blank">`summoner-v4`</a>
+    ///
+    /// Note: this method is automatically generated.
+    #[inline]
+    pub fn summoner_v4(&self) -> SummonerV4<'_> {
+        SummonerV4 { base: self }
+    }
+    /// Returns a handle for accessing blocking [TftLeagueV1](crate::blocking::endpoints::TftLeagueV1) endpoints.
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/apis#tft-league-v1" target="_blank">`tft-league-v1`</a>
+    ///
+    /// Note: this method is automatically generated.
+    #[inline]
+    pub fn tft_league_v1(&self) -> TftLeagueV1<'_> {
+        TftLeagueV1 { base: self }
+    }
+    /// Returns a handle for accessing blocking [TftMatchV1](crate::blocking::endpoints::TftMatchV1) endpoints.
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/apis#tft-match-v1" target="_blank">`tft-match-v1`</a>
+    ///
+    /// Note: this method is automatically generated.
+    #[inline]
+    pub fn tft_match_v1(&self) -> TftMatchV1<'_> {
+        TftMatchV1 { base: self }
+    }
+    /// Returns a handle for accessing blocking [TftStatusV1](crate::blocking::endpoints::TftStatusV1) endpoints.
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/apis#tft-status-v1" target="_blank">`tft-status-v1`</a>
+    ///
+    /// Note: this method is automatically generated.
+    #[inline]
+    pub fn tft_status_v1(&self) -> TftStatusV1<'_> {
+        TftStatusV1 { base: self }
+    }
+    /// Returns a handle for accessing blocking [TftSummonerV1](crate::blocking::endpoints::TftSummonerV1) endpoints.
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/apis#tft-summoner-v1" target="_blank">`tft-summoner-v1`</a>
+    ///
+    /// Note: this method is automatically generated.
+    #[inline]
+    pub fn tft_summoner_v1(&self) -> TftSummonerV1<'_> {
+        TftSummonerV1 { base: self }
+    }
+    /// Returns a handle for accessing blocking [TournamentStubV5](crate::blocking::endpoints::TournamentStubV5) endpoints.
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/apis#tournament-stub-v5" target="_blank">`tournament-stub-v5`</a>
+    ///
+    /// Note: this method is automatically generated.
+    #[inline]
+    pub fn tournament_stub_v5(&self) -> TournamentStubV5<'_> {
+        TournamentStubV5 { base: self }
+    }
+    /// Returns a handle for accessing blocking [TournamentV5](crate::blocking::endpoints::TournamentV5) endpoints.
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/apis#tournament-v5" target="_blank">`tournament-v5`</a>
+    ///
+    /// Note: this method is automatically generated.
+    #[inline]
+    pub fn tournament_v5(&self) -> TournamentV5<'_> {
+        TournamentV5 { base: self }
+    }
+    /// Returns a handle for accessing blocking [ValContentV1](crate::blocking::endpoints::ValContentV1) endpoints.
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/apis#val-content-v1" target="_blank">`val-content-v1`</a>
+    ///
+    /// Note: this method is automatically generated.
+    #[inline]
+    pub fn val_content_v1(&self) -> ValContentV1<'_> {
+        ValContentV1 { base: self }
+    }
+    /// Returns a handle for accessing blocking [ValMatchV1](crate::blocking::endpoints::ValMatchV1) endpoints.
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/apis#val-match-v1" target="_blank">`val-match-v1`</a>
+    ///
+    /// Note: this method is automatically generated.
+    #[inline]
+    pub fn val_match_v1(&self) -> ValMatchV1<'_> {
+        ValMatchV1 { base: self }
+    }
+    /// Returns a handle for accessing blocking [ValRankedV1](crate::blocking::endpoints::ValRankedV1) endpoints.
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/apis#val-ranked-v1" target="_blank">`val-ranked-v1`</a>
+    ///
+    /// Note: this method is automatically generated.
+    #[inline]
+    pub fn val_ranked_v1(&self) -> ValRankedV1<'_> {
+        ValRankedV1 { base: self }
+    }
+    /// Returns a handle for accessing blocking [ValStatusV1](crate::blocking::endpoints::ValStatusV1) endpoints.
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/apis#val-status-v1" target="_blank">`val-status-v1`</a>
+    ///
+    /// Note: this method is automatically generated.
+    #[inline]
+    pub fn val_status_v1(&self) -> ValStatusV1<'_> {
+        ValStatusV1 { base: self }
+    }
+}
+
+/// Blocking AccountV1 endpoints handle, accessed by calling [`account_v1()`](crate::blocking::RiotApi::account_v1) on a [`blocking::RiotApi`](crate::blocking::RiotApi) instance.
+/// # Riot Developer API Reference
+/// <a href="https://developer.riotgames.com/apis#account-v1" target="_blank">`account-v1`</a>
+///
+/// Note: this struct is automatically generated.
+#[repr(transparent)]
+pub struct AccountV1<'a> {
+    base: &'a RiotApi,
+}
+impl<'a> AccountV1<'a> {
+    /// Get account by puuid
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// * `puuid` (required, in path)
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#account-v1/GET_getByPuuid" target="_blank">`account-v1.getByPuuid`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_by_puuid(&self, route: RegionalRoute, puuid: &str)
+        -> Result<account_v1::Account>
+    {
+        self.base.block_on(self.base.inner.account_v1().get_by_puuid(route, puuid))
+    }
+
+    /// Get account by riot id
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// * `tag_line` (required, in path) - When querying for a player by their riot id, the gameName and tagLine query params are required.
+    /// * `game_name` (required, in path) - When querying for a player by their riot id, the gameName and tagLine query params are required.
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#account-v1/GET_getByRiotId" target="_blank">`account-v1.getByRiotId`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_by_riot_id(&self, route: RegionalRoute, game_name: &str, tag_line: &str)
+        -> Result<Option<account_v1::Account>>
+    {
+        self.base.block_on(self.base.inner.account_v1().get_by_riot_id(route, game_name, tag_line))
+    }
+
+    /// Get account by access token
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// * `authorization` (required, in header)
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#account-v1/GET_getByAccessToken" target="_blank">`account-v1.getByAccessToken`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_by_access_token(&self, route: RegionalRoute, authorization: &str)
+        -> Result<account_v1::Account>
+    {
+        self.base.block_on(self.base.inner.account_v1().get_by_access_token(route, authorization))
+    }
+
+    /// Get active shard for a player
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// * `game` (required, in path)
+    /// * `puuid` (required, in path)
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#account-v1/GET_getActiveShard" target="_blank">`account-v1.getActiveShard`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_active_shard(&self, route: RegionalRoute, game: &str, puuid: &str)
+        -> Result<Option<account_v1::ActiveShard>>
+    {
+        self.base.block_on(self.base.inner.account_v1().get_active_shard(route, game, puuid))
+    }
+
+}
+
+/// Blocking ChampionMasteryV4 endpoints handle, accessed by calling [`champion_mastery_v4()`](crate::blocking::RiotApi::champion_mastery_v4) on a [`blocking::RiotApi`](crate::blocking::RiotApi) instance.
+/// # Riot Developer API Reference
+/// <a href="https://developer.riotgames.com/apis#champion-mastery-v4" target="_blank">`champion-mastery-v4`</a>
+///
+/// Note: this struct is automatically generated.
+#[repr(transparent)]
+pub struct ChampionMasteryV4<'a> {
+    base: &'a RiotApi,
+}
+impl<'a> ChampionMasteryV4<'a> {
+    /// Get all champion mastery entries sorted by number of champion points descending.
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// * `encrypted_puuid` (required, in path)
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#champion-mastery-v4/GET_getAllChampionMasteriesByPUUID" target="_blank">`champion-mastery-v4.getAllChampionMasteriesByPUUID`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_all_champion_masteries_by_puuid(&self, route: PlatformRoute, encrypted_puuid: &str)
+        -> Result<Vec<champion_mastery_v4::ChampionMastery>>
+    {
+        self.base.block_on(self.base.inner.champion_mastery_v4().get_all_champion_masteries_by_puuid(route, encrypted_puuid))
+    }
+
+    /// Get a champion mastery by puuid and champion ID.
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// * `encrypted_puuid` (required, in path)
+    /// * `champion_id` (required, in path) - Champion ID to retrieve Champion Mastery.
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#champion-mastery-v4/GET_getChampionMasteryByPUUID" target="_blank">`champion-mastery-v4.getChampionMasteryByPUUID`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_champion_mastery_by_puuid(&self, route: PlatformRoute, encrypted_puuid: &str, champion_id: crate::consts::Champion)
+        -> Result<champion_mastery_v4::ChampionMastery>
+    {
+        self.base.block_on(self.base.inner.champion_mastery_v4().get_champion_mastery_by_puuid(route, encrypted_puuid, champion_id))
+    }
+
+    /// Get specified number of top champion mastery entries sorted by number of champion points descending.
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// * `encrypted_puuid` (required, in path)
+    /// * `count` (optional, in query) - Number of entries to retrieve, defaults to 3.
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#champion-mastery-v4/GET_getTopChampionMasteriesByPUUID" target="_blank">`champion-mastery-v4.getTopChampionMasteriesByPUUID`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_top_champion_masteries_by_puuid(&self, route: PlatformRoute, encrypted_puuid: &str, count: Option<i32>)
+        -> Result<Vec<champion_mastery_v4::ChampionMastery>>
+    {
+        self.base.block_on(self.base.inner.champion_mastery_v4().get_top_champion_masteries_by_puuid(route, encrypted_puuid, count))
+    }
+
+    /// Get a player's total champion mastery score, which is the sum of individual champion mastery levels.
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// * `encrypted_puuid` (required, in path)
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#champion-mastery-v4/GET_getChampionMasteryScoreByPUUID" target="_blank">`champion-mastery-v4.getChampionMasteryScoreByPUUID`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_champion_mastery_score_by_puuid(&self, route: PlatformRoute, encrypted_puuid: &str)
+        -> Result<i32>
+    {
+        self.base.block_on(self.base.inner.champion_mastery_v4().get_champion_mastery_score_by_puuid(route, encrypted_puuid))
+    }
+
+}
+
+/// Blocking ChampionV3 endpoints handle, accessed by calling [`champion_v3()`](crate::blocking::RiotApi::champion_v3) on a [`blocking::RiotApi`](crate::blocking::RiotApi) instance.
+/// # Riot Developer API Reference
+/// <a href="https://developer.riotgames.com/apis#champion-v3" target="_blank">`champion-v3`</a>
+///
+/// Note: this struct is automatically generated.
+#[repr(transparent)]
+pub struct ChampionV3<'a> {
+    base: &'a RiotApi,
+}
+impl<'a> ChampionV3<'a> {
+    /// Returns champion rotations, including free-to-play and low-level free-to-play rotations (REST)
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#champion-v3/GET_getChampionInfo" target="_blank">`champion-v3.getChampionInfo`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_champion_info(&self, route: PlatformRoute)
+        -> Result<champion_v3::ChampionInfo>
+    {
+        self.base.block_on(self.base.inner.champion_v3().get_champion_info(route))
+    }
+
+}
+
+/// Blocking ClashV1 endpoints handle, accessed by calling [`clash_v1()`](crate::blocking::RiotApi::clash_v1) on a [`blocking::RiotApi`](crate::blocking::RiotApi) instance.
+/// # Riot Developer API Reference
+/// <a href="https://developer.riotgames.com/apis#clash-v1" target="_blank">`clash-v1`</a>
+///
+/// Note: this struct is automatically generated.
+#[repr(transparent)]
+pub struct ClashV1<'a> {
+    base: &'a RiotApi,
+}
+impl<'a> ClashV1<'a> {
+    /// Get players by summoner ID.
+    /// ## Implementation Notes
+    /// This endpoint returns a list of active Clash players for a given summoner ID. If a summoner registers for multiple tournaments at the same time (e.g., Saturday and Sunday) then both registrations would appear in this list.
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// * `summoner_id` (required, in path)
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#clash-v1/GET_getPlayersBySummoner" target="_blank">`clash-v1.getPlayersBySummoner`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_players_by_summoner(&self, route: PlatformRoute, summoner_id: &str)
+        -> Result<Vec<clash_v1::Player>>
+    {
+        self.base.block_on(self.base.inner.clash_v1().get_players_by_summoner(route, summoner_id))
+    }
+
+    /// Get team by ID.
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// * `team_id` (required, in path)
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#clash-v1/GET_getTeamById" target="_blank">`clash-v1.getTeamById`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_team_by_id(&self, route: PlatformRoute, team_id: &str)
+        -> Result<Option<clash_v1::Team>>
+    {
+        self.base.block_on(self.base.inner.clash_v1().get_team_by_id(route, team_id))
+    }
+
+    /// Get all active or upcoming tournaments.
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#clash-v1/GET_getTournaments" target="_blank">`clash-v1.getTournaments`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_tournaments(&self, route: PlatformRoute)
+        -> Result<Vec<clash_v1::Tournament>>
+    {
+        self.base.block_on(self.base.inner.clash_v1().get_tournaments(route))
+    }
+
+    /// Get tournament by team ID.
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// * `team_id` (required, in path)
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#clash-v1/GET_getTournamentByTeam" target="_blank">`clash-v1.getTournamentByTeam`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_tournament_by_team(&self, route: PlatformRoute, team_id: &str)
+        -> Result<Option<clash_v1::Tournament>>
+    {
+        self.base.block_on(self.base.inner.clash_v1().get_tournament_by_team(route, team_id))
+    }
+
+    /// Get tournament by ID.
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// * `tournament_id` (required, in path)
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#clash-v1/GET_getTournamentById" target="_blank">`clash-v1.getTournamentById`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_tournament_by_id(&self, route: PlatformRoute, tournament_id: i32)
+        -> Result<Option<clash_v1::Tournament>>
+    {
+        self.base.block_on(self.base.inner.clash_v1().get_tournament_by_id(route, tournament_id))
+    }
+
+}
+
+/// Blocking LeagueExpV4 endpoints handle, accessed by calling [`league_exp_v4()`](crate::blocking::RiotApi::league_exp_v4) on a [`blocking::RiotApi`](crate::blocking::RiotApi) instance.
+/// # Riot Developer API Reference
+/// <a href="https://developer.riotgames.com/apis#league-exp-v4" target="_blank">`league-exp-v4`</a>
+///
+/// Note: this struct is automatically generated.
+#[repr(transparent)]
+pub struct LeagueExpV4<'a> {
+    base: &'a RiotApi,
+}
+impl<'a> LeagueExpV4<'a> {
+    /// Get all the league entries.
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// * `queue` (required, in path) - Note that the queue value must be a valid ranked queue.
+    /// * `tier` (required, in path)
+    /// * `division` (required, in path)
+    /// * `page` (optional, in query) - Defaults to 1. Starts with page 1.
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#league-exp-v4/GET_getLeagueEntries" target="_blank">`league-exp-v4.getLeagueEntries`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_league_entries(&self, route: PlatformRoute, queue: crate::consts::QueueType, tier: crate::consts::Tier, division: crate::consts::Division, page: Option<i32>)
+        -> Result<Vec<league_exp_v4::LeagueEntry>>
+    {
+        self.base.block_on(self.base.inner.league_exp_v4().get_league_entries(route, queue, tier, division, page))
+    }
+
+}
+
+/// Blocking LeagueV4 endpoints handle, accessed by calling [`league_v4()`](crate::blocking::RiotApi::league_v4) on a [`blocking::RiotApi`](crate::blocking::RiotApi) instance.
+/// # Riot Developer API Reference
+/// <a href="https://developer.riotgames.com/apis#league-v4" target="_blank">`league-v4`</a>
+///
+/// Note: this struct is automatically generated.
+#[repr(transparent)]
+pub struct LeagueV4<'a> {
+    base: &'a RiotApi,
+}
+impl<'a> LeagueV4<'a> {
+    /// Get the challenger league for given queue.
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// * `queue` (required, in path)
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#league-v4/GET_getChallengerLeague" target="_blank">`league-v4.getChallengerLeague`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_challenger_league(&self, route: PlatformRoute, queue: crate::consts::QueueType)
+        -> Result<league_v4::LeagueList>
+    {
+        self.base.block_on(self.base.inner.league_v4().get_challenger_league(route, queue))
+    }
+
+    /// Get league entries in all queues for a given summoner ID.
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// * `encrypted_summoner_id` (required, in path)
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#league-v4/GET_getLeagueEntriesForSummoner" target="_blank">`league-v4.getLeagueEntriesForSummoner`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_league_entries_for_summoner(&self, route: PlatformRoute, encrypted_summoner_id: &str)
+        -> Result<Vec<league_v4::LeagueEntry>>
+    {
+        self.base.block_on(self.base.inner.league_v4().get_league_entries_for_summoner(route, encrypted_summoner_id))
+    }
+
+    /// Get all the league entries.
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// * `division` (required, in path)
+    /// * `tier` (required, in path)
+    /// * `queue` (required, in path) - Note that the queue value must be a valid ranked queue.
+    /// * `page` (optional, in query) - Defaults to 1. Starts with page 1.
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#league-v4/GET_getLeagueEntries" target="_blank">`league-v4.getLeagueEntries`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_league_entries(&self, route: PlatformRoute, queue: crate::consts::QueueType, tier: crate::consts::Tier, division: crate::consts::Division, page: Option<i32>)
+        -> Result<Vec<league_v4::LeagueEntry>>
+    {
+        self.base.block_on(self.base.inner.league_v4().get_league_entries(route, queue, tier, division, page))
+    }
+
+    /// Get the grandmaster league of a specific queue.
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// * `queue` (required, in path)
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#league-v4/GET_getGrandmasterLeague" target="_blank">`league-v4.getGrandmasterLeague`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_grandmaster_league(&self, route: PlatformRoute, queue: crate::consts::QueueType)
+        -> Result<league_v4::LeagueList>
+    {
+        self.base.block_on(self.base.inner.league_v4().get_grandmaster_league(route, queue))
+    }
+
+    /// Get league with given ID, including inactive entries.
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// * `league_id` (required, in path) - The UUID of the league.
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#league-v4/GET_getLeagueById" target="_blank">`league-v4.getLeagueById`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_league_by_id(&self, route: PlatformRoute, league_id: &str)
+        -> Result<Option<league_v4::LeagueList>>
+    {
+        self.base.block_on(self.base.inner.league_v4().get_league_by_id(route, league_id))
+    }
+
+    /// Get the master league for given queue.
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// * `queue` (required, in path)
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#league-v4/GET_getMasterLeague" target="_blank">`league-v4.getMasterLeague`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_master_league(&self, route: PlatformRoute, queue: crate::consts::QueueType)
+        -> Result<league_v4::LeagueList>
+    {
+        self.base.block_on(self.base.inner.league_v4().get_master_league(route, queue))
+    }
+
+}
+
+/// Blocking LolChallengesV1 endpoints handle, accessed by calling [`lol_challenges_v1()`](crate::blocking::RiotApi::lol_challenges_v1) on a [`blocking::RiotApi`](crate::blocking::RiotApi) instance.
+/// # Riot Developer API Reference
+/// <a href="https://developer.riotgames.com/apis#lol-challenges-v1" target="_blank">`lol-challenges-v1`</a>
+///
+/// Note: this struct is automatically generated.
+#[repr(transparent)]
+pub struct LolChallengesV1<'a> {
+    base: &'a RiotApi,
+}
+impl<'a> LolChallengesV1<'a> {
+    /// List of all basic challenge configuration information (includes all translations for names and descriptions)
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#lol-challenges-v1/GET_getAllChallengeConfigs" target="_blank">`lol-challenges-v1.getAllChallengeConfigs`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_all_challenge_configs(&self, route: PlatformRoute)
+        -> Result<Vec<lol_challenges_v1::ChallengeConfigInfo>>
+    {
+        self.base.block_on(self.base.inner.lol_challenges_v1().get_all_challenge_configs(route))
+    }
+
+    /// Map of level to percentile of players who have achieved it - keys: ChallengeId -> Season -> Level -> percentile of players who achieved it
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#lol-challenges-v1/GET_getAllChallengePercentiles" target="_blank">`lol-challenges-v1.getAllChallengePercentiles`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_all_challenge_percentiles(&self, route: PlatformRoute)
+        -> Result<std::collections::HashMap<i64, std::collections::HashMap<crate::consts::Tier, f64>>>
+    {
+        self.base.block_on(self.base.inner.lol_challenges_v1().get_all_challenge_percentiles(route))
+    }
+
+    /// Get challenge configuration (REST)
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// * `challenge_id` (required, in path)
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#lol-challenges-v1/GET_getChallengeConfigs" target="_blank">`lol-challenges-v1.getChallengeConfigs`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_challenge_configs(&self, route: PlatformRoute, challenge_id: i64)
+        -> Result<Option<lol_challenges_v1::ChallengeConfigInfo>>
+    {
+        self.base.block_on(self.base.inner.lol_challenges_v1().get_challenge_configs(route, challenge_id))
+    }
+
+    /// Return top players for each level. Level must be MASTER, GRANDMASTER or CHALLENGER.
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// * `level` (required, in path)
+    /// * `challenge_id` (required, in path)
+    /// * `limit` (optional, in query)
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#lol-challenges-v1/GET_getChallengeLeaderboards" target="_blank">`lol-challenges-v1.getChallengeLeaderboards`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_challenge_leaderboards(&self, route: PlatformRoute, challenge_id: i64, level: crate::consts::Tier, limit: Option<i32>)
+        -> Result<Option<Vec<lol_challenges_v1::ApexPlayerInfo>>>
+    {
+        self.base.block_on(self.base.inner.lol_challenges_v1().get_challenge_leaderboards(route, challenge_id, level, limit))
+    }
+
+    /// Map of level to percentile of players who have achieved it
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// * `challenge_id` (required, in path)
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#lol-challenges-v1/GET_getChallengePercentiles" target="_blank">`lol-challenges-v1.getChallengePercentiles`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_challenge_percentiles(&self, route: PlatformRoute, challenge_id: i64)
+        -> Result<Option<std::collections::HashMap<crate::consts::Tier, f64>>>
+    {
+        self.base.block_on(self.base.inner.lol_challenges_v1().get_challenge_percentiles(route, challenge_id))
+    }
+
+    /// Returns player information with list of all progressed challenges (REST)
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// * `puuid` (required, in path)
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#lol-challenges-v1/GET_getPlayerData" target="_blank">`lol-challenges-v1.getPlayerData`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_player_data(&self, route: PlatformRoute, puuid: &str)
+        -> Result<lol_challenges_v1::PlayerInfo>
+    {
+        self.base.block_on(self.base.inner.lol_challenges_v1().get_player_data(route, puuid))
+    }
+
+}
+
+/// Blocking LolStatusV3 endpoints handle, accessed by calling [`lol_status_v3()`](crate::blocking::RiotApi::lol_status_v3) on a [`blocking::RiotApi`](crate::blocking::RiotApi) instance.
+/// # Riot Developer API Reference
+/// <a href="https://developer.riotgames.com/apis#lol-status-v3" target="_blank">`lol-status-v3`</a>
+///
+/// Note: this struct is automatically generated.
+#[repr(transparent)]
+pub struct LolStatusV3<'a> {
+    base: &'a RiotApi,
+}
+impl<'a> LolStatusV3<'a> {
+    /// Get League of Legends status for the given shard.
+    /// ## Rate Limit Notes
+    /// Requests to this API are not counted against the application Rate Limits.
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#lol-status-v3/GET_getShardData" target="_blank">`lol-status-v3.getShardData`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_shard_data(&self, route: PlatformRoute)
+        -> Result<lol_status_v3::ShardStatus>
+    {
+        self.base.block_on(self.base.inner.lol_status_v3().get_shard_data(route))
+    }
+
+}
+
+/// Blocking LolStatusV4 endpoints handle, accessed by calling [`lol_status_v4()`](crate::blocking::RiotApi::lol_status_v4) on a [`blocking::RiotApi`](crate::blocking::RiotApi) instance.
+/// # Riot Developer API Reference
+/// <a href="https://developer.riotgames.com/apis#lol-status-v4" target="_blank">`lol-status-v4`</a>
+///
+/// Note: this struct is automatically generated.
+#[repr(transparent)]
+pub struct LolStatusV4<'a> {
+    base: &'a RiotApi,
+}
+impl<'a> LolStatusV4<'a> {
+    /// Get League of Legends status for the given platform.
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#lol-status-v4/GET_getPlatformData" target="_blank">`lol-status-v4.getPlatformData`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_platform_data(&self, route: PlatformRoute)
+        -> Result<lol_status_v4::PlatformData>
+    {
+        self.base.block_on(self.base.inner.lol_status_v4().get_platform_data(route))
+    }
+
+}
+
+/// Blocking LorDeckV1 endpoints handle, accessed by calling [`lor_deck_v1()`](crate::blocking::RiotApi::lor_deck_v1) on a [`blocking::RiotApi`](crate::blocking::RiotApi) instance.
+/// # Riot Developer API Reference
+/// <a href="https://developer.riotgames.com/apis#lor-deck-v1" target="_blank">`lor-deck-v1`</a>
+///
+/// Note: this struct is automatically generated.
+#[repr(transparent)]
+pub struct LorDeckV1<'a> {
+    base: &'a RiotApi,
+}
+impl<'a> LorDeckV1<'a> {
+    /// Get a list of the calling user's decks.
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// * `authorization` (required, in header)
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#lor-deck-v1/GET_getDecks" target="_blank">`lor-deck-v1.getDecks`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_decks(&self, route: RegionalRoute, authorization: &str)
+        -> Result<Vec<lor_deck_v1::Deck>>
+    {
+        self.base.block_on(self.base.inner.lor_deck_v1().get_decks(route, authorization))
+    }
+
+    /// Create a new deck for the calling user.
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// * `authorization` (required, in header)
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#lor-deck-v1/POST_createDeck" target="_blank">`lor-deck-v1.createDeck`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn create_deck(&self, route: RegionalRoute, body: &lor_deck_v1::NewDeck, authorization: &str)
+        -> Result<String>
+    {
+        self.base.block_on(self.base.inner.lor_deck_v1().create_deck(route, body, authorization))
+    }
+
+}
+
+/// Blocking LorInventoryV1 endpoints handle, accessed by calling [`lor_inventory_v1()`](crate::blocking::RiotApi::lor_inventory_v1) on a [`blocking::RiotApi`](crate::blocking::RiotApi) instance.
+/// # Riot Developer API Reference
+/// <a href="https://developer.riotgames.com/apis#lor-inventory-v1" target="_blank">`lor-inventory-v1`</a>
+///
+/// Note: this struct is automatically generated.
+#[repr(transparent)]
+pub struct LorInventoryV1<'a> {
+    base: &'a RiotApi,
+}
+impl<'a> LorInventoryV1<'a> {
+    /// Return a list of cards owned by the calling user.
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// * `authorization` (required, in header)
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#lor-inventory-v1/GET_getCards" target="_blank">`lor-inventory-v1.getCards`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_cards(&self, route: RegionalRoute, authorization: &str)
+        -> Result<Vec<lor_inventory_v1::Card>>
+    {
+        self.base.block_on(self.base.inner.lor_inventory_v1().get_cards(route, authorization))
+    }
+
+}
+
+/// Blocking LorMatchV1 endpoints handle, accessed by calling [`lor_match_v1()`](crate::blocking::RiotApi::lor_match_v1) on a [`blocking::RiotApi`](crate::blocking::RiotApi) instance.
+/// # Riot Developer API Reference
+/// <a href="https://developer.riotgames.com/apis#lor-match-v1" target="_blank">`lor-match-v1`</a>
+///
+/// Note: this struct is automatically generated.
+#[repr(transparent)]
+pub struct LorMatchV1<'a> {
+    base: &'a RiotApi,
+}
+impl<'a> LorMatchV1<'a> {
+    /// Get a list of match ids by PUUID
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// * `puuid` (required, in path)
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#lor-match-v1/GET_getMatchIdsByPUUID" target="_blank">`lor-match-v1.getMatchIdsByPUUID`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_match_ids_by_puuid(&self, route: RegionalRoute, puuid: &str)
+        -> Result<Vec<String>>
+    {
+        self.base.block_on(self.base.inner.lor_match_v1().get_match_ids_by_puuid(route, puuid))
+    }
+
+    /// Get match by id
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// * `match_id` (required, in path)
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#lor-match-v1/GET_getMatch" target="_blank">`lor-match-v1.getMatch`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_match(&self, route: RegionalRoute, match_id: &str)
+        -> Result<lor_match_v1::Match>
+    {
+        self.base.block_on(self.base.inner.lor_match_v1().get_match(route, match_id))
+    }
+
+}
+
+/// Blocking LorRankedV1 endpoints handle, accessed by calling [`lor_ranked_v1()`](crate::blocking::RiotApi::lor_ranked_v1) on a [`blocking::RiotApi`](crate::blocking::RiotApi) instance.
+/// # Riot Developer API Reference
+/// <a href="https://developer.riotgames.com/apis#lor-ranked-v1" target="_blank">`lor-ranked-v1`</a>
+///
+/// Note: this struct is automatically generated.
+#[repr(transparent)]
+pub struct LorRankedV1<'a> {
+    base: &'a RiotApi,
+}
+impl<'a> LorRankedV1<'a> {
+    /// Get the players in Master tier.
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#lor-ranked-v1/GET_getLeaderboards" target="_blank">`lor-ranked-v1.getLeaderboards`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_leaderboards(&self, route: RegionalRoute)
+        -> Result<lor_ranked_v1::Leaderboard>
+    {
+        self.base.block_on(self.base.inner.lor_ranked_v1().get_leaderboards(route))
+    }
+
+}
+
+/// Blocking LorStatusV1 endpoints handle, accessed by calling [`lor_status_v1()`](crate::blocking::RiotApi::lor_status_v1) on a [`blocking::RiotApi`](crate::blocking::RiotApi) instance.
+/// # Riot Developer API Reference
+/// <a href="https://developer.riotgames.com/apis#lor-status-v1" target="_blank">`lor-status-v1`</a>
+///
+/// Note: this struct is automatically generated.
+#[repr(transparent)]
+pub struct LorStatusV1<'a> {
+    base: &'a RiotApi,
+}
+impl<'a> LorStatusV1<'a> {
+    /// Get Legends of Runeterra status for the given platform.
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#lor-status-v1/GET_getPlatformData" target="_blank">`lor-status-v1.getPlatformData`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_platform_data(&self, route: RegionalRoute)
+        -> Result<lor_status_v1::PlatformData>
+    {
+        self.base.block_on(self.base.inner.lor_status_v1().get_platform_data(route))
+    }
+
+}
+
+/// Blocking MatchV5 endpoints handle, accessed by calling [`match_v5()`](crate::blocking::RiotApi::match_v5) on a [`blocking::RiotApi`](crate::blocking::RiotApi) instance.
+/// # Riot Developer API Reference
+/// <a href="https://developer.riotgames.com/apis#match-v5" target="_blank">`match-v5`</a>
+///
+/// Note: this struct is automatically generated.
+#[repr(transparent)]
+pub struct MatchV5<'a> {
+    base: &'a RiotApi,
+}
+impl<'a> MatchV5<'a> {
+    /// Get a list of match ids by puuid
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// * `puuid` (required, in path)
+    /// * `start_time` (optional, in query) - Epoch timestamp in seconds. The matchlist started storing timestamps on June 16th, 2021. Any matches played before June 16th, 2021 won't be included in the results if the startTime filter is set.
+    /// * `end_time` (optional, in query) - Epoch timestamp in seconds.
+    /// * `queue` (optional, in query) - Filter the list of match ids by a specific queue id. This filter is mutually inclusive of the type filter meaning any match ids returned must match both the queue and type filters.
+    /// * `type` (optional, in query) - Filter the list of match ids by the type of match. This filter is mutually inclusive of the queue filter meaning any match ids returned must match both the queue and type filters.
+    /// * `start` (optional, in query) - Defaults to 0. Start index.
+    /// * `count` (optional, in query) - Defaults to 20. Valid values: 0 to 100. Number of match ids to return.
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#match-v5/GET_getMatchIdsByPUUID" target="_blank">`match-v5.getMatchIdsByPUUID`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_match_ids_by_puuid(&self, route: RegionalRoute, puuid: &str, count: Option<i32>, end_time: Option<i64>, queue: Option<crate::consts::Queue>, start_time: Option<i64>, start: Option<i32>, r#type: Option<&str>)
+        -> Result<Vec<String>>
+    {
+        self.base.block_on(self.base.inner.match_v5().get_match_ids_by_puuid(route, puuid, count, end_time, queue, start_time, start, r#type))
+    }
+
+    /// Get a match by match id
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// * `match_id` (required, in path)
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#match-v5/GET_getMatch" target="_blank">`match-v5.getMatch`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_match(&self, route: RegionalRoute, match_id: &str)
+        -> Result<Option<match_v5::Match>>
+    {
+        self.base.block_on(self.base.inner.match_v5().get_match(route, match_id))
+    }
+
+    /// Get a match timeline by match id
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// * `match_id` (required, in path)
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#match-v5/GET_getTimeline" target="_blank">`match-v5.getTimeline`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_timeline(&self, route: RegionalRoute, match_id: &str)
+        -> Result<Option<match_v5::MatchTimeline>>
+    {
+        self.base.block_on(self.base.inner.match_v5().get_timeline(route, match_id))
+    }
+
+}
+
+/// Blocking SpectatorTftV5 endpoints handle, accessed by calling [`spectator_tft_v5()`](crate::blocking::RiotApi::spectator_tft_v5) on a [`blocking::RiotApi`](crate::blocking::RiotApi) instance.
+/// # Riot Developer API Reference
+/// <a href="https://developer.riotgames.com/apis#spectator-tft-v5" target="_blank">`spectator-tft-v5`</a>
+///
+/// Note: this struct is automatically generated.
+#[repr(transparent)]
+pub struct SpectatorTftV5<'a> {
+    base: &'a RiotApi,
+}
+impl<'a> SpectatorTftV5<'a> {
+    /// Get current game information for the given puuid.
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// * `encrypted_puuid` (required, in path) - The puuid of the summoner.
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#spectator-tft-v5/GET_getCurrentGameInfoByPuuid" target="_blank">`spectator-tft-v5.getCurrentGameInfoByPuuid`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_current_game_info_by_puuid(&self, route: PlatformRoute, encrypted_puuid: &str)
+        -> Result<Option<spectator_tft_v5::CurrentGameInfo>>
+    {
+        self.base.block_on(self.base.inner.spectator_tft_v5().get_current_game_info_by_puuid(route, encrypted_puuid))
+    }
+
+    /// Get list of featured games.
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#spectator-tft-v5/GET_getFeaturedGames" target="_blank">`spectator-tft-v5.getFeaturedGames`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_featured_games(&self, route: PlatformRoute)
+        -> Result<spectator_tft_v5::FeaturedGames>
+    {
+        self.base.block_on(self.base.inner.spectator_tft_v5().get_featured_games(route))
+    }
+
+}
+
+/// Blocking SpectatorV4 endpoints handle, accessed by calling [`spectator_v4()`](crate::blocking::RiotApi::spectator_v4) on a [`blocking::RiotApi`](crate::blocking::RiotApi) instance.
+/// # Riot Developer API Reference
+/// <a href="https://developer.riotgames.com/apis#spectator-v4" target="_blank">`spectator-v4`</a>
+///
+/// Note: this struct is automatically generated.
+#[repr(transparent)]
+pub struct SpectatorV4<'a> {
+    base: &'a RiotApi,
+}
+impl<'a> SpectatorV4<'a> {
+    /// Get current game information for the given summoner ID.
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// * `encrypted_summoner_id` (required, in path) - The ID of the summoner.
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#spectator-v4/GET_getCurrentGameInfoBySummoner" target="_blank">`spectator-v4.getCurrentGameInfoBySummoner`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_current_game_info_by_summoner(&self, route: PlatformRoute, encrypted_summoner_id: &str)
+        -> Result<Option<spectator_v4::CurrentGameInfo>>
+    {
+        self.base.block_on(self.base.inner.spectator_v4().get_current_game_info_by_summoner(route, encrypted_summoner_id))
+    }
+
+    /// Get list of featured games.
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#spectator-v4/GET_getFeaturedGames" target="_blank">`spectator-v4.getFeaturedGames`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_featured_games(&self, route: PlatformRoute)
+        -> Result<spectator_v4::FeaturedGames>
+    {
+        self.base.block_on(self.base.inner.spectator_v4().get_featured_games(route))
+    }
+
+}
+
+/// Blocking SpectatorV5 endpoints handle, accessed by calling [`spectator_v5()`](crate::blocking::RiotApi::spectator_v5) on a [`blocking::RiotApi`](crate::blocking::RiotApi) instance.
+/// # Riot Developer API Reference
+/// <a href="https://developer.riotgames.com/apis#spectator-v5" target="_blank">`spectator-v5`</a>
+///
+/// Note: this struct is automatically generated.
+#[repr(transparent)]
+pub struct SpectatorV5<'a> {
+    base: &'a RiotApi,
+}
+impl<'a> SpectatorV5<'a> {
+    /// Get current game information for the given puuid.
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// * `encrypted_puuid` (required, in path) - The puuid of the summoner.
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#spectator-v5/GET_getCurrentGameInfoByPuuid" target="_blank">`spectator-v5.getCurrentGameInfoByPuuid`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_current_game_info_by_puuid(&self, route: PlatformRoute, encrypted_puuid: &str)
+        -> Result<Option<spectator_v5::CurrentGameInfo>>
+    {
+        self.base.block_on(self.base.inner.spectator_v5().get_current_game_info_by_puuid(route, encrypted_puuid))
+    }
+
+    /// Get list of featured games.
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#spectator-v5/GET_getFeaturedGames" target="_blank">`spectator-v5.getFeaturedGames`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_featured_games(&self, route: PlatformRoute)
+        -> Result<spectator_v5::FeaturedGames>
+    {
+        self.base.block_on(self.base.inner.spectator_v5().get_featured_games(route))
+    }
+
+}
+
+/// Blocking SummonerV4 endpoints handle, accessed by calling [`summoner_v4()`](crate::blocking::RiotApi::summoner_v4) on a [`blocking::RiotApi`](crate::blocking::RiotApi) instance.
+/// # Riot Developer API Reference
+/// <a href="https://developer.riotgames.com/apis#summoner-v4" target="_blank">`summoner-v4`</a>
+///
+/// Note: this struct is automatically generated.
+#[repr(transparent)]
+pub struct SummonerV4<'a> {
+    base: &'a RiotApi,
+}
+impl<'a> SummonerV4<'a> {
+    /// Get a summoner by its RSO encrypted PUUID.
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// * `rso_puuid` (required, in path) - Summoner ID
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#summoner-v4/GET_getByRSOPUUID" target="_blank">`summoner-v4.getByRSOPUUID`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_by_rsopuuid(&self, route: PlatformRoute, rso_puuid: &str)
+        -> Result<summoner_v4::Summoner>
+    {
+        self.base.block_on(self.base.inner.summoner_v4().get_by_rsopuuid(route, rso_puuid))
+    }
+
+    /// Get a summoner by account ID.
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// * `encrypted_account_id` (required, in path)
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#summoner-v4/GET_getByAccountId" target="_blank">`summoner-v4.getByAccountId`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_by_account_id(&self, route: PlatformRoute, encrypted_account_id: &str)
+        -> Result<summoner_v4::Summoner>
+    {
+        self.base.block_on(self.base.inner.summoner_v4().get_by_account_id(route, encrypted_account_id))
+    }
+
+    /// Get a summoner by summoner name.
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// * `summoner_name` (required, in path) - Summoner Name
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#summoner-v4/GET_getBySummonerName" target="_blank">`summoner-v4.getBySummonerName`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_by_summoner_name(&self, route: PlatformRoute, summoner_name: &str)
+        -> Result<Option<summoner_v4::Summoner>>
+    {
+        self.base.block_on(self.base.inner.summoner_v4().get_by_summoner_name(route, summoner_name))
+    }
+
+    /// Get a summoner by PUUID.
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// * `encrypted_puuid` (required, in path) - Summoner ID
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#summoner-v4/GET_getByPUUID" target="_blank">`summoner-v4.getByPUUID`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_by_puuid(&self, route: PlatformRoute, encrypted_puuid: &str)
+        -> Result<summoner_v4::Summoner>
+    {
+        self.base.block_on(self.base.inner.summoner_v4().get_by_puuid(route, encrypted_puuid))
+    }
+
+    /// Get a summoner by access token.
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// * `authorization` (optional, in header) - Bearer token
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#summoner-v4/GET_getByAccessToken" target="_blank">`summoner-v4.getByAccessToken`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_by_access_token(&self, route: PlatformRoute, authorization: Option<&str>)
+        -> Result<summoner_v4::Summoner>
+    {
+        self.base.block_on(self.base.inner.summoner_v4().get_by_access_token(route, authorization))
+    }
+
+    /// Get a summoner by summoner ID.
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// * `encrypted_summoner_id` (required, in path) - Summoner ID
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#summoner-v4/GET_getBySummonerId" target="_blank">`summoner-v4.getBySummonerId`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_by_summoner_id(&self, route: PlatformRoute, encrypted_summoner_id: &str)
+        -> Result<summoner_v4::Summoner>
+    {
+        self.base.block_on(self.base.inner.summoner_v4().get_by_summoner_id(route, encrypted_summoner_id))
+    }
+
+}
+
+/// Blocking TftLeagueV1 endpoints handle, accessed by calling [`tft_league_v1()`](crate::blocking::RiotApi::tft_league_v1) on a [`blocking::RiotApi`](crate::blocking::RiotApi) instance.
+/// # Riot Developer API Reference
+/// <a href="https://developer.riotgames.com/apis#tft-league-v1" target="_blank">`tft-league-v1`</a>
+///
+/// Note: this struct is automatically generated.
+#[repr(transparent)]
+pub struct TftLeagueV1<'a> {
+    base: &'a RiotApi,
+}
+impl<'a> TftLeagueV1<'a> {
+    /// Get the challenger league.
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// * `queue` (optional, in query) - Defaults to RANKED_TFT.
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#tft-league-v1/GET_getChallengerLeague" target="_blank">`tft-league-v1.getChallengerLeague`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_challenger_league(&self, route: PlatformRoute, queue: Option<&str>)
+        -> Result<tft_league_v1::LeagueList>
+    {
+        self.base.block_on(self.base.inner.tft_league_v1().get_challenger_league(route, queue))
+    }
+
+    /// Get league entries for a given summoner ID.
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// * `summoner_id` (required, in path)
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#tft-league-v1/GET_getLeagueEntriesForSummoner" target="_blank">`tft-league-v1.getLeagueEntriesForSummoner`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_league_entries_for_summoner(&self, route: PlatformRoute, summoner_id: &str)
+        -> Result<Vec<tft_league_v1::LeagueEntry>>
+    {
+        self.base.block_on(self.base.inner.tft_league_v1().get_league_entries_for_summoner(route, summoner_id))
+    }
+
+    /// Get all the league entries.
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// * `tier` (required, in path)
+    /// * `division` (required, in path)
+    /// * `queue` (optional, in query) - Defaults to RANKED_TFT.
+    /// * `page` (optional, in query) - Defaults to 1. Starts with page 1.
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#tft-league-v1/GET_getLeagueEntries" target="_blank">`tft-league-v1.getLeagueEntries`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_league_entries(&self, route: PlatformRoute, tier: crate::consts::Tier, division: &str, page: Option<i32>, queue: Option<&str>)
+        -> Result<Vec<tft_league_v1::LeagueEntry>>
+    {
+        self.base.block_on(self.base.inner.tft_league_v1().get_league_entries(route, tier, division, page, queue))
+    }
+
+    /// Get the grandmaster league.
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// * `queue` (optional, in query) - Defaults to RANKED_TFT.
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#tft-league-v1/GET_getGrandmasterLeague" target="_blank">`tft-league-v1.getGrandmasterLeague`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_grandmaster_league(&self, route: PlatformRoute, queue: Option<&str>)
+        -> Result<tft_league_v1::LeagueList>
+    {
+        self.base.block_on(self.base.inner.tft_league_v1().get_grandmaster_league(route, queue))
+    }
+
+    /// Get league with given ID, including inactive entries.
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// * `league_id` (required, in path) - The UUID of the league.
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#tft-league-v1/GET_getLeagueById" target="_blank">`tft-league-v1.getLeagueById`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_league_by_id(&self, route: PlatformRoute, league_id: &str)
+        -> Result<Option<tft_league_v1::LeagueList>>
+    {
+        self.base.block_on(self.base.inner.tft_league_v1().get_league_by_id(route, league_id))
+    }
+
+    /// Get the master league.
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// * `queue` (optional, in query) - Defaults to RANKED_TFT.
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#tft-league-v1/GET_getMasterLeague" target="_blank">`tft-league-v1.getMasterLeague`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_master_league(&self, route: PlatformRoute, queue: Option<&str>)
+        -> Result<tft_league_v1::LeagueList>
+    {
+        self.base.block_on(self.base.inner.tft_league_v1().get_master_league(route, queue))
+    }
+
+    /// Get the top rated ladder for given queue
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// * `queue` (required, in path)
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#tft-league-v1/GET_getTopRatedLadder" target="_blank">`tft-league-v1.getTopRatedLadder`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_top_rated_ladder(&self, route: PlatformRoute, queue: crate::consts::QueueType)
+        -> Result<Vec<tft_league_v1::TopRatedLadderEntry>>
+    {
+        self.base.block_on(self.base.inner.tft_league_v1().get_top_rated_ladder(route, queue))
+    }
+
+}
+
+/// Blocking TftMatchV1 endpoints handle, accessed by calling [`tft_match_v1()`](crate::blocking::RiotApi::tft_match_v1) on a [`blocking::RiotApi`](crate::blocking::RiotApi) instance.
+/// # Riot Developer API Reference
+/// <a href="https://developer.riotgames.com/apis#tft-match-v1" target="_blank">`tft-match-v1`</a>
+///
+/// Note: this struct is automatically generated.
+#[repr(transparent)]
+pub struct TftMatchV1<'a> {
+    base: &'a RiotApi,
+}
+impl<'a> TftMatchV1<'a> {
+    /// Get a list of match ids by PUUID
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// * `puuid` (required, in path)
+    /// * `start` (optional, in query) - Defaults to 0. Start index.
+    /// * `end_time` (optional, in query) - Epoch timestamp in seconds.
+    /// * `start_time` (optional, in query) - Epoch timestamp in seconds. The matchlist started storing timestamps on June 16th, 2021. Any matches played before June 16th, 2021 won't be included in the results if the startTime filter is set.
+    /// * `count` (optional, in query) - Defaults to 20. Number of match ids to return.
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#tft-match-v1/GET_getMatchIdsByPUUID" target="_blank">`tft-match-v1.getMatchIdsByPUUID`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_match_ids_by_puuid(&self, route: RegionalRoute, puuid: &str, count: Option<i32>, end_time: Option<i64>, start: Option<i32>, start_time: Option<i64>)
+        -> Result<Vec<String>>
+    {
+        self.base.block_on(self.base.inner.tft_match_v1().get_match_ids_by_puuid(route, puuid, count, end_time, start, start_time))
+    }
+
+    /// Get a match by match id
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// * `match_id` (required, in path)
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#tft-match-v1/GET_getMatch" target="_blank">`tft-match-v1.getMatch`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_match(&self, route: RegionalRoute, match_id: &str)
+        -> Result<Option<tft_match_v1::Match>>
+    {
+        self.base.block_on(self.base.inner.tft_match_v1().get_match(route, match_id))
+    }
+
+}
+
+/// Blocking TftStatusV1 endpoints handle, accessed by calling [`tft_status_v1()`](crate::blocking::RiotApi::tft_status_v1) on a [`blocking::RiotApi`](crate::blocking::RiotApi) instance.
+/// # Riot Developer API Reference
+/// <a href="https://developer.riotgames.com/apis#tft-status-v1" target="_blank">`tft-status-v1`</a>
+///
+/// Note: this struct is automatically generated.
+#[repr(transparent)]
+pub struct TftStatusV1<'a> {
+    base: &'a RiotApi,
+}
+impl<'a> TftStatusV1<'a> {
+    /// Get Teamfight Tactics status for the given platform.
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#tft-status-v1/GET_getPlatformData" target="_blank">`tft-status-v1.getPlatformData`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_platform_data(&self, route: PlatformRoute)
+        -> Result<tft_status_v1::PlatformData>
+    {
+        self.base.block_on(self.base.inner.tft_status_v1().get_platform_data(route))
+    }
+
+}
+
+/// Blocking TftSummonerV1 endpoints handle, accessed by calling [`tft_summoner_v1()`](crate::blocking::RiotApi::tft_summoner_v1) on a [`blocking::RiotApi`](crate::blocking::RiotApi) instance.
+/// # Riot Developer API Reference
+/// <a href="https://developer.riotgames.com/apis#tft-summoner-v1" target="_blank">`tft-summoner-v1`</a>
+///
+/// Note: this struct is automatically generated.
+#[repr(transparent)]
+pub struct TftSummonerV1<'a> {
+    base: &'a RiotApi,
+}
+impl<'a> TftSummonerV1<'a> {
+    /// Get a summoner by account ID.
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// * `encrypted_account_id` (required, in path)
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#tft-summoner-v1/GET_getByAccountId" target="_blank">`tft-summoner-v1.getByAccountId`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_by_account_id(&self, route: PlatformRoute, encrypted_account_id: &str)
+        -> Result<tft_summoner_v1::Summoner>
+    {
+        self.base.block_on(self.base.inner.tft_summoner_v1().get_by_account_id(route, encrypted_account_id))
+    }
+
+    /// Get a summoner by summoner name.
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// * `summoner_name` (required, in path) - Summoner Name
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#tft-summoner-v1/GET_getBySummonerName" target="_blank">`tft-summoner-v1.getBySummonerName`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_by_summoner_name(&self, route: PlatformRoute, summoner_name: &str)
+        -> Result<Option<tft_summoner_v1::Summoner>>
+    {
+        self.base.block_on(self.base.inner.tft_summoner_v1().get_by_summoner_name(route, summoner_name))
+    }
+
+    /// Get a summoner by PUUID.
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// * `encrypted_puuid` (required, in path) - Summoner ID
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#tft-summoner-v1/GET_getByPUUID" target="_blank">`tft-summoner-v1.getByPUUID`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_by_puuid(&self, route: PlatformRoute, encrypted_puuid: &str)
+        -> Result<tft_summoner_v1::Summoner>
+    {
+        self.base.block_on(self.base.inner.tft_summoner_v1().get_by_puuid(route, encrypted_puuid))
+    }
+
+    /// Get a summoner by access token.
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// * `authorization` (optional, in header) - Bearer token.
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#tft-summoner-v1/GET_getByAccessToken" target="_blank">`tft-summoner-v1.getByAccessToken`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_by_access_token(&self, route: PlatformRoute, authorization: Option<&str>)
+        -> Result<tft_summoner_v1::Summoner>
+    {
+        self.base.block_on(self.base.inner.tft_summoner_v1().get_by_access_token(route, authorization))
+    }
+
+    /// Get a summoner by summoner ID.
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// * `encrypted_summoner_id` (required, in path) - Summoner ID
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#tft-summoner-v1/GET_getBySummonerId" target="_blank">`tft-summoner-v1.getBySummonerId`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_by_summoner_id(&self, route: PlatformRoute, encrypted_summoner_id: &str)
+        -> Result<tft_summoner_v1::Summoner>
+    {
+        self.base.block_on(self.base.inner.tft_summoner_v1().get_by_summoner_id(route, encrypted_summoner_id))
+    }
+
+}
+
+/// Blocking TournamentStubV5 endpoints handle, accessed by calling [`tournament_stub_v5()`](crate::blocking::RiotApi::tournament_stub_v5) on a [`blocking::RiotApi`](crate::blocking::RiotApi) instance.
+/// # Riot Developer API Reference
+/// <a href="https://developer.riotgames.com/apis#tournament-stub-v5" target="_blank">`tournament-stub-v5`</a>
+///
+/// Note: this struct is automatically generated.
+#[repr(transparent)]
+pub struct TournamentStubV5<'a> {
+    base: &'a RiotApi,
+}
+impl<'a> TournamentStubV5<'a> {
+    /// Create a tournament code for the given tournament - Stub method
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// * `count` (optional, in query) - The number of codes to create (max 1000)
+    /// * `tournament_id` (required, in query) - The tournament ID
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#tournament-stub-v5/POST_createTournamentCode" target="_blank">`tournament-stub-v5.createTournamentCode`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn create_tournament_code(&self, route: RegionalRoute, body: &tournament_stub_v5::TournamentCodeParametersV5, tournament_id: i64, count: Option<i32>)
+        -> Result<Vec<String>>
+    {
+        self.base.block_on(self.base.inner.tournament_stub_v5().create_tournament_code(route, body, tournament_id, count))
+    }
+
+    /// Returns the tournament code DTO associated with a tournament code string - Stub Method
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// * `tournament_code` (required, in path) - The tournament code string.
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#tournament-stub-v5/GET_getTournamentCode" target="_blank">`tournament-stub-v5.getTournamentCode`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_tournament_code(&self, route: RegionalRoute, tournament_code: &str)
+        -> Result<tournament_stub_v5::TournamentCodeV5>
+    {
+        self.base.block_on(self.base.inner.tournament_stub_v5().get_tournament_code(route, tournament_code))
+    }
+
+    /// Gets a list of lobby events by tournament code - Stub method
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// * `tournament_code` (required, in path) - The short code to look up lobby events for
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#tournament-stub-v5/GET_getLobbyEventsByCode" target="_blank">`tournament-stub-v5.getLobbyEventsByCode`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_lobby_events_by_code(&self, route: RegionalRoute, tournament_code: &str)
+        -> Result<tournament_stub_v5::LobbyEventV5Wrapper>
+    {
+        self.base.block_on(self.base.inner.tournament_stub_v5().get_lobby_events_by_code(route, tournament_code))
+    }
+
+    /// Creates a tournament provider and returns its ID - Stub method
+    /// ## Implementation Notes
+    /// Providers will need to call this endpoint first to register their callback URL and their API key with the tournament system before any other tournament provider endpoints will work.
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#tournament-stub-v5/POST_registerProviderData" target="_blank">`tournament-stub-v5.registerProviderData`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn register_provider_data(&self, route: RegionalRoute, body: &tournament_stub_v5::ProviderRegistrationParametersV5)
+        -> Result<i32>
+    {
+        self.base.block_on(self.base.inner.tournament_stub_v5().register_provider_data(route, body))
+    }
+
+    /// Creates a tournament and returns its ID - Stub method
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#tournament-stub-v5/POST_registerTournament" target="_blank">`tournament-stub-v5.registerTournament`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn register_tournament(&self, route: RegionalRoute, body: &tournament_stub_v5::TournamentRegistrationParametersV5)
+        -> Result<i32>
+    {
+        self.base.block_on(self.base.inner.tournament_stub_v5().register_tournament(route, body))
+    }
+
+}
+
+/// Blocking TournamentV5 endpoints handle, accessed by calling [`tournament_v5()`](crate::blocking::RiotApi::tournament_v5) on a [`blocking::RiotApi`](crate::blocking::RiotApi) instance.
+/// # Riot Developer API Reference
+/// <a href="https://developer.riotgames.com/apis#tournament-v5" target="_blank">`tournament-v5`</a>
+///
+/// Note: this struct is automatically generated.
+#[repr(transparent)]
+pub struct TournamentV5<'a> {
+    base: &'a RiotApi,
+}
+impl<'a> TournamentV5<'a> {
+    /// Create a tournament code for the given tournament.
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// * `tournament_id` (required, in query) - The tournament ID
+    /// * `count` (optional, in query) - The number of codes to create (max 1000)
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#tournament-v5/POST_createTournamentCode" target="_blank">`tournament-v5.createTournamentCode`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn create_tournament_code(&self, route: RegionalRoute, body: &tournament_v5::TournamentCodeParametersV5, tournament_id: i64, count: Option<i32>)
+        -> Result<Vec<String>>
+    {
+        self.base.block_on(self.base.inner.tournament_v5().create_tournament_code(route, body, tournament_id, count))
+    }
+
+    /// Returns the tournament code DTO associated with a tournament code string.
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// * `tournament_code` (required, in path) - The tournament code string.
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#tournament-v5/GET_getTournamentCode" target="_blank">`tournament-v5.getTournamentCode`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_tournament_code(&self, route: RegionalRoute, tournament_code: &str)
+        -> Result<tournament_v5::TournamentCodeV5>
+    {
+        self.base.block_on(self.base.inner.tournament_v5().get_tournament_code(route, tournament_code))
+    }
+
+    /// Update the pick type, map, spectator type, or allowed puuids for a code.
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// * `tournament_code` (required, in path) - The tournament code to update
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#tournament-v5/PUT_updateCode" target="_blank">`tournament-v5.updateCode`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn update_code(&self, route: RegionalRoute, body: &tournament_v5::TournamentCodeUpdateParametersV5, tournament_code: &str)
+        -> Result<()>
+    {
+        self.base.block_on(self.base.inner.tournament_v5().update_code(route, body, tournament_code))
+    }
+
+    /// Get games details
+    /// ## Implementation Notes
+    /// Additional endpoint to get tournament games. From this endpoint, you are able to get participants PUUID (the callback doesn't contain this info).
+    ///
+    /// You can also use it to check if the game was recorded and validate callbacks. If the endpoint returns the game, it means a callback was attempted.
+    ///
+    /// This will only work for tournament codes created after November 10, 2023.
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// * `tournament_code` (required, in path)
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#tournament-v5/GET_getGames" target="_blank">`tournament-v5.getGames`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_games(&self, route: RegionalRoute, tournament_code: &str)
+        -> Result<Vec<tournament_v5::TournamentGamesV5>>
+    {
+        self.base.block_on(self.base.inner.tournament_v5().get_games(route, tournament_code))
+    }
+
+    /// Gets a list of lobby events by tournament code.
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// * `tournament_code` (required, in path) - The short code to look up lobby events for
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#tournament-v5/GET_getLobbyEventsByCode" target="_blank">`tournament-v5.getLobbyEventsByCode`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_lobby_events_by_code(&self, route: RegionalRoute, tournament_code: &str)
+        -> Result<tournament_v5::LobbyEventV5Wrapper>
+    {
+        self.base.block_on(self.base.inner.tournament_v5().get_lobby_events_by_code(route, tournament_code))
+    }
+
+    /// Creates a tournament provider and returns its ID.
+    /// ## Implementation Notes
+    /// Providers will need to call this endpoint first to register their callback URL and their API key with the tournament system before any other tournament provider endpoints will work.
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#tournament-v5/POST_registerProviderData" target="_blank">`tournament-v5.registerProviderData`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn register_provider_data(&self, route: RegionalRoute, body: &tournament_v5::ProviderRegistrationParametersV5)
+        -> Result<i32>
+    {
+        self.base.block_on(self.base.inner.tournament_v5().register_provider_data(route, body))
+    }
+
+    /// Creates a tournament and returns its ID.
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#tournament-v5/POST_registerTournament" target="_blank">`tournament-v5.registerTournament`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn register_tournament(&self, route: RegionalRoute, body: &tournament_v5::TournamentRegistrationParametersV5)
+        -> Result<i32>
+    {
+        self.base.block_on(self.base.inner.tournament_v5().register_tournament(route, body))
+    }
+
+}
+
+/// Blocking ValContentV1 endpoints handle, accessed by calling [`val_content_v1()`](crate::blocking::RiotApi::val_content_v1) on a [`blocking::RiotApi`](crate::blocking::RiotApi) instance.
+/// # Riot Developer API Reference
+/// <a href="https://developer.riotgames.com/apis#val-content-v1" target="_blank">`val-content-v1`</a>
+///
+/// Note: this struct is automatically generated.
+#[repr(transparent)]
+pub struct ValContentV1<'a> {
+    base: &'a RiotApi,
+}
+impl<'a> ValContentV1<'a> {
+    /// Get content optionally filtered by locale
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// * `locale` (optional, in query)
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#val-content-v1/GET_getContent" target="_blank">`val-content-v1.getContent`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_content(&self, route: ValPlatformRoute, locale: Option<&str>)
+        -> Result<val_content_v1::Content>
+    {
+        self.base.block_on(self.base.inner.val_content_v1().get_content(route, locale))
+    }
+
+}
+
+/// Blocking ValMatchV1 endpoints handle, accessed by calling [`val_match_v1()`](crate::blocking::RiotApi::val_match_v1) on a [`blocking::RiotApi`](crate::blocking::RiotApi) instance.
+/// # Riot Developer API Reference
+/// <a href="https://developer.riotgames.com/apis#val-match-v1" target="_blank">`val-match-v1`</a>
+///
+/// Note: this struct is automatically generated.
+#[repr(transparent)]
+pub struct ValMatchV1<'a> {
+    base: &'a RiotApi,
+}
+impl<'a> ValMatchV1<'a> {
+    /// Get match by id
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// * `match_id` (required, in path)
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#val-match-v1/GET_getMatch" target="_blank">`val-match-v1.getMatch`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_match(&self, route: ValPlatformRoute, match_id: &str)
+        -> Result<Option<val_match_v1::Match>>
+    {
+        self.base.block_on(self.base.inner.val_match_v1().get_match(route, match_id))
+    }
+
+    /// Get matchlist for games played by puuid
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// * `puuid` (required, in path)
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#val-match-v1/GET_getMatchlist" target="_blank">`val-match-v1.getMatchlist`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_matchlist(&self, route: ValPlatformRoute, puuid: &str)
+        -> Result<val_match_v1::Matchlist>
+    {
+        self.base.block_on(self.base.inner.val_match_v1().get_matchlist(route, puuid))
+    }
+
+    /// Get recent matches
+    /// ## Implementation Notes
+    /// Returns a list of match ids that have completed in the last 10 minutes for live regions and 12 hours for the esports routing value. NA/LATAM/BR share a match history deployment. As such, recent matches will return a combined list of matches from those three regions. Requests are load balanced so you may see some inconsistencies as matches are added/removed from the list.
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// * `queue` (required, in path)
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#val-match-v1/GET_getRecent" target="_blank">`val-match-v1.getRecent`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_recent(&self, route: ValPlatformRoute, queue: &str)
+        -> Result<val_match_v1::RecentMatches>
+    {
+        self.base.block_on(self.base.inner.val_match_v1().get_recent(route, queue))
+    }
+
+}
+
+/// Blocking ValRankedV1 endpoints handle, accessed by calling [`val_ranked_v1()`](crate::blocking::RiotApi::val_ranked_v1) on a [`blocking::RiotApi`](crate::blocking::RiotApi) instance.
+/// # Riot Developer API Reference
+/// <a href="https://developer.riotgames.com/apis#val-ranked-v1" target="_blank">`val-ranked-v1`</a>
+///
+/// Note: this struct is automatically generated.
+#[repr(transparent)]
+pub struct ValRankedV1<'a> {
+    base: &'a RiotApi,
+}
+impl<'a> ValRankedV1<'a> {
+    /// Get leaderboard for the competitive queue
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// * `act_id` (required, in path) - Act ids can be found using the val-content API.
+    /// * `size` (optional, in query) - Defaults to 200. Valid values: 1 to 200.
+    /// * `start_index` (optional, in query) - Defaults to 0.
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#val-ranked-v1/GET_getLeaderboard" target="_blank">`val-ranked-v1.getLeaderboard`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_leaderboard(&self, route: ValPlatformRoute, act_id: &str, size: Option<i32>, start_index: Option<i32>)
+        -> Result<Option<val_ranked_v1::Leaderboard>>
+    {
+        self.base.block_on(self.base.inner.val_ranked_v1().get_leaderboard(route, act_id, size, start_index))
+    }
+
+}
+
+/// Blocking ValStatusV1 endpoints handle, accessed by calling [`val_status_v1()`](crate::blocking::RiotApi::val_status_v1) on a [`blocking::RiotApi`](crate::blocking::RiotApi) instance.
+/// # Riot Developer API Reference
+/// <a href="https://developer.riotgames.com/apis#val-status-v1" target="_blank">`val-status-v1`</a>
+///
+/// Note: this struct is automatically generated.
+#[repr(transparent)]
+pub struct ValStatusV1<'a> {
+    base: &'a RiotApi,
+}
+impl<'a> ValStatusV1<'a> {
+    /// Get VALORANT status for the given platform.
+    /// # Parameters
+    /// * `route` - Route to query.
+    /// # Riot Developer API Reference
+    /// <a href="https://developer.riotgames.com/api-methods/#val-status-v1/GET_getPlatformData" target="_blank">`val-status-v1.getPlatformData`</a>
+    ///
+    /// Note: this method is automatically generated.
+    pub fn get_platform_data(&self, route: ValPlatformRoute)
+        -> Result<val_status_v1::PlatformData>
+    {
+        self.base.block_on(self.base.inner.val_status_v1().get_platform_data(route))
+    }
+
+}
@@ -0,0 +1,104 @@
+//! Blocking (synchronous) API, enabled with the `blocking` feature.
+//!
+//! [`blocking::RiotApi`](RiotApi) has the same endpoint handles as the async
+//! [`RiotApi`](crate::RiotApi), e.g. [`match_v5()`](RiotApi::match_v5), but each endpoint
+//! method blocks the current thread and returns the result directly. Internally it wraps an
+//! async `RiotApi` and a Tokio runtime, so it has the same rate limiting, retries, and
+//! middleware. A single instance may be shared between many threads, e.g. with an
+//! [`Arc`](std::sync::Arc), and will share its rate limits between them.
+//!
+//! Blocking methods must not be called from within an async runtime, and will panic if they
+//! are.
+//!
+//! ```no_run
+//! use riven::blocking::RiotApi;
+//! use riven::consts::PlatformRoute;
+//!
+//! let riot_api = RiotApi::new("RGAPI-01234567-89ab-cdef-0123-456789abcdef");
+//! let summoner = riot_api
+//!     .summoner_v4()
+//!     .get_by_puuid(PlatformRoute::NA1, "puuid")
+//!     .unwrap();
+//! ```
+use std::future::Future;
+
+use tokio::runtime::Runtime;
+
+use crate::RiotApiConfig;
+
+#[rustfmt::skip]
+pub mod endpoints;
+
+/// Blocking counterpart of [`crate::RiotApi`], see the [module docs](self).
+pub struct RiotApi {
+    /// The async API which sends requests.
+    inner: crate::RiotApi,
+    /// Runtime which drives requests.
+    runtime: Runtime,
+}
+
+impl RiotApi {
+    /// Constructs a new instance from an API key (e.g.
+    /// `"RGAPI-01234567-89ab-cdef-0123-456789abcdef"`) or a [RiotApiConfig].
+    ///
+    /// # Panics
+    /// If the Tokio runtime cannot be created.
+    pub fn new(config: impl Into<RiotApiConfig>) -> Self {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .thread_name("riven-blocking")
+            .enable_all()
+            .build()
+            .expect("Failed to create Tokio runtime.");
+        Self {
+            inner: crate::RiotApi::new(config),
+            runtime,
+        }
+    }
+
+    /// The wrapped async [`RiotApi`](crate::RiotApi), for example to check its rate limit
+    /// status or to replace its API key.
+    pub fn async_api(&self) -> &crate::RiotApi {
+        &self.inner
+    }
+
+    /// Runs `future` to completion on the runtime, blocking the current thread.
+    fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.runtime.block_on(future)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use reqwest::StatusCode;
+
+    use super::*;
+    use crate::consts::RegionalRoute;
+    use crate::transport::{MockResponse, MockTransport};
+
+    #[test]
+    fn blocking_from_threads() {
+        let transport = Arc::new(
+            MockTransport::new().set_default_response(
+                MockResponse::new(StatusCode::OK)
+                    .set_body(r#"{"puuid":"abc","gameName":"Riven","tagLine":"NA1"}"#),
+            ),
+        );
+        let riot_api =
+            RiotApi::new(RiotApiConfig::with_key("RGAPI-test").set_transport(transport.clone()));
+        std::thread::scope(|scope| {
+            for _ in 0..2 {
+                scope.spawn(|| {
+                    let account = riot_api
+                        .account_v1()
+                        .get_by_puuid(RegionalRoute::AMERICAS, "abc")
+                        .unwrap();
+                    assert_eq!(Some("Riven"), account.game_name.as_deref());
+                });
+            }
+        });
+        assert_eq!(2, transport.requests().len());
+    }
+}
@@ -0,0 +1,132 @@
+use std::collections::{BTreeMap, HashMap};
+
+use futures::future::{self, BoxFuture};
+use parking_lot::Mutex;
+
+use super::{CacheStore, CachedResponse};
+use crate::time::{Duration, Instant};
+
+/// [`CacheStore`] which keeps responses in process memory, evicting the least recently used
+/// response once `capacity` responses are cached.
+#[derive(Debug)]
+pub struct MemoryCacheStore {
+    capacity: usize,
+    inner: Mutex<Lru>,
+}
+
+#[derive(Debug, Default)]
+struct Lru {
+    /// Entries by key, with their expiry (`None` for never) and the tick they were last used.
+    entries: HashMap<String, (CachedResponse, Option<Instant>, u64)>,
+    /// Keys by the tick they were last used, least recent first.
+    order: BTreeMap<u64, String>,
+    /// Incremented each time an entry is used.
+    tick: u64,
+}
+
+impl Lru {
+    fn remove(&mut self, key: &str) {
+        if let Some((_, _, tick)) = self.entries.remove(key) {
+            self.order.remove(&tick);
+        }
+    }
+}
+
+impl MemoryCacheStore {
+    /// Creates a new, empty `MemoryCacheStore` which holds up to `capacity` responses.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            inner: Mutex::new(Lru::default()),
+        }
+    }
+
+    /// Synchronous version of [`CacheStore::get`].
+    fn get_sync(&self, key: &str) -> Option<CachedResponse> {
+        let mut lru = self.inner.lock();
+        let lru = &mut *lru;
+        let (response, expires, tick) = lru.entries.get_mut(key)?;
+        if expires.is_some_and(|expires| expires <= Instant::now()) {
+            lru.remove(key);
+            return None;
+        }
+        // Mark as most recently used.
+        lru.order.remove(tick);
+        lru.tick += 1;
+        *tick = lru.tick;
+        lru.order.insert(lru.tick, key.to_owned());
+        Some(response.clone())
+    }
+
+    /// Synchronous version of [`CacheStore::put`].
+    fn put_sync(&self, key: &str, response: CachedResponse, ttl: Duration) {
+        if 0 == self.capacity {
+            return;
+        }
+        let mut lru = self.inner.lock();
+        lru.remove(key);
+        while self.capacity <= lru.entries.len() {
+            let Some((_, oldest)) = lru.order.pop_first() else {
+                break;
+            };
+            lru.entries.remove(&oldest);
+        }
+        // Treat TTLs too large to represent, such as `Duration::MAX`, as never expiring.
+        let expires = Instant::now().checked_add(ttl);
+        lru.tick += 1;
+        let tick = lru.tick;
+        lru.entries
+            .insert(key.to_owned(), (response, expires, tick));
+        lru.order.insert(tick, key.to_owned());
+    }
+}
+
+impl CacheStore for MemoryCacheStore {
+    fn get<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Option<CachedResponse>> {
+        Box::pin(future::ready(self.get_sync(key)))
+    }
+
+    fn put<'a>(
+        &'a self,
+        key: &'a str,
+        response: CachedResponse,
+        ttl: Duration,
+    ) -> BoxFuture<'a, ()> {
+        self.put_sync(key, response, ttl);
+        Box::pin(future::ready(()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn response(body: &str) -> CachedResponse {
+        CachedResponse {
+            status: 200,
+            headers: Vec::new(),
+            body: body.as_bytes().to_owned(),
+        }
+    }
+
+    #[test]
+    fn lru_eviction_and_expiry() {
+        let store = MemoryCacheStore::new(2);
+        let ttl = Duration::from_secs(60);
+        store.put_sync("a", response("a"), ttl);
+        store.put_sync("b", response("b"), ttl);
+        // Use "a", so "b" is evicted.
+        assert_eq!(Some(response("a")), store.get_sync("a"));
+        store.put_sync("c", response("c"), ttl);
+        assert_eq!(None, store.get_sync("b"));
+        assert_eq!(Some(response("a")), store.get_sync("a"));
+        assert_eq!(Some(response("c")), store.get_sync("c"));
+
+        store.put_sync("a", response("a"), Duration::ZERO);
+        assert_eq!(None, store.get_sync("a"));
+        assert_eq!(1, store.inner.lock().entries.len());
+
+        store.put_sync("d", response("d"), Duration::MAX);
+        assert_eq!(Some(response("d")), store.get_sync("d"));
+    }
+}
@@ -0,0 +1,272 @@
+//! Opt-in cache for responses of effectively static endpoints.
+//!
+//! [`ResponseCache`] is a [`Middleware`] which caches successful `GET` responses, keyed on
+//! method ID, route, and full request URL. Only methods with a configured TTL are cached, for
+//! example `champion-v3.getChampionInfo` or `match-v5.getMatch`. Requests with an
+//! `Authorization` header, such as RSO requests on behalf of a user, are never cached, as
+//! their responses are specific to that user. Raw response bodies are
+//! stored, so both typed endpoint methods and
+//! [`RiotApi::execute_raw`](crate::RiotApi::execute_raw) consumers benefit. Cache hits are
+//! returned without sending a request, and therefore do not use any rate limit tokens.
+//!
+//! Responses are kept in a [`CacheStore`], by default a [`MemoryCacheStore`] LRU cache.
+//!
+//! ```
+//! use std::sync::Arc;
+//!
+//! use riven::cache::{MemoryCacheStore, ResponseCache};
+//! use riven::time::Duration;
+//! use riven::RiotApiConfig;
+//!
+//! let cache = ResponseCache::new(Arc::new(MemoryCacheStore::new(10_000)))
+//!     .set_ttl("champion-v3.getChampionInfo", Duration::from_secs(60 * 60))
+//!     .set_ttl("match-v5.getMatch", Duration::from_secs(24 * 60 * 60));
+//! let config = RiotApiConfig::with_key("RGAPI-...").add_middleware(Arc::new(cache));
+//! ```
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use futures::future::BoxFuture;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, AUTHORIZATION};
+use reqwest::{Method, ResponseBuilderExt, StatusCode, Url};
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "tracing")]
+use tracing as log;
+
+use crate::middleware::{Middleware, MiddlewareFuture, MiddlewareRequest, Next};
+use crate::time::Duration;
+use crate::{ResponseInfo, RiotApiError};
+
+mod memory;
+pub use memory::MemoryCacheStore;
+
+/// Storage backend for cached responses.
+pub trait CacheStore: fmt::Debug + Send + Sync {
+    /// Gets the response cached under `key`, if any and not expired.
+    fn get<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Option<CachedResponse>>;
+
+    /// Caches `response` under `key`, expiring after `ttl`.
+    fn put<'a>(
+        &'a self,
+        key: &'a str,
+        response: CachedResponse,
+        ttl: Duration,
+    ) -> BoxFuture<'a, ()>;
+}
+
+/// A raw cached response.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CachedResponse {
+    /// HTTP status code.
+    pub status: u16,
+    /// Response headers.
+    pub headers: Vec<(String, Vec<u8>)>,
+    /// Response body.
+    pub body: Vec<u8>,
+}
+
+impl CachedResponse {
+    /// Reads the entire `response` body.
+    pub(crate) async fn from_response(response: reqwest::Response) -> reqwest::Result<Self> {
+        let status = response.status().as_u16();
+        let headers = response
+            .headers()
+            .iter()
+            .map(|(name, value)| (name.as_str().to_owned(), value.as_bytes().to_owned()))
+            .collect();
+        let body = response.bytes().await?.to_vec();
+        Ok(Self {
+            status,
+            headers,
+            body,
+        })
+    }
+
+    /// Converts this back into a `reqwest::Response`.
+    pub(crate) fn into_response(self) -> reqwest::Response {
+        self.into_http_response(http::Response::builder())
+    }
+
+    /// Converts this back into a `reqwest::Response`, with `url` as the response URL.
+    pub(crate) fn into_response_with_url(self, url: Url) -> reqwest::Response {
+        self.into_http_response(http::Response::builder().url(url))
+    }
+
+    fn into_http_response(self, builder: http::response::Builder) -> reqwest::Response {
+        let mut response = builder.body(self.body).expect("Failed to build response.");
+        *response.status_mut() = StatusCode::from_u16(self.status).unwrap_or(StatusCode::OK);
+        let headers: &mut HeaderMap = response.headers_mut();
+        for (name, value) in self.headers {
+            if let (Ok(name), Ok(value)) = (
+                HeaderName::from_bytes(name.as_bytes()),
+                HeaderValue::from_bytes(&value),
+            ) {
+                headers.append(name, value);
+            }
+        }
+        response.into()
+    }
+}
+
+/// [`Middleware`] which caches responses, see the [module docs](self).
+#[derive(Debug)]
+pub struct ResponseCache {
+    store: Arc<dyn CacheStore>,
+    ttls: HashMap<&'static str, Duration>,
+    default_ttl: Option<Duration>,
+}
+
+impl ResponseCache {
+    /// Creates a new `ResponseCache` using the given `store`. No methods are cached until
+    /// TTLs are set with [`Self::set_ttl`] or [`Self::set_default_ttl`].
+    pub fn new(store: Arc<dyn CacheStore>) -> Self {
+        Self {
+            store,
+            ttls: HashMap::new(),
+            default_ttl: None,
+        }
+    }
+
+    /// Caches responses of the method `method_id`, e.g. `"match-v5.getMatch"`, for `ttl`.
+    ///
+    /// # Returns
+    /// `self`, for chaining.
+    pub fn set_ttl(mut self, method_id: &'static str, ttl: Duration) -> Self {
+        self.ttls.insert(method_id, ttl);
+        self
+    }
+
+    /// Caches responses of all methods without a TTL set by [`Self::set_ttl`] for
+    /// `default_ttl`.
+    ///
+    /// # Returns
+    /// `self`, for chaining.
+    pub fn set_default_ttl(mut self, default_ttl: Duration) -> Self {
+        self.default_ttl = Some(default_ttl);
+        self
+    }
+
+    async fn handle_cached<'a>(
+        &'a self,
+        request: MiddlewareRequest,
+        next: Next<'a>,
+        url: Url,
+        ttl: Duration,
+    ) -> crate::Result<ResponseInfo> {
+        let key = format!("{} {} {}", request.method_id, request.route, url);
+        if let Some(cached) = self.store.get(&key).await {
+            log::trace!("Cache hit: {}", key);
+            return Ok(ResponseInfo {
+                // Use the request URL, so cache hits look the same as live responses.
+                response: cached.into_response_with_url(url),
+                retries: 0,
+                status_none: false,
+            });
+        }
+
+        let rinfo = next.run(request).await?;
+        if rinfo.status_none {
+            return Ok(rinfo);
+        }
+        let retries = rinfo.retries;
+        let status = rinfo.response.status();
+        let url = rinfo.response.url().clone();
+        let cached = CachedResponse::from_response(rinfo.response)
+            .await
+            .map_err(|e| RiotApiError::new(e, retries, None, Some(status)))?;
+        self.store.put(&key, cached.clone(), ttl).await;
+        Ok(ResponseInfo {
+            response: cached.into_response_with_url(url),
+            retries,
+            status_none: false,
+        })
+    }
+}
+
+impl Middleware for ResponseCache {
+    fn handle<'a>(&'a self, request: MiddlewareRequest, next: Next<'a>) -> MiddlewareFuture<'a> {
+        let ttl = self
+            .ttls
+            .get(request.method_id)
+            .copied()
+            .or(self.default_ttl);
+        let url = request
+            .request
+            .try_clone()
+            .and_then(|request| request.build().ok())
+            .filter(|request| Method::GET == request.method())
+            // Don't share user-specific responses between users.
+            .filter(|request| !request.headers().contains_key(AUTHORIZATION))
+            .map(|request| request.url().clone());
+        match (ttl, url) {
+            (Some(ttl), Some(url)) => Box::pin(self.handle_cached(request, next, url, ttl)),
+            _ => next.run(request),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "tokio", not(target_family = "wasm")))]
+mod test {
+    use reqwest::StatusCode;
+
+    use super::*;
+    use crate::transport::{MockResponse, MockTransport};
+    use crate::{RiotApi, RiotApiConfig};
+
+    /// Creates a transport which returns `1`, `2`, ..., `n` for successive requests.
+    fn counting_transport(n: usize) -> Arc<MockTransport> {
+        let transport = Arc::new(MockTransport::new());
+        for count in 1..=n {
+            transport.push_response(MockResponse::new(StatusCode::OK).set_body(count.to_string()));
+        }
+        transport
+    }
+
+    #[tokio::test]
+    async fn cache_hit() {
+        let cache = ResponseCache::new(Arc::new(MemoryCacheStore::new(10)))
+            .set_ttl("test.cached", Duration::from_secs(60));
+        let config = RiotApiConfig::with_key("RGAPI-test")
+            .set_transport(counting_transport(4))
+            .add_middleware(Arc::new(cache));
+        let riot_api = RiotApi::new(config);
+
+        let get = |method_id, path| {
+            let request = riot_api.request(Method::GET, "NA1", path);
+            riot_api.execute_val::<usize>(method_id, "NA1", request)
+        };
+        assert_eq!(1, get("test.cached", "/a").await.unwrap());
+        assert_eq!(1, get("test.cached", "/a").await.unwrap());
+        assert_eq!(2, get("test.cached", "/b").await.unwrap());
+        assert_eq!(3, get("test.uncached", "/a").await.unwrap());
+        assert_eq!(4, get("test.uncached", "/a").await.unwrap());
+
+        let request = riot_api.request(Method::GET, "NA1", "/a");
+        let rinfo = riot_api
+            .execute_raw("test.cached", "NA1", request)
+            .await
+            .unwrap();
+        assert_eq!("/a", rinfo.response.url().path());
+    }
+
+    #[tokio::test]
+    async fn authorized_uncached() {
+        let cache = ResponseCache::new(Arc::new(MemoryCacheStore::new(10)))
+            .set_ttl("test.cached", Duration::from_secs(60));
+        let config = RiotApiConfig::with_key("RGAPI-test")
+            .set_transport(counting_transport(3))
+            .add_middleware(Arc::new(cache));
+        let riot_api = RiotApi::new(config);
+
+        let get = |token| {
+            let request = riot_api
+                .request(Method::GET, "NA1", "/a")
+                .bearer_auth(token);
+            riot_api.execute_val::<usize>("test.cached", "NA1", request)
+        };
+        assert_eq!(1, get("token-a").await.unwrap());
+        assert_eq!(2, get("token-b").await.unwrap());
+        assert_eq!(3, get("token-a").await.unwrap());
+    }
+}
@@ -0,0 +1,459 @@
+//! Record-and-replay of requests and responses, for offline tests.
+//!
+//! [`Cassette`] is a [`Middleware`] with two modes:
+//! * [`Cassette::record`] passes requests through as usual, and writes each request (method
+//!   ID, route, HTTP method, URL, and body) and its final response (status, headers, and body)
+//!   to a fixture file. Request headers, including the API key, are not recorded.
+//!   Fixture files are [JSON Lines](https://jsonlines.org/), with one entry appended per
+//!   request, so recording does not depend on the `Cassette` being dropped.
+//! * [`Cassette::replay`] loads a fixture file and serves the recorded responses, without
+//!   sending any requests. Replayed requests are not rate limited.
+//!
+//! In replay mode, each request is matched against the recorded requests with the same
+//! method ID, route, HTTP method, URL, and body. If a request was recorded multiple times,
+//! the responses are returned in recorded order, with the last response repeated once they
+//! run out. Requests which were not recorded fail with a [`CassetteMiss`] middleware error,
+//! see [`RiotApiError::source_middleware_error`].
+//!
+//! Bodies and header values are stored as strings when they are valid UTF-8, and otherwise as
+//! base64, so they are replayed byte-for-byte.
+//!
+//! Responses are recorded after Riven's retries, and requests which failed without a
+//! response, e.g. connection errors, are not recorded.
+//!
+//! ```no_run
+//! use std::sync::Arc;
+//!
+//! use riven::cassette::Cassette;
+//! use riven::RiotApiConfig;
+//!
+//! let cassette = Cassette::replay("tests/cassettes/tests_na.jsonl").unwrap();
+//! let config = RiotApiConfig::with_key("RGAPI-...").add_middleware(Arc::new(cassette));
+//! ```
+use std::convert::TryFrom;
+use std::fmt;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use parking_lot::Mutex;
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "tracing")]
+use tracing as log;
+
+use crate::cache::CachedResponse;
+use crate::middleware::{Middleware, MiddlewareFuture, MiddlewareRequest, Next};
+use crate::req::RegionalRequester;
+use crate::{ResponseInfo, Result, RiotApiError};
+
+/// A recorded request, used as the key to match replayed requests.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct CassetteRequest {
+    method_id: String,
+    route: String,
+    method: String,
+    url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    body: Option<CassetteBytes>,
+}
+
+impl CassetteRequest {
+    /// Converts `response` back into a `reqwest::Response`, with this request's URL.
+    fn response(&self, response: CachedResponse) -> reqwest::Response {
+        match Url::parse(&self.url) {
+            Ok(url) => response.into_response_with_url(url),
+            Err(_) => response.into_response(),
+        }
+    }
+}
+
+/// A recorded response.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CassetteResponse {
+    status: u16,
+    headers: Vec<(String, CassetteBytes)>,
+    body: CassetteBytes,
+}
+
+impl From<CachedResponse> for CassetteResponse {
+    fn from(response: CachedResponse) -> Self {
+        Self {
+            status: response.status,
+            headers: response
+                .headers
+                .into_iter()
+                .map(|(name, value)| (name, CassetteBytes(value)))
+                .collect(),
+            body: CassetteBytes(response.body),
+        }
+    }
+}
+
+impl From<CassetteResponse> for CachedResponse {
+    fn from(response: CassetteResponse) -> Self {
+        Self {
+            status: response.status,
+            headers: response
+                .headers
+                .into_iter()
+                .map(|(name, value)| (name, value.0))
+                .collect(),
+            body: response.body.0,
+        }
+    }
+}
+
+/// Raw bytes, serialized as a string if valid UTF-8, otherwise as `{ "base64": "..." }`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "BytesRepr", into = "BytesRepr")]
+struct CassetteBytes(Vec<u8>);
+
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum BytesRepr {
+    Utf8(String),
+    Base64 { base64: String },
+}
+
+impl From<CassetteBytes> for BytesRepr {
+    fn from(bytes: CassetteBytes) -> Self {
+        match String::from_utf8(bytes.0) {
+            Ok(string) => Self::Utf8(string),
+            Err(e) => Self::Base64 {
+                base64: BASE64.encode(e.as_bytes()),
+            },
+        }
+    }
+}
+
+impl TryFrom<BytesRepr> for CassetteBytes {
+    type Error = base64::DecodeError;
+    fn try_from(repr: BytesRepr) -> std::result::Result<Self, Self::Error> {
+        match repr {
+            BytesRepr::Utf8(string) => Ok(Self(string.into_bytes())),
+            BytesRepr::Base64 { base64 } => BASE64.decode(base64).map(Self),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CassetteEntry {
+    request: CassetteRequest,
+    response: CassetteResponse,
+}
+
+#[derive(Debug)]
+enum Mode {
+    /// The fixture file, opened (and truncated) when the first entry is recorded.
+    Record(Mutex<Option<File>>),
+    /// Loaded entries, with whether each has been replayed.
+    Replay(Mutex<Vec<(CassetteEntry, bool)>>),
+}
+
+/// [`Middleware`] which records or replays requests, see the [module docs](self).
+#[derive(Debug)]
+pub struct Cassette {
+    path: PathBuf,
+    mode: Mode,
+}
+
+impl Cassette {
+    /// Creates a `Cassette` which records requests to the fixture file at `path`. The file is
+    /// overwritten when the first request is recorded, then each request is appended as it
+    /// completes.
+    pub fn record(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            mode: Mode::Record(Mutex::new(None)),
+        }
+    }
+
+    /// Creates a `Cassette` which replays requests from the fixture file at `path`.
+    ///
+    /// # Returns
+    /// An error if the file could not be read or is not a valid fixture file.
+    pub fn replay(path: impl Into<PathBuf>) -> std::io::Result<Self> {
+        let path = path.into();
+        let mut entries = Vec::new();
+        for line in BufReader::new(File::open(&path)?).lines() {
+            let line = line?;
+            if !line.trim().is_empty() {
+                entries.push((serde_json::from_str(&line)?, false));
+            }
+        }
+        Ok(Self {
+            path,
+            mode: Mode::Replay(Mutex::new(entries)),
+        })
+    }
+
+    /// The path of the fixture file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Finds the next recorded response for `request`.
+    fn find(&self, request: &CassetteRequest) -> Option<CassetteResponse> {
+        let Mode::Replay(entries) = &self.mode else {
+            return None;
+        };
+        let mut entries = entries.lock();
+        let mut matching = entries
+            .iter_mut()
+            .filter(|(entry, _)| &entry.request == request)
+            .peekable();
+        while let Some((entry, used)) = matching.next() {
+            // Use the first unused entry, or repeat the last.
+            if !*used || matching.peek().is_none() {
+                *used = true;
+                return Some(entry.response.clone());
+            }
+        }
+        None
+    }
+
+    /// Appends an entry to the fixture file.
+    fn append(&self, request: CassetteRequest, response: CassetteResponse) {
+        let Mode::Record(file) = &self.mode else {
+            return;
+        };
+        let result = serde_json::to_vec(&CassetteEntry { request, response })
+            .map_err(std::io::Error::from)
+            .and_then(|mut line| {
+                line.push(b'\n');
+                let mut file = file.lock();
+                let file = match &mut *file {
+                    Some(file) => file,
+                    None => file.insert(
+                        OpenOptions::new()
+                            .write(true)
+                            .create(true)
+                            .truncate(true)
+                            .open(&self.path)?,
+                    ),
+                };
+                file.write_all(&line)
+            });
+        if let Err(e) = result {
+            log::warn!("Failed to write cassette {}: {}", self.path.display(), e);
+        }
+    }
+
+    async fn replay_response(&self, request: CassetteRequest) -> Result<ResponseInfo> {
+        let Some(recorded) = self.find(&request) else {
+            log::debug!("Cassette miss: {} {}", request.method, request.url);
+            return Err(RiotApiError::from_middleware(
+                CassetteMiss {
+                    method_id: request.method_id,
+                    url: request.url,
+                },
+                None,
+            ));
+        };
+        let response = request.response(recorded.into());
+        let status = response.status();
+        let status_none = RegionalRequester::NONE_STATUS_CODES.contains(&status);
+        match response.error_for_status_ref() {
+            Err(e) if !status_none => Err(RiotApiError::from_response(e, 0, response).await),
+            _ => Ok(ResponseInfo {
+                response,
+                retries: 0,
+                status_none,
+            }),
+        }
+    }
+
+    async fn handle_record<'a>(
+        &'a self,
+        request: MiddlewareRequest,
+        next: Next<'a>,
+        key: CassetteRequest,
+    ) -> Result<ResponseInfo> {
+        match next.run(request).await {
+            Ok(rinfo) => {
+                let retries = rinfo.retries;
+                let status = rinfo.response.status();
+                let cached = CachedResponse::from_response(rinfo.response)
+                    .await
+                    .map_err(|e| RiotApiError::new(e, retries, None, Some(status)))?;
+                let response = key.response(cached.clone());
+                self.append(key, cached.into());
+                Ok(ResponseInfo { response, ..rinfo })
+            }
+            Err(mut e) => {
+                if let Some(response) = e.take_response() {
+                    if let Ok(cached) = CachedResponse::from_response(response).await {
+                        e.set_response(key.response(cached.clone()));
+                        self.append(key, cached.into());
+                    }
+                }
+                Err(e)
+            }
+        }
+    }
+}
+
+impl Middleware for Cassette {
+    fn handle<'a>(&'a self, request: MiddlewareRequest, next: Next<'a>) -> MiddlewareFuture<'a> {
+        let built = request
+            .request
+            .try_clone()
+            .and_then(|request| request.build().ok());
+        let Some(built) = built else {
+            return next.run(request);
+        };
+        let key = CassetteRequest {
+            method_id: request.method_id.to_owned(),
+            route: request.route.to_owned(),
+            method: built.method().to_string(),
+            url: built.url().to_string(),
+            body: built
+                .body()
+                .and_then(|body| body.as_bytes())
+                .map(|body| CassetteBytes(body.to_vec())),
+        };
+        match &self.mode {
+            Mode::Record(_) => Box::pin(self.handle_record(request, next, key)),
+            Mode::Replay(_) => Box::pin(self.replay_response(key)),
+        }
+    }
+}
+
+/// Middleware error when a [`Cassette`] in replay mode has no recorded response for a
+/// request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CassetteMiss {
+    method_id: String,
+    url: String,
+}
+impl CassetteMiss {
+    /// The method ID of the request.
+    pub fn method_id(&self) -> &str {
+        &self.method_id
+    }
+    /// The URL of the request.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+}
+impl fmt::Display for CassetteMiss {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "no recorded response in cassette for {} {}",
+            self.method_id, self.url
+        )
+    }
+}
+impl std::error::Error for CassetteMiss {}
+
+#[cfg(all(test, feature = "tokio", not(target_family = "wasm")))]
+mod test {
+    use std::sync::Arc;
+
+    use reqwest::{Method, StatusCode};
+
+    use super::*;
+    use crate::transport::{MockResponse, MockTransport};
+    use crate::{RiotApi, RiotApiConfig};
+
+    async fn get(riot_api: &RiotApi, path: &str) -> Result<u32> {
+        let request = riot_api.request(Method::GET, "na1", path);
+        riot_api.execute_val("test.method", "NA1", request).await
+    }
+
+    #[tokio::test]
+    async fn record_replay() {
+        let path =
+            std::env::temp_dir().join(format!("riven-cassette-{}.jsonl", std::process::id()));
+
+        let transport = Arc::new(MockTransport::new());
+        transport.push_response(MockResponse::new(StatusCode::OK).set_body("1"));
+        transport.push_response(MockResponse::new(StatusCode::OK).set_body("2"));
+        transport.push_response(MockResponse::new(StatusCode::FORBIDDEN));
+        let riot_api = RiotApi::new(
+            RiotApiConfig::with_key("RGAPI-test")
+                .set_transport(transport)
+                .add_middleware(Arc::new(Cassette::record(&path))),
+        );
+        assert_eq!(1, get(&riot_api, "/a").await.unwrap());
+        assert_eq!(2, get(&riot_api, "/a").await.unwrap());
+        assert_eq!(
+            Some(StatusCode::FORBIDDEN),
+            get(&riot_api, "/b").await.unwrap_err().status_code()
+        );
+
+        // Entries are written as they are recorded, while the recording cassette is alive.
+        let recorded = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(3, recorded.lines().count());
+
+        // No transport responses, so everything must come from the cassette.
+        let riot_api = RiotApi::new(
+            RiotApiConfig::with_key("RGAPI-test")
+                .set_transport(Arc::new(MockTransport::new()))
+                .add_middleware(Arc::new(Cassette::replay(&path).unwrap())),
+        );
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(1, get(&riot_api, "/a").await.unwrap());
+        assert_eq!(2, get(&riot_api, "/a").await.unwrap());
+        assert_eq!(2, get(&riot_api, "/a").await.unwrap());
+        let err = get(&riot_api, "/b").await.unwrap_err();
+        assert_eq!(Some(StatusCode::FORBIDDEN), err.status_code());
+        assert_eq!("/b", err.response().unwrap().url().path());
+        let err = get(&riot_api, "/c").await.unwrap_err();
+        let miss = err.source_middleware_error().unwrap();
+        assert!(miss.downcast_ref::<CassetteMiss>().is_some());
+    }
+
+    #[tokio::test]
+    async fn non_utf8_round_trip() {
+        let path =
+            std::env::temp_dir().join(format!("riven-cassette-bytes-{}.jsonl", std::process::id()));
+        let bytes: &[u8] = b"\xff\xfe riven \x80";
+
+        async fn send(riot_api: &RiotApi, body: &[u8]) -> Vec<u8> {
+            let request = riot_api
+                .request(Method::POST, "na1", "/bytes")
+                .body(body.to_vec());
+            let rinfo = riot_api
+                .execute_raw("test.method", "NA1", request)
+                .await
+                .unwrap();
+            rinfo.response.bytes().await.unwrap().to_vec()
+        }
+
+        let transport = Arc::new(MockTransport::new());
+        transport.push_response(MockResponse::new(StatusCode::OK).set_body(bytes));
+        let riot_api = RiotApi::new(
+            RiotApiConfig::with_key("RGAPI-test")
+                .set_transport(transport)
+                .add_middleware(Arc::new(Cassette::record(&path))),
+        );
+        assert_eq!(bytes, send(&riot_api, bytes).await);
+        assert!(std::fs::read_to_string(&path).unwrap().contains("base64"));
+
+        let riot_api = RiotApi::new(
+            RiotApiConfig::with_key("RGAPI-test")
+                .set_transport(Arc::new(MockTransport::new()))
+                .add_middleware(Arc::new(Cassette::replay(&path).unwrap())),
+        );
+        std::fs::remove_file(&path).unwrap();
+        // Matches the exact request body, and replays the exact response body.
+        assert_eq!(bytes, send(&riot_api, bytes).await);
+        let err = riot_api
+            .execute_raw(
+                "test.method",
+                "NA1",
+                riot_api
+                    .request(Method::POST, "na1", "/bytes")
+                    .body(String::from_utf8_lossy(bytes).into_owned()),
+            )
+            .await
+            .err()
+            .unwrap();
+        assert!(err.source_middleware_error().is_some());
+    }
+}
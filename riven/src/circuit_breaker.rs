@@ -0,0 +1,350 @@
+//! Circuit breakers, which stop sending requests to a failing method.
+//!
+//! When enabled with
+//! [`RiotApiConfig::set_circuit_breaker`](crate::RiotApiConfig::set_circuit_breaker), each
+//! route and method (e.g. `KR` `match-v5.getMatch`) gets its own circuit breaker, which tracks
+//! the outcomes of recent requests. Server errors (5xx) and connection errors count as
+//! failures, any other response counts as a success.
+//!
+//! * [`CircuitState::Closed`]: Requests are sent normally. If the failure rate of the recent
+//!   requests reaches the configured threshold, the circuit opens.
+//! * [`CircuitState::Open`]: Requests fail immediately, without being sent, with an error where
+//!   [`RiotApiError::is_circuit_open`](crate::RiotApiError::is_circuit_open) is true. After
+//!   the configured duration, the circuit becomes half-open.
+//! * [`CircuitState::HalfOpen`]: A limited number of probe requests are sent. If a probe
+//!   succeeds the circuit closes, if it fails the circuit opens again.
+//!
+//! The state of a route's circuit breakers can be inspected with
+//! [`RiotApi::circuit_breaker_status`](crate::RiotApi::circuit_breaker_status).
+use std::collections::VecDeque;
+
+use parking_lot::Mutex;
+#[cfg(feature = "tracing")]
+use tracing as log;
+
+use crate::time::{Duration, Instant};
+use crate::CircuitOpen;
+
+/// Configuration for circuit breakers, see the [module docs](self).
+#[derive(Debug, Clone)]
+pub struct CircuitBreakerConfig {
+    window: usize,
+    min_requests: usize,
+    failure_rate: f32,
+    open_duration: Duration,
+    half_open_probes: usize,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CircuitBreakerConfig {
+    /// Creates a new `CircuitBreakerConfig` with the following settings:
+    ///
+    /// * `window = 20` requests.
+    /// * `min_requests = 10`.
+    /// * `failure_rate = 0.5`.
+    /// * `open_duration = 30 sec`.
+    /// * `half_open_probes = 1`.
+    pub fn new() -> Self {
+        Self {
+            window: 20,
+            min_requests: 10,
+            failure_rate: 0.5,
+            open_duration: Duration::from_secs(30),
+            half_open_probes: 1,
+        }
+    }
+
+    /// Sets the number of most recent requests used to compute the failure rate.
+    ///
+    /// # Panics
+    /// If `window` is zero.
+    ///
+    /// # Returns
+    /// `self`, for chaining.
+    pub fn set_window(mut self, window: usize) -> Self {
+        assert!(0 < window, "window must be non-zero.");
+        self.window = window;
+        self
+    }
+
+    /// Sets the minimum number of requests within the window before the circuit may open.
+    ///
+    /// # Returns
+    /// `self`, for chaining.
+    pub fn set_min_requests(mut self, min_requests: usize) -> Self {
+        self.min_requests = min_requests;
+        self
+    }
+
+    /// Sets the failure rate at which the circuit opens.
+    ///
+    /// # Panics
+    /// If `failure_rate` is not in range (0, 1\].
+    ///
+    /// # Returns
+    /// `self`, for chaining.
+    pub fn set_failure_rate(mut self, failure_rate: f32) -> Self {
+        // Use inverted check to handle NaN.
+        if 0.0 < failure_rate && failure_rate <= 1.0 {
+            self.failure_rate = failure_rate;
+            return self;
+        }
+        panic!("failure_rate \"{}\" not in range (0, 1].", failure_rate);
+    }
+
+    /// Sets how long the circuit stays open before becoming half-open.
+    ///
+    /// # Returns
+    /// `self`, for chaining.
+    pub fn set_open_duration(mut self, open_duration: Duration) -> Self {
+        self.open_duration = open_duration;
+        self
+    }
+
+    /// Sets the maximum number of concurrent probe requests while half-open.
+    ///
+    /// # Panics
+    /// If `half_open_probes` is zero.
+    ///
+    /// # Returns
+    /// `self`, for chaining.
+    pub fn set_half_open_probes(mut self, half_open_probes: usize) -> Self {
+        assert!(0 < half_open_probes, "half_open_probes must be non-zero.");
+        self.half_open_probes = half_open_probes;
+        self
+    }
+}
+
+/// State of a circuit breaker, see the [module docs](self).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CircuitState {
+    /// Requests are sent normally.
+    Closed,
+    /// Requests fail immediately.
+    Open,
+    /// Limited probe requests are sent.
+    HalfOpen,
+}
+
+/// Current status of a single circuit breaker, from
+/// [`RiotApi::circuit_breaker_status`](crate::RiotApi::circuit_breaker_status).
+#[derive(Debug, Clone, PartialEq)]
+pub struct CircuitBreakerStatus {
+    /// Current state.
+    pub state: CircuitState,
+    /// Number of requests within the window.
+    pub requests: usize,
+    /// Number of failed requests within the window.
+    pub failures: usize,
+    /// If open, the time remaining until the circuit becomes half-open.
+    pub retry_in: Option<Duration>,
+}
+
+/// Circuit breaker for a single route and method.
+pub(crate) struct CircuitBreaker {
+    config: CircuitBreakerConfig,
+    inner: Mutex<CircuitBreakerInner>,
+}
+
+struct CircuitBreakerInner {
+    state: CircuitState,
+    /// Outcomes of the most recent requests, `true` for failures, newest at the back.
+    outcomes: VecDeque<bool>,
+    /// When the circuit last opened.
+    opened_at: Instant,
+    /// Number of probe requests in flight while half-open.
+    probes: usize,
+}
+
+impl CircuitBreakerInner {
+    fn failures(&self) -> usize {
+        self.outcomes.iter().filter(|&&failure| failure).count()
+    }
+
+    /// Transitions from open to half-open if the open duration has passed.
+    fn update(&mut self, config: &CircuitBreakerConfig) {
+        if CircuitState::Open == self.state && config.open_duration <= self.opened_at.elapsed() {
+            self.state = CircuitState::HalfOpen;
+        }
+    }
+
+    fn retry_in(&self, config: &CircuitBreakerConfig) -> Option<Duration> {
+        (CircuitState::Open == self.state).then(|| {
+            config
+                .open_duration
+                .saturating_sub(self.opened_at.elapsed())
+        })
+    }
+
+    fn open(&mut self) {
+        self.state = CircuitState::Open;
+        self.opened_at = Instant::now();
+        self.outcomes.clear();
+    }
+}
+
+impl CircuitBreaker {
+    pub fn new(config: CircuitBreakerConfig) -> Self {
+        Self {
+            inner: Mutex::new(CircuitBreakerInner {
+                state: CircuitState::Closed,
+                outcomes: VecDeque::with_capacity(config.window),
+                opened_at: Instant::now(),
+                probes: 0,
+            }),
+            config,
+        }
+    }
+
+    /// Checks that the circuit is not open, without taking a half-open probe slot. Used to fail
+    /// before waiting for rate limits, see [`Self::try_acquire`].
+    pub fn check_not_open(&self) -> Result<(), CircuitOpen> {
+        let mut inner = self.inner.lock();
+        inner.update(&self.config);
+        match inner.state {
+            CircuitState::Open => Err(CircuitOpen {
+                retry_in: inner.retry_in(&self.config),
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks if a request may be sent. The returned permit should be used to record the
+    /// request's outcome.
+    pub fn try_acquire(&self) -> Result<CircuitPermit<'_>, CircuitOpen> {
+        let mut inner = self.inner.lock();
+        inner.update(&self.config);
+        match inner.state {
+            CircuitState::Closed => Ok(CircuitPermit {
+                breaker: self,
+                probe: false,
+            }),
+            CircuitState::HalfOpen if inner.probes < self.config.half_open_probes => {
+                inner.probes += 1;
+                Ok(CircuitPermit {
+                    breaker: self,
+                    probe: true,
+                })
+            }
+            _ => Err(CircuitOpen {
+                retry_in: inner.retry_in(&self.config),
+            }),
+        }
+    }
+
+    pub fn status(&self) -> CircuitBreakerStatus {
+        let mut inner = self.inner.lock();
+        inner.update(&self.config);
+        CircuitBreakerStatus {
+            state: inner.state,
+            requests: inner.outcomes.len(),
+            failures: inner.failures(),
+            retry_in: inner.retry_in(&self.config),
+        }
+    }
+
+    fn record(&self, probe: bool, failure: bool) {
+        let mut inner = self.inner.lock();
+        if probe {
+            inner.probes -= 1;
+        }
+        match inner.state {
+            CircuitState::Closed => {
+                if self.config.window <= inner.outcomes.len() {
+                    inner.outcomes.pop_front();
+                }
+                inner.outcomes.push_back(failure);
+                let requests = inner.outcomes.len();
+                let failures = inner.failures();
+                if self.config.min_requests <= requests
+                    && self.config.failure_rate <= failures as f32 / requests as f32
+                {
+                    log::warn!(
+                        "Circuit breaker opened after {} failures in {} requests.",
+                        failures,
+                        requests
+                    );
+                    inner.open();
+                }
+            }
+            CircuitState::HalfOpen if probe => {
+                if failure {
+                    log::warn!("Circuit breaker probe failed, re-opening.");
+                    inner.open();
+                } else {
+                    log::info!("Circuit breaker probe succeeded, closing.");
+                    inner.state = CircuitState::Closed;
+                }
+            }
+            // Outcomes of requests sent before the circuit opened are ignored.
+            _ => {}
+        }
+    }
+}
+
+/// Permission to send a request through a [`CircuitBreaker`]. If dropped without recording
+/// an outcome, for example if the request is cancelled, no outcome is recorded.
+pub(crate) struct CircuitPermit<'a> {
+    breaker: &'a CircuitBreaker,
+    probe: bool,
+}
+
+impl CircuitPermit<'_> {
+    /// Records the outcome of the request.
+    pub fn record(mut self, failure: bool) {
+        self.breaker.record(self.probe, failure);
+        // Probe already released.
+        self.probe = false;
+    }
+}
+
+impl Drop for CircuitPermit<'_> {
+    fn drop(&mut self) {
+        if self.probe {
+            self.breaker.inner.lock().probes -= 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn open_half_open_close() {
+        let breaker = CircuitBreaker::new(
+            CircuitBreakerConfig::new()
+                .set_window(4)
+                .set_min_requests(4)
+                .set_open_duration(Duration::ZERO),
+        );
+        for failure in [false, true, false, true] {
+            breaker.try_acquire().unwrap().record(failure);
+        }
+        assert_eq!(CircuitState::HalfOpen, breaker.status().state);
+
+        // Only one probe allowed at a time.
+        let probe = breaker.try_acquire().unwrap();
+        assert!(breaker.try_acquire().is_err());
+        drop(probe);
+        breaker.try_acquire().unwrap().record(false);
+        let status = breaker.status();
+        assert_eq!(CircuitState::Closed, status.state);
+        assert_eq!(0, status.requests);
+    }
+
+    #[test]
+    fn stays_open() {
+        let breaker = CircuitBreaker::new(CircuitBreakerConfig::new().set_min_requests(1));
+        breaker.try_acquire().unwrap().record(true);
+        let err = breaker.try_acquire().err().unwrap();
+        assert!(err.retry_in().unwrap() <= Duration::from_secs(30));
+        assert!(breaker.check_not_open().is_err());
+        assert_eq!(CircuitState::Open, breaker.status().state);
+    }
+}
@@ -0,0 +1,304 @@
+//! Coalescing of identical concurrent requests.
+//!
+//! [`RequestCoalescer`] is a [`Middleware`] which makes concurrent identical `GET` requests
+//! (same method ID, route, and URL) share a single upstream request, and therefore a single
+//! rate limit token. The first request is sent as usual, and any identical requests made
+//! while it is in flight wait for and receive a copy of its result. Requests with an
+//! `Authorization` header, such as RSO requests on behalf of a user, are never coalesced.
+//!
+//! Results, including `status_none` and errors, are the same as for a normal call. Each
+//! request receives its own copy of a failed response. Waiting requests use the
+//! [`Priority`](crate::Priority) and [`Deadline`](crate::Deadline) of the first request.
+//!
+//! ```
+//! use std::sync::Arc;
+//!
+//! use riven::coalesce::RequestCoalescer;
+//! use riven::RiotApiConfig;
+//!
+//! let config =
+//!     RiotApiConfig::with_key("RGAPI-...").add_middleware(Arc::new(RequestCoalescer::new()));
+//! ```
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use futures::channel::oneshot;
+use parking_lot::Mutex;
+use reqwest::header::AUTHORIZATION;
+use reqwest::{Method, Url};
+#[cfg(feature = "tracing")]
+use tracing as log;
+
+use crate::cache::CachedResponse;
+use crate::middleware::{Middleware, MiddlewareFuture, MiddlewareRequest, Next};
+use crate::{ResponseInfo, Result, RiotApiError};
+
+/// A successful response shared between coalesced requests, with its URL.
+struct SharedResponse {
+    response: CachedResponse,
+    url: Url,
+    retries: u8,
+    status_none: bool,
+}
+
+/// An error shared between coalesced requests, with its failed response and URL, if any.
+struct SharedError {
+    error: Arc<RiotApiError>,
+    response: Option<(CachedResponse, Url)>,
+}
+
+type SharedResult = std::result::Result<Arc<SharedResponse>, Arc<SharedError>>;
+
+/// [`Middleware`] which coalesces identical concurrent requests, see the
+/// [module docs](self).
+#[derive(Default)]
+pub struct RequestCoalescer {
+    /// Senders for the requests waiting on each in-flight request, by key.
+    in_flight: Mutex<HashMap<String, Vec<oneshot::Sender<SharedResult>>>>,
+}
+
+impl RequestCoalescer {
+    /// Creates a new `RequestCoalescer`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    async fn handle_coalesced<'a>(
+        &'a self,
+        request: MiddlewareRequest,
+        next: Next<'a>,
+        key: String,
+    ) -> Result<ResponseInfo> {
+        let receiver = {
+            let mut in_flight = self.in_flight.lock();
+            match in_flight.get_mut(&key) {
+                Some(waiters) => {
+                    let (sender, receiver) = oneshot::channel();
+                    waiters.push(sender);
+                    Some(receiver)
+                }
+                None => {
+                    in_flight.insert(key.clone(), Vec::new());
+                    None
+                }
+            }
+        };
+        if let Some(receiver) = receiver {
+            log::trace!("Coalescing request: {}", key);
+            match receiver.await {
+                Ok(shared) => {
+                    return shared
+                        .map(|shared| Self::unshare(&shared))
+                        .map_err(|shared| Self::unshare_error(&shared))
+                }
+                // First request was cancelled, send this request by itself instead.
+                Err(oneshot::Canceled) => return next.run(request).await,
+            }
+        }
+
+        // Remove the entry if cancelled, dropping the senders.
+        let guard = InFlightGuard {
+            coalescer: self,
+            key: &key,
+        };
+        let result = next.run(request).await;
+        let waiters = self.in_flight.lock().remove(&key).unwrap_or_default();
+        std::mem::forget(guard);
+        if waiters.is_empty() {
+            return result;
+        }
+
+        let shared = match result {
+            Ok(rinfo) => {
+                let retries = rinfo.retries;
+                let status = rinfo.response.status();
+                let url = rinfo.response.url().clone();
+                match CachedResponse::from_response(rinfo.response).await {
+                    Ok(response) => Ok(Arc::new(SharedResponse {
+                        response,
+                        url,
+                        retries,
+                        status_none: rinfo.status_none,
+                    })),
+                    Err(e) => Err(Arc::new(SharedError {
+                        error: Arc::new(RiotApiError::new(e, retries, None, Some(status))),
+                        response: None,
+                    })),
+                }
+            }
+            Err(mut e) => {
+                let response = match e.take_response() {
+                    Some(response) => {
+                        let url = response.url().clone();
+                        CachedResponse::from_response(response)
+                            .await
+                            .ok()
+                            .map(|response| (response, url))
+                    }
+                    None => None,
+                };
+                Err(Arc::new(SharedError {
+                    error: Arc::new(e),
+                    response,
+                }))
+            }
+        };
+        for waiter in waiters {
+            // Ignore waiters which were cancelled.
+            let _ = waiter.send(shared.clone());
+        }
+        shared
+            .map(|shared| Self::unshare(&shared))
+            .map_err(|shared| Self::unshare_error(&shared))
+    }
+
+    fn unshare(shared: &SharedResponse) -> ResponseInfo {
+        ResponseInfo {
+            response: shared
+                .response
+                .clone()
+                .into_response_with_url(shared.url.clone()),
+            retries: shared.retries,
+            status_none: shared.status_none,
+        }
+    }
+
+    fn unshare_error(shared: &SharedError) -> RiotApiError {
+        let mut error = RiotApiError::shared(Arc::clone(&shared.error));
+        if let Some((response, url)) = &shared.response {
+            error.set_response(response.clone().into_response_with_url(url.clone()));
+        }
+        error
+    }
+}
+
+impl Middleware for RequestCoalescer {
+    fn handle<'a>(&'a self, request: MiddlewareRequest, next: Next<'a>) -> MiddlewareFuture<'a> {
+        let url = request
+            .request
+            .try_clone()
+            .and_then(|request| request.build().ok())
+            .filter(|request| Method::GET == request.method())
+            // Don't share user-specific responses between users.
+            .filter(|request| !request.headers().contains_key(AUTHORIZATION))
+            .map(|request| request.url().to_string());
+        match url {
+            Some(url) => {
+                let key = format!("{} {} {}", request.method_id, request.route, url);
+                Box::pin(self.handle_coalesced(request, next, key))
+            }
+            None => next.run(request),
+        }
+    }
+}
+
+impl std::fmt::Debug for RequestCoalescer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RequestCoalescer")
+            .field("in_flight", &self.in_flight.lock().keys())
+            .finish()
+    }
+}
+
+/// Removes an in-flight entry when the first request is cancelled.
+struct InFlightGuard<'a> {
+    coalescer: &'a RequestCoalescer,
+    key: &'a str,
+}
+
+impl Drop for InFlightGuard<'_> {
+    fn drop(&mut self) {
+        self.coalescer.in_flight.lock().remove(self.key);
+    }
+}
+
+#[cfg(all(test, feature = "tokio", not(target_family = "wasm")))]
+mod test {
+    use reqwest::{Request, StatusCode};
+
+    use super::*;
+    use crate::time::Duration;
+    use crate::transport::{HttpTransport, MockResponse, MockTransport, TransportFuture};
+    use crate::{RiotApi, RiotApiConfig};
+
+    /// [`MockTransport`] which slowly returns the number of requests it has received with
+    /// `status`, so concurrent requests overlap.
+    #[derive(Debug)]
+    struct SlowTransport(MockTransport);
+    impl SlowTransport {
+        fn new(status: StatusCode, n: usize) -> Arc<Self> {
+            let transport = MockTransport::new();
+            for count in 1..=n {
+                transport.push_response(MockResponse::new(status).set_body(count.to_string()));
+            }
+            Arc::new(Self(transport))
+        }
+    }
+    impl HttpTransport for SlowTransport {
+        fn send(&self, request: Request) -> TransportFuture<'_> {
+            let response = self.0.send(request);
+            Box::pin(async move {
+                tokio::time::sleep(Duration::from_millis(200)).await;
+                response.await
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn coalesce_concurrent() {
+        let config = RiotApiConfig::with_key("RGAPI-test")
+            .set_transport(SlowTransport::new(StatusCode::OK, 3))
+            .add_middleware(Arc::new(RequestCoalescer::new()));
+        let riot_api = RiotApi::new(config);
+
+        let get = || {
+            let request = riot_api.request(Method::GET, "NA1", "/");
+            riot_api.execute_val::<usize>("test.method", "NA1", request)
+        };
+        let (a, b, c) = futures::join!(get(), get(), get());
+        assert_eq!((1, 1, 1), (a.unwrap(), b.unwrap(), c.unwrap()));
+        // Not in flight anymore.
+        assert_eq!(2, get().await.unwrap());
+
+        let get_raw = || {
+            let request = riot_api.request(Method::GET, "NA1", "/a");
+            riot_api.execute_raw("test.method", "NA1", request)
+        };
+        let (a, b) = futures::join!(get_raw(), get_raw());
+        assert_eq!("/a", a.unwrap().response.url().path());
+        assert_eq!("/a", b.unwrap().response.url().path());
+    }
+
+    #[tokio::test]
+    async fn coalesce_error_responses() {
+        let config = RiotApiConfig::with_key("RGAPI-test")
+            .set_transport(SlowTransport::new(StatusCode::BAD_REQUEST, 1))
+            .add_middleware(Arc::new(RequestCoalescer::new()));
+        let riot_api = RiotApi::new(config);
+
+        let get = || async {
+            let request = riot_api.request(Method::GET, "NA1", "/");
+            let Err(mut err) = riot_api.execute_raw("test.method", "NA1", request).await else {
+                panic!("400 should fail.");
+            };
+            err.take_response().unwrap().text().await.unwrap()
+        };
+        let (a, b) = futures::join!(get(), get());
+        assert_eq!(("1", "1"), (&*a, &*b));
+    }
+
+    #[tokio::test]
+    async fn authorized_not_coalesced() {
+        let config = RiotApiConfig::with_key("RGAPI-test")
+            .set_transport(SlowTransport::new(StatusCode::OK, 2))
+            .add_middleware(Arc::new(RequestCoalescer::new()));
+        let riot_api = RiotApi::new(config);
+
+        let get = |token| {
+            let request = riot_api.request(Method::GET, "NA1", "/").bearer_auth(token);
+            riot_api.execute_val::<usize>("test.method", "NA1", request)
+        };
+        let (a, b) = futures::join!(get("token-a"), get("token-b"));
+        assert_eq!(3, a.unwrap() + b.unwrap());
+    }
+}
@@ -1,19 +1,38 @@
 //! Configuration of RiotApi.
+use std::sync::Arc;
+
+use parking_lot::RwLock;
 use reqwest::header::{HeaderMap, HeaderValue};
 use reqwest::ClientBuilder;
 
+use crate::circuit_breaker::CircuitBreakerConfig;
+use crate::key_pool::{AuthFailureConfig, GameFamily};
+use crate::metrics::Metrics;
+use crate::middleware::Middleware;
+use crate::rate_limit_store::{MemoryRateLimitStore, RateLimitSnapshot, RateLimitStore};
+use crate::retry::{ExponentialBackoff, RetryPolicy};
 use crate::time::Duration;
+use crate::timer::Timer;
+use crate::transport::HttpTransport;
 
 /// Configuration for instantiating RiotApi.
 #[derive(Debug)]
 pub struct RiotApiConfig {
     pub(crate) base_url: String,
-    pub(crate) retries: u8,
-    pub(crate) app_rate_usage_factor: f32,
-    pub(crate) method_rate_usage_factor: f32,
-    pub(crate) burst_factor: f32,
-    pub(crate) duration_overhead: Duration,
+    pub(crate) api_keys: Vec<(Option<GameFamily>, HeaderValue)>,
+    pub(crate) retry_policy: Arc<dyn RetryPolicy>,
+    pub(crate) rate_limit_tuning: RwLock<RateLimitTuning>,
+    pub(crate) bulk_rate_usage_factor: f32,
+    pub(crate) deadline: Option<Duration>,
+    pub(crate) circuit_breaker: Option<CircuitBreakerConfig>,
+    pub(crate) auth_failure: Option<AuthFailureConfig>,
+    pub(crate) middlewares: Vec<Arc<dyn Middleware>>,
+    pub(crate) metrics: Option<Arc<dyn Metrics>>,
     pub(crate) client_builder: Option<ClientBuilder>,
+    pub(crate) transport: Option<Arc<dyn HttpTransport>>,
+    pub(crate) timer: Option<Arc<dyn Timer>>,
+    pub(crate) rate_limit_store: Arc<dyn RateLimitStore>,
+    pub(crate) rate_limit_snapshot: Option<RateLimitSnapshot>,
 }
 
 impl RiotApiConfig {
@@ -67,20 +86,27 @@ impl RiotApiConfig {
     /// [https://developer.riotgames.com/](https://developer.riotgames.com/),
     /// and should look like `"RGAPI-01234567-89ab-cdef-0123-456789abcdef"`.
     pub fn with_key(api_key: impl AsRef<[u8]>) -> Self {
+        let mut api_key = HeaderValue::from_bytes(api_key.as_ref()).unwrap();
+        api_key.set_sensitive(true);
         let mut default_headers = HeaderMap::new();
-        default_headers.insert(
-            Self::RIOT_KEY_HEADER,
-            HeaderValue::from_bytes(api_key.as_ref()).unwrap(),
-        );
+        default_headers.insert(Self::RIOT_KEY_HEADER, api_key.clone());
 
         Self {
             base_url: Self::DEFAULT_BASE_URL.into(),
-            retries: Self::DEFAULT_RETRIES,
-            app_rate_usage_factor: Self::DEFAULT_RATE_USAGE_FACTOR,
-            method_rate_usage_factor: Self::DEFAULT_RATE_USAGE_FACTOR,
-            burst_factor: Self::PRECONFIG_BURST_BURST_FACTOR,
-            duration_overhead: Self::PRECONFIG_BURST_DURATION_OVERHEAD,
+            api_keys: vec![(None, api_key)],
+            retry_policy: Arc::new(ExponentialBackoff::new(Self::DEFAULT_RETRIES)),
+            rate_limit_tuning: RwLock::new(RateLimitTuning::new()),
+            bulk_rate_usage_factor: Self::DEFAULT_RATE_USAGE_FACTOR,
+            deadline: None,
+            circuit_breaker: None,
+            auth_failure: None,
+            middlewares: Vec::new(),
+            metrics: None,
             client_builder: Some(ClientBuilder::new().default_headers(default_headers)),
+            transport: None,
+            timer: None,
+            rate_limit_store: Arc::new(MemoryRateLimitStore::new()),
+            rate_limit_snapshot: None,
         }
     }
 
@@ -95,15 +121,58 @@ impl RiotApiConfig {
     pub fn with_client_builder(client_builder: ClientBuilder) -> Self {
         Self {
             base_url: Self::DEFAULT_BASE_URL.to_owned(),
-            retries: Self::DEFAULT_RETRIES,
-            app_rate_usage_factor: Self::DEFAULT_RATE_USAGE_FACTOR,
-            method_rate_usage_factor: Self::DEFAULT_RATE_USAGE_FACTOR,
-            burst_factor: Self::PRECONFIG_BURST_BURST_FACTOR,
-            duration_overhead: Self::PRECONFIG_BURST_DURATION_OVERHEAD,
+            api_keys: Vec::new(),
+            retry_policy: Arc::new(ExponentialBackoff::new(Self::DEFAULT_RETRIES)),
+            rate_limit_tuning: RwLock::new(RateLimitTuning::new()),
+            bulk_rate_usage_factor: Self::DEFAULT_RATE_USAGE_FACTOR,
+            deadline: None,
+            circuit_breaker: None,
+            auth_failure: None,
+            middlewares: Vec::new(),
+            metrics: None,
             client_builder: Some(client_builder),
+            transport: None,
+            timer: None,
+            rate_limit_store: Arc::new(MemoryRateLimitStore::new()),
+            rate_limit_snapshot: None,
         }
     }
 
+    /// Adds another API key to the key pool, see the [`key_pool`](crate::key_pool) module.
+    /// Each key has its own rate limits, and requests are sent with whichever key has
+    /// capacity soonest.
+    ///
+    /// When using [`Self::with_client_builder`], the client builder's key is not part of the
+    /// pool, so all keys should be added with this method.
+    ///
+    /// # Panics
+    /// If `api_key` is not a valid header value.
+    ///
+    /// # Returns
+    /// `self`, for chaining.
+    pub fn add_api_key(mut self, api_key: impl AsRef<[u8]>) -> Self {
+        let mut api_key = HeaderValue::from_bytes(api_key.as_ref()).unwrap();
+        api_key.set_sensitive(true);
+        self.api_keys.push((None, api_key));
+        self
+    }
+
+    /// Adds an API key used only for endpoints of the given [`GameFamily`], e.g. a TFT
+    /// product key for `tft-*` endpoints, see the [`key_pool`](crate::key_pool) module. Each
+    /// key has its own rate limits. Multiple keys may be added for the same family.
+    ///
+    /// # Panics
+    /// If `api_key` is not a valid header value.
+    ///
+    /// # Returns
+    /// `self`, for chaining.
+    pub fn add_family_api_key(mut self, family: GameFamily, api_key: impl AsRef<[u8]>) -> Self {
+        let mut api_key = HeaderValue::from_bytes(api_key.as_ref()).unwrap();
+        api_key.set_sensitive(true);
+        self.api_keys.push((Some(family), api_key));
+        self
+    }
+
     /// Sets rate limiting settings to preconfigured values optimized for burst,
     /// low latency:
     ///
@@ -112,10 +181,9 @@ impl RiotApiConfig {
     ///
     /// # Returns
     /// `self`, for chaining.
-    pub fn preconfig_burst(mut self) -> Self {
-        self.burst_factor = Self::PRECONFIG_BURST_BURST_FACTOR;
-        self.duration_overhead = Self::PRECONFIG_BURST_DURATION_OVERHEAD;
-        self
+    pub fn preconfig_burst(self) -> Self {
+        self.set_burst_factor(Self::PRECONFIG_BURST_BURST_FACTOR)
+            .set_duration_overhead(Self::PRECONFIG_BURST_DURATION_OVERHEAD)
     }
 
     /// Sets the rate limiting settings to preconfigured values  optimized for
@@ -126,10 +194,9 @@ impl RiotApiConfig {
     ///
     /// # Returns
     /// `self`, for chaining.
-    pub fn preconfig_throughput(mut self) -> Self {
-        self.burst_factor = Self::PRECONFIG_THROUGHPUT_BURST_FACTOR;
-        self.duration_overhead = Self::PRECONFIG_THROUGHPUT_DURATION_OVERHEAD;
-        self
+    pub fn preconfig_throughput(self) -> Self {
+        self.set_burst_factor(Self::PRECONFIG_THROUGHPUT_BURST_FACTOR)
+            .set_duration_overhead(Self::PRECONFIG_THROUGHPUT_DURATION_OVERHEAD)
     }
 
     /// Set the base url for requests. The string should contain a `"{}"`
@@ -148,10 +215,24 @@ impl RiotApiConfig {
     /// for retry-after headers). A value of `0` means one request will be sent
     /// and it will not be retried if it fails.
     ///
+    /// This replaces the retry policy with an
+    /// [`ExponentialBackoff`](crate::retry::ExponentialBackoff) with `retries` retries, see
+    /// [Self::set_retry_policy].
+    ///
     /// # Returns
     /// `self`, for chaining.
     pub fn set_retries(mut self, retries: u8) -> Self {
-        self.retries = retries;
+        self.retry_policy = Arc::new(ExponentialBackoff::new(retries));
+        self
+    }
+
+    /// Sets the policy which decides whether to retry failed requests, and how long to wait
+    /// before retrying. See the [`retry`](crate::retry) module for the provided policies.
+    ///
+    /// # Returns
+    /// `self`, for chaining.
+    pub fn set_retry_policy(mut self, retry_policy: Arc<dyn RetryPolicy>) -> Self {
+        self.retry_policy = retry_policy;
         self
     }
 
@@ -180,16 +261,9 @@ impl RiotApiConfig {
     /// # Returns
     /// `self`, for chaining.
     pub fn set_rate_usage_factor(mut self, rate_usage_factor: f32) -> Self {
-        // Use inverted check to handle NaN.
-        if 0.0 < rate_usage_factor && rate_usage_factor <= 1.0 {
-            self.app_rate_usage_factor = rate_usage_factor;
-            self.method_rate_usage_factor = rate_usage_factor;
-            return self;
-        }
-        panic!(
-            "rate_usage_factor \"{}\" not in range (0, 1].",
-            rate_usage_factor
-        );
+        let tuning = self.rate_limit_tuning.get_mut();
+        *tuning = tuning.set_rate_usage_factor(rate_usage_factor);
+        self
     }
 
     /// See [Self::set_rate_usage_factor]. Setting this is useful if you have multiple
@@ -202,15 +276,9 @@ impl RiotApiConfig {
     /// # Returns
     /// `self`, for chaining.
     pub fn set_app_rate_usage_factor(mut self, app_rate_usage_factor: f32) -> Self {
-        // Use inverted check to handle NaN.
-        if 0.0 < app_rate_usage_factor && app_rate_usage_factor <= 1.0 {
-            self.app_rate_usage_factor = app_rate_usage_factor;
-            return self;
-        }
-        panic!(
-            "app_rate_usage_factor \"{}\" not in range (0, 1].",
-            app_rate_usage_factor
-        );
+        let tuning = self.rate_limit_tuning.get_mut();
+        *tuning = tuning.set_app_rate_usage_factor(app_rate_usage_factor);
+        self
     }
 
     /// See [Self::set_rate_usage_factor] and [Self::set_app_rate_usage_factor].
@@ -223,14 +291,31 @@ impl RiotApiConfig {
     /// # Returns
     /// `self`, for chaining.
     pub fn set_method_rate_usage_factor(mut self, method_rate_usage_factor: f32) -> Self {
+        let tuning = self.rate_limit_tuning.get_mut();
+        *tuning = tuning.set_method_rate_usage_factor(method_rate_usage_factor);
+        self
+    }
+
+    /// Limits [`Priority::Bulk`](crate::Priority::Bulk) requests to this fraction of the app
+    /// rate limit (after applying the app rate usage factor), reserving the remainder for
+    /// higher priority requests. For example, `0.5` allows bulk requests to use at most half of
+    /// the app rate limit. A value of `1.0` (the default) does not limit bulk requests, other
+    /// than having them wait behind higher priority requests.
+    ///
+    /// # Panics
+    /// If `bulk_rate_usage_factor` is not in range (0, 1\].
+    ///
+    /// # Returns
+    /// `self`, for chaining.
+    pub fn set_bulk_rate_usage_factor(mut self, bulk_rate_usage_factor: f32) -> Self {
         // Use inverted check to handle NaN.
-        if 0.0 < method_rate_usage_factor && method_rate_usage_factor <= 1.0 {
-            self.method_rate_usage_factor = method_rate_usage_factor;
+        if 0.0 < bulk_rate_usage_factor && bulk_rate_usage_factor <= 1.0 {
+            self.bulk_rate_usage_factor = bulk_rate_usage_factor;
             return self;
         }
         panic!(
-            "method_rate_usage_factor \"{}\" not in range (0, 1].",
-            method_rate_usage_factor
+            "bulk_rate_usage_factor \"{}\" not in range (0, 1].",
+            bulk_rate_usage_factor
         );
     }
 
@@ -270,12 +355,9 @@ impl RiotApiConfig {
     /// # Returns
     /// `self`, for chaining.
     pub fn set_burst_factor(mut self, burst_factor: f32) -> Self {
-        // Use inverted check to handle NaN.
-        if 0.0 < burst_factor && burst_factor <= 1.0 {
-            self.burst_factor = burst_factor;
-            return self;
-        }
-        panic!("burst_factor \"{}\" not in range (0, 1].", burst_factor);
+        let tuning = self.rate_limit_tuning.get_mut();
+        *tuning = tuning.set_burst_factor(burst_factor);
+        self
     }
 
     /// Sets the additional bucket duration to consider when rate limiting.
@@ -292,7 +374,131 @@ impl RiotApiConfig {
     /// # Returns
     /// `self`, for chaining.
     pub fn set_duration_overhead(mut self, duration_overhead: Duration) -> Self {
-        self.duration_overhead = duration_overhead;
+        let tuning = self.rate_limit_tuning.get_mut();
+        *tuning = tuning.set_duration_overhead(duration_overhead);
+        self
+    }
+
+    /// Sets a default deadline for each request, relative to when the request starts. If a
+    /// request would need to wait past its deadline, for a rate limit or a retry backoff, it
+    /// fails immediately instead. See [`Deadline`](crate::Deadline) for details and for setting
+    /// deadlines on individual requests.
+    ///
+    /// By default there is no deadline, and requests wait as long as needed.
+    ///
+    /// # Returns
+    /// `self`, for chaining.
+    pub fn set_deadline(mut self, deadline: Duration) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Enables circuit breakers for each route and method, which fail requests immediately
+    /// while the method is failing. See the [`circuit_breaker`](crate::circuit_breaker)
+    /// module for details.
+    ///
+    /// By default circuit breakers are disabled.
+    ///
+    /// # Returns
+    /// `self`, for chaining.
+    pub fn set_circuit_breaker(mut self, circuit_breaker: CircuitBreakerConfig) -> Self {
+        self.circuit_breaker = Some(circuit_breaker);
+        self
+    }
+
+    /// Enables halting requests when the API key is rejected with a `401` or `403`, instead
+    /// of continuing to send requests which will be rejected. See the
+    /// [`key_pool`](crate::key_pool) module for details.
+    ///
+    /// By default this is disabled.
+    ///
+    /// # Returns
+    /// `self`, for chaining.
+    pub fn set_auth_failure_halt(mut self, auth_failure: AuthFailureConfig) -> Self {
+        self.auth_failure = Some(auth_failure);
+        self
+    }
+
+    /// Adds a middleware layer which wraps every request. Middleware added first is
+    /// outermost. See the [`middleware`](crate::middleware) module for details.
+    ///
+    /// # Returns
+    /// `self`, for chaining.
+    pub fn add_middleware(mut self, middleware: Arc<dyn Middleware>) -> Self {
+        self.middlewares.push(middleware);
+        self
+    }
+
+    /// Sets the [`Metrics`] implementation which receives events about requests, rate limit
+    /// waits, retries, and rate limit changes. See the [`metrics`](crate::metrics) module.
+    ///
+    /// By default no metrics are recorded.
+    ///
+    /// # Returns
+    /// `self`, for chaining.
+    pub fn set_metrics(mut self, metrics: Arc<dyn Metrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Sets the [`HttpTransport`] used to send requests. See the
+    /// [`transport`](crate::transport) module.
+    ///
+    /// By default requests are sent by a
+    /// [`ReqwestTransport`](crate::transport::ReqwestTransport) using the client built from
+    /// the client builder. The client is still used to build requests when a different
+    /// transport is set, but its default headers are not applied. API keys given to
+    /// [`Self::with_key`] or [`Self::add_api_key`] are always included, but when using
+    /// [`Self::with_client_builder`] alone the transport must add the
+    /// [`Self::RIOT_KEY_HEADER`] header itself.
+    ///
+    /// # Returns
+    /// `self`, for chaining.
+    pub fn set_transport(mut self, transport: Arc<dyn HttpTransport>) -> Self {
+        self.transport = Some(transport);
+        self
+    }
+
+    /// Sets the [`Timer`] used to wait for rate limits and retry backoffs, for example to run
+    /// under an executor other than Tokio. See the [`timer`](crate::timer) module.
+    ///
+    /// By default a [`TokioTimer`](crate::timer::TokioTimer) is used, or a
+    /// [`GlooTimer`](crate::timer::GlooTimer) on wasm.
+    ///
+    /// # Returns
+    /// `self`, for chaining.
+    pub fn set_timer(mut self, timer: Arc<dyn Timer>) -> Self {
+        self.timer = Some(timer);
+        self
+    }
+
+    /// Sets the store used to keep track of rate limit buckets. By default buckets are kept in
+    /// process memory, in a [`MemoryRateLimitStore`].
+    ///
+    /// Multiple `RiotApi` instances using the same API key may share a single store, such as an
+    /// [`HttpRateLimitStore`](crate::rate_limit_store::HttpRateLimitStore), to coordinate their
+    /// usage of the rate limit instead of each using a fraction via
+    /// [Self::set_rate_usage_factor].
+    ///
+    /// # Returns
+    /// `self`, for chaining.
+    pub fn set_rate_limit_store(mut self, rate_limit_store: Arc<dyn RateLimitStore>) -> Self {
+        self.rate_limit_store = rate_limit_store;
+        self
+    }
+
+    /// Sets rate limit state to restore when the `RiotApi` is constructed, as saved by
+    /// [`RiotApi::rate_limit_snapshot`](crate::RiotApi::rate_limit_snapshot). This allows
+    /// learned rate limits, used tokens, and `retry-after` deadlines to persist across
+    /// restarts.
+    ///
+    /// Buckets are restored into the store set by [Self::set_rate_limit_store], so this
+    /// should be called after that if both are used.
+    ///
+    /// # Returns
+    /// `self`, for chaining.
+    pub fn set_rate_limit_snapshot(mut self, rate_limit_snapshot: RateLimitSnapshot) -> Self {
+        self.rate_limit_snapshot = Some(rate_limit_snapshot);
         self
     }
 }
@@ -302,3 +508,129 @@ impl<T: AsRef<[u8]>> From<T> for RiotApiConfig {
         Self::with_key(api_key)
     }
 }
+
+/// Rate limit settings which can be changed on a live [`RiotApi`](crate::RiotApi), see
+/// [`RiotApi::set_rate_limit_tuning`](crate::RiotApi::set_rate_limit_tuning). Each setting
+/// is the same as the corresponding [`RiotApiConfig`] setting.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimitTuning {
+    app_rate_usage_factor: f32,
+    method_rate_usage_factor: f32,
+    burst_factor: f32,
+    duration_overhead: Duration,
+}
+
+impl RateLimitTuning {
+    /// The default tuning, the same as [`RiotApiConfig::with_key`].
+    const fn new() -> Self {
+        Self {
+            app_rate_usage_factor: RiotApiConfig::DEFAULT_RATE_USAGE_FACTOR,
+            method_rate_usage_factor: RiotApiConfig::DEFAULT_RATE_USAGE_FACTOR,
+            burst_factor: RiotApiConfig::PRECONFIG_BURST_BURST_FACTOR,
+            duration_overhead: RiotApiConfig::PRECONFIG_BURST_DURATION_OVERHEAD,
+        }
+    }
+
+    /// See [`RiotApiConfig::set_app_rate_usage_factor`].
+    pub fn app_rate_usage_factor(&self) -> f32 {
+        self.app_rate_usage_factor
+    }
+
+    /// See [`RiotApiConfig::set_method_rate_usage_factor`].
+    pub fn method_rate_usage_factor(&self) -> f32 {
+        self.method_rate_usage_factor
+    }
+
+    /// See [`RiotApiConfig::set_burst_factor`].
+    pub fn burst_factor(&self) -> f32 {
+        self.burst_factor
+    }
+
+    /// See [`RiotApiConfig::set_duration_overhead`].
+    pub fn duration_overhead(&self) -> Duration {
+        self.duration_overhead
+    }
+
+    /// See [`RiotApiConfig::set_rate_usage_factor`].
+    ///
+    /// # Panics
+    /// If `rate_usage_factor` is not in range (0, 1].
+    ///
+    /// # Returns
+    /// `self`, for chaining.
+    pub fn set_rate_usage_factor(mut self, rate_usage_factor: f32) -> Self {
+        // Use inverted check to handle NaN.
+        if 0.0 < rate_usage_factor && rate_usage_factor <= 1.0 {
+            self.app_rate_usage_factor = rate_usage_factor;
+            self.method_rate_usage_factor = rate_usage_factor;
+            return self;
+        }
+        panic!(
+            "rate_usage_factor \"{}\" not in range (0, 1].",
+            rate_usage_factor
+        );
+    }
+
+    /// See [`RiotApiConfig::set_app_rate_usage_factor`].
+    ///
+    /// # Panics
+    /// If `app_rate_usage_factor` is not in range (0, 1\].
+    ///
+    /// # Returns
+    /// `self`, for chaining.
+    pub fn set_app_rate_usage_factor(mut self, app_rate_usage_factor: f32) -> Self {
+        // Use inverted check to handle NaN.
+        if 0.0 < app_rate_usage_factor && app_rate_usage_factor <= 1.0 {
+            self.app_rate_usage_factor = app_rate_usage_factor;
+            return self;
+        }
+        panic!(
+            "app_rate_usage_factor \"{}\" not in range (0, 1].",
+            app_rate_usage_factor
+        );
+    }
+
+    /// See [`RiotApiConfig::set_method_rate_usage_factor`].
+    ///
+    /// # Panics
+    /// If `method_rate_usage_factor` is not in range (0, 1\].
+    ///
+    /// # Returns
+    /// `self`, for chaining.
+    pub fn set_method_rate_usage_factor(mut self, method_rate_usage_factor: f32) -> Self {
+        // Use inverted check to handle NaN.
+        if 0.0 < method_rate_usage_factor && method_rate_usage_factor <= 1.0 {
+            self.method_rate_usage_factor = method_rate_usage_factor;
+            return self;
+        }
+        panic!(
+            "method_rate_usage_factor \"{}\" not in range (0, 1].",
+            method_rate_usage_factor
+        );
+    }
+
+    /// See [`RiotApiConfig::set_burst_factor`].
+    ///
+    /// # Panics
+    /// If `burst_factor` is not in range (0, 1\].
+    ///
+    /// # Returns
+    /// `self`, for chaining.
+    pub fn set_burst_factor(mut self, burst_factor: f32) -> Self {
+        // Use inverted check to handle NaN.
+        if 0.0 < burst_factor && burst_factor <= 1.0 {
+            self.burst_factor = burst_factor;
+            return self;
+        }
+        panic!("burst_factor \"{}\" not in range (0, 1].", burst_factor);
+    }
+
+    /// See [`RiotApiConfig::set_duration_overhead`].
+    ///
+    /// # Returns
+    /// `self`, for chaining.
+    pub fn set_duration_overhead(mut self, duration_overhead: Duration) -> Self {
+        self.duration_overhead = duration_overhead;
+        self
+    }
+}
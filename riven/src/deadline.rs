@@ -0,0 +1,116 @@
+use std::cell::Cell;
+use std::future::Future;
+
+#[cfg(feature = "tracing")]
+use tracing as log;
+
+use crate::time::{Duration, Instant};
+use crate::util::{get_scoped, Scoped};
+use crate::{DeadlineExceeded, RiotApiConfig};
+
+thread_local! {
+    static DEADLINE: Cell<Option<Deadline>> = const { Cell::new(None) };
+}
+
+/// A deadline for a request, after which it should fail rather than keep waiting.
+///
+/// If a request would need to wait past its deadline, either for a rate limit token or for a
+/// retry backoff, it fails immediately with an error where
+/// [`RiotApiError::is_deadline_exceeded`](crate::RiotApiError::is_deadline_exceeded) is true,
+/// instead of sleeping. This allows interactive handlers to fall back (e.g. to cached data)
+/// rather than timing out upstream. Note that the deadline does not limit the time spent
+/// sending the request itself, see [`reqwest::ClientBuilder::timeout`] for that.
+///
+/// A default deadline relative to the start of each request can be set with
+/// [`RiotApiConfig::set_deadline`](crate::RiotApiConfig::set_deadline). To set a deadline for
+/// specific requests, wrap the request future using [`Deadline::scope`]:
+/// ```ignore
+/// let summoner = Deadline::after(Duration::from_secs(2))
+///     .scope(riot_api.summoner_v4().get_by_puuid(PlatformRoute::NA1, puuid))
+///     .await?;
+/// ```
+/// If both are set, the earlier deadline is used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Deadline(Instant);
+
+impl Deadline {
+    /// A deadline at the given `instant`.
+    pub fn at(instant: Instant) -> Self {
+        Self(instant)
+    }
+
+    /// A deadline `duration` from now.
+    pub fn after(duration: Duration) -> Self {
+        Self(Instant::now() + duration)
+    }
+
+    /// The instant of this deadline.
+    pub fn instant(self) -> Instant {
+        self.0
+    }
+
+    /// Time remaining until this deadline, or zero if it has passed.
+    pub fn remaining(self) -> Duration {
+        self.0.saturating_duration_since(Instant::now())
+    }
+
+    /// Checks if waiting `wait` from now would pass this deadline. A `wait` of `None` means
+    /// an unknown wait, which only fails if the deadline has already passed.
+    pub(crate) fn check(self, wait: Option<Duration>) -> Result<(), DeadlineExceeded> {
+        if Instant::now() + wait.unwrap_or_default() <= self.0 {
+            return Ok(());
+        }
+        log::debug!(
+            "Wait of {:?} exceeds deadline, {:?} remaining.",
+            wait,
+            self.remaining()
+        );
+        Err(DeadlineExceeded {
+            wait,
+            remaining: self.remaining(),
+        })
+    }
+
+    /// Runs the given future with this deadline. All requests sent within `future` will use
+    /// this deadline, unless overridden by a nested `scope`.
+    pub fn scope<F: Future>(self, future: F) -> impl Future<Output = F::Output> {
+        Scoped::new(&DEADLINE, self, future)
+    }
+
+    /// Gets the deadline of a request starting now, from [`Self::scope`] and
+    /// [`RiotApiConfig::set_deadline`], whichever is earlier.
+    pub(crate) fn current(config: &RiotApiConfig) -> Option<Self> {
+        let scoped = get_scoped(&DEADLINE);
+        let config = config.deadline.map(Self::after);
+        match (scoped, config) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        }
+    }
+}
+
+#[cfg(all(test, not(target_family = "wasm")))]
+mod test {
+    use super::*;
+
+    #[test]
+    fn check() {
+        let deadline = Deadline::after(Duration::from_secs(10));
+        assert_eq!(Ok(()), deadline.check(None));
+        assert_eq!(Ok(()), deadline.check(Some(Duration::from_secs(1))));
+        let err = deadline.check(Some(Duration::from_secs(20))).unwrap_err();
+        assert_eq!(Some(Duration::from_secs(20)), err.wait());
+        assert!(err.remaining() <= Duration::from_secs(10));
+    }
+
+    #[tokio::test]
+    async fn current_uses_earliest() {
+        let config = RiotApiConfig::with_key("RGAPI-test").set_deadline(Duration::from_secs(60));
+        let config_deadline = Deadline::current(&config).unwrap();
+        assert!(config_deadline.remaining() <= Duration::from_secs(60));
+
+        let scoped = Deadline::after(Duration::from_secs(1));
+        let current = scoped.scope(async { Deadline::current(&config) }).await;
+        assert_eq!(Some(scoped), current);
+    }
+}
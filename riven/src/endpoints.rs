@@ -33,7 +33,7 @@ impl RiotApi {
     ///
     /// Note: this method is automatically generated.
     #[inline]
-    pub fn account_v1(&self) -> AccountV1 {
+    pub fn account_v1(&self) -> AccountV1<'_> {
         AccountV1 { base: self }
     }
     /// Returns a handle for accessing [ChampionMasteryV4](crate::endpoints::ChampionMasteryV4) endpoints.
@@ -42,7 +42,7 @@ impl RiotApi {
     ///
     /// Note: this method is automatically generated.
     #[inline]
-    pub fn champion_mastery_v4(&self) -> ChampionMasteryV4 {
+    pub fn champion_mastery_v4(&self) -> ChampionMasteryV4<'_> {
         ChampionMasteryV4 { base: self }
     }
     /// Returns a handle for accessing [ChampionV3](crate::endpoints::ChampionV3) endpoints.
@@ -51,7 +51,7 @@ impl RiotApi {
     ///
     /// Note: this method is automatically generated.
     #[inline]
-    pub fn champion_v3(&self) -> ChampionV3 {
+    pub fn champion_v3(&self) -> ChampionV3<'_> {
         ChampionV3 { base: self }
     }
     /// Returns a handle for accessing [ClashV1](crate::endpoints::ClashV1) endpoints.
@@ -60,7 +60,7 @@ impl RiotApi {
     ///
     /// Note: this method is automatically generated.
     #[inline]
-    pub fn clash_v1(&self) -> ClashV1 {
+    pub fn clash_v1(&self) -> ClashV1<'_> {
         ClashV1 { base: self }
     }
     /// Returns a handle for accessing [LeagueExpV4](crate::endpoints::LeagueExpV4) endpoints.
@@ -69,7 +69,7 @@ impl RiotApi {
     ///
     /// Note: this method is automatically generated.
     #[inline]
-    pub fn league_exp_v4(&self) -> LeagueExpV4 {
+    pub fn league_exp_v4(&self) -> LeagueExpV4<'_> {
         LeagueExpV4 { base: self }
     }
     /// Returns a handle for accessing [LeagueV4](crate::endpoints::LeagueV4) endpoints.
@@ -78,7 +78,7 @@ impl RiotApi {
     ///
     /// Note: this method is automatically generated.
     #[inline]
-    pub fn league_v4(&self) -> LeagueV4 {
+    pub fn league_v4(&self) -> LeagueV4<'_> {
         LeagueV4 { base: self }
     }
     /// Returns a handle for accessing [LolChallengesV1](crate::endpoints::LolChallengesV1) endpoints.
@@ -87,7 +87,7 @@ impl RiotApi {
     ///
     /// Note: this method is automatically generated.
     #[inline]
-    pub fn lol_challenges_v1(&self) -> LolChallengesV1 {
+    pub fn lol_challenges_v1(&self) -> LolChallengesV1<'_> {
         LolChallengesV1 { base: self }
     }
     /// Returns a handle for accessing [LolStatusV3](crate::endpoints::LolStatusV3) endpoints.
@@ -96,7 +96,7 @@ impl RiotApi {
     ///
     /// Note: this method is automatically generated.
     #[inline]
-    pub fn lol_status_v3(&self) -> LolStatusV3 {
+    pub fn lol_status_v3(&self) -> LolStatusV3<'_> {
         LolStatusV3 { base: self }
     }
     /// Returns a handle for accessing [LolStatusV4](crate::endpoints::LolStatusV4) endpoints.
@@ -105,7 +105,7 @@ impl RiotApi {
     ///
     /// Note: this method is automatically generated.
     #[inline]
-    pub fn lol_status_v4(&self) -> LolStatusV4 {
+    pub fn lol_status_v4(&self) -> LolStatusV4<'_> {
         LolStatusV4 { base: self }
     }
     /// Returns a handle for accessing [LorDeckV1](crate::endpoints::LorDeckV1) endpoints.
@@ -114,7 +114,7 @@ impl RiotApi {
     ///
     /// Note: this method is automatically generated.
     #[inline]
-    pub fn lor_deck_v1(&self) -> LorDeckV1 {
+    pub fn lor_deck_v1(&self) -> LorDeckV1<'_> {
         LorDeckV1 { base: self }
     }
     /// Returns a handle for accessing [LorInventoryV1](crate::endpoints::LorInventoryV1) endpoints.
@@ -123,7 +123,7 @@ impl RiotApi {
     ///
     /// Note: this method is automatically generated.
     #[inline]
-    pub fn lor_inventory_v1(&self) -> LorInventoryV1 {
+    pub fn lor_inventory_v1(&self) -> LorInventoryV1<'_> {
         LorInventoryV1 { base: self }
     }
     /// Returns a handle for accessing [LorMatchV1](crate::endpoints::LorMatchV1) endpoints.
@@ -132,7 +132,7 @@ impl RiotApi {
     ///
     /// Note: this method is automatically generated.
     #[inline]
-    pub fn lor_match_v1(&self) -> LorMatchV1 {
+    pub fn lor_match_v1(&self) -> LorMatchV1<'_> {
         LorMatchV1 { base: self }
     }
     /// Returns a handle for accessing [LorRankedV1](crate::endpoints::LorRankedV1) endpoints.
@@ -141,7 +141,7 @@ impl RiotApi {
     ///
     /// Note: this method is automatically generated.
     #[inline]
-    pub fn lor_ranked_v1(&self) -> LorRankedV1 {
+    pub fn lor_ranked_v1(&self) -> LorRankedV1<'_> {
         LorRankedV1 { base: self }
     }
     /// Returns a handle for accessing [LorStatusV1](crate::endpoints::LorStatusV1) endpoints.
@@ -150,7 +150,7 @@ impl RiotApi {
     ///
     /// Note: this method is automatically generated.
     #[inline]
-    pub fn lor_status_v1(&self) -> LorStatusV1 {
+    pub fn lor_status_v1(&self) -> LorStatusV1<'_> {
         LorStatusV1 { base: self }
     }
     /// Returns a handle for accessing [MatchV5](crate::endpoints::MatchV5) endpoints.
@@ -159,7 +159,7 @@ impl RiotApi {
     ///
     /// Note: this method is automatically generated.
     #[inline]
-    pub fn match_v5(&self) -> MatchV5 {
+    pub fn match_v5(&self) -> MatchV5<'_> {
         MatchV5 { base: self }
     }
     /// Returns a handle for accessing [SpectatorTftV5](crate::endpoints::SpectatorTftV5) endpoints.
@@ -168,7 +168,7 @@ impl RiotApi {
     ///
     /// Note: this method is automatically generated.
     #[inline]
-    pub fn spectator_tft_v5(&self) -> SpectatorTftV5 {
+    pub fn spectator_tft_v5(&self) -> SpectatorTftV5<'_> {
         SpectatorTftV5 { base: self }
     }
     /// Returns a handle for accessing [SpectatorV4](crate::endpoints::SpectatorV4) endpoints.
@@ -177,7 +177,7 @@ impl RiotApi {
     ///
     /// Note: this method is automatically generated.
     #[inline]
-    pub fn spectator_v4(&self) -> SpectatorV4 {
+    pub fn spectator_v4(&self) -> SpectatorV4<'_> {
         SpectatorV4 { base: self }
     }
     /// Returns a handle for accessing [SpectatorV5](crate::endpoints::SpectatorV5) endpoints.
@@ -186,7 +186,7 @@ impl RiotApi {
     ///
     /// Note: this method is automatically generated.
     #[inline]
-    pub fn spectator_v5(&self) -> SpectatorV5 {
+    pub fn spectator_v5(&self) -> SpectatorV5<'_> {
         SpectatorV5 { base: self }
     }
     /// Returns a handle for accessing [SummonerV4](crate::endpoints::SummonerV4) endpoints.
@@ -195,7 +195,7 @@ impl RiotApi {
     ///
     /// Note: this method is automatically generated.
     #[inline]
-    pub fn summoner_v4(&self) -> SummonerV4 {
+    pub fn summoner_v4(&self) -> SummonerV4<'_> {
         SummonerV4 { base: self }
     }
     /// Returns a handle for accessing [TftLeagueV1](crate::endpoints::TftLeagueV1) endpoints.
@@ -204,7 +204,7 @@ impl RiotApi {
     ///
     /// Note: this method is automatically generated.
     #[inline]
-    pub fn tft_league_v1(&self) -> TftLeagueV1 {
+    pub fn tft_league_v1(&self) -> TftLeagueV1<'_> {
         TftLeagueV1 { base: self }
     }
     /// Returns a handle for accessing [TftMatchV1](crate::endpoints::TftMatchV1) endpoints.
@@ -213,7 +213,7 @@ impl RiotApi {
     ///
     /// Note: this method is automatically generated.
     #[inline]
-    pub fn tft_match_v1(&self) -> TftMatchV1 {
+    pub fn tft_match_v1(&self) -> TftMatchV1<'_> {
         TftMatchV1 { base: self }
     }
     /// Returns a handle for accessing [TftStatusV1](crate::endpoints::TftStatusV1) endpoints.
@@ -222,7 +222,7 @@ impl RiotApi {
     ///
     /// Note: this method is automatically generated.
     #[inline]
-    pub fn tft_status_v1(&self) -> TftStatusV1 {
+    pub fn tft_status_v1(&self) -> TftStatusV1<'_> {
         TftStatusV1 { base: self }
     }
     /// Returns a handle for accessing [TftSummonerV1](crate::endpoints::TftSummonerV1) endpoints.
@@ -231,7 +231,7 @@ impl RiotApi {
     ///
     /// Note: this method is automatically generated.
     #[inline]
-    pub fn tft_summoner_v1(&self) -> TftSummonerV1 {
+    pub fn tft_summoner_v1(&self) -> TftSummonerV1<'_> {
         TftSummonerV1 { base: self }
     }
     /// Returns a handle for accessing [TournamentStubV5](crate::endpoints::TournamentStubV5) endpoints.
@@ -240,7 +240,7 @@ impl RiotApi {
     ///
     /// Note: this method is automatically generated.
     #[inline]
-    pub fn tournament_stub_v5(&self) -> TournamentStubV5 {
+    pub fn tournament_stub_v5(&self) -> TournamentStubV5<'_> {
         TournamentStubV5 { base: self }
     }
     /// Returns a handle for accessing [TournamentV5](crate::endpoints::TournamentV5) endpoints.
@@ -249,7 +249,7 @@ impl RiotApi {
     ///
     /// Note: this method is automatically generated.
     #[inline]
-    pub fn tournament_v5(&self) -> TournamentV5 {
+    pub fn tournament_v5(&self) -> TournamentV5<'_> {
         TournamentV5 { base: self }
     }
     /// Returns a handle for accessing [ValContentV1](crate::endpoints::ValContentV1) endpoints.
@@ -258,7 +258,7 @@ impl RiotApi {
     ///
     /// Note: this method is automatically generated.
     #[inline]
-    pub fn val_content_v1(&self) -> ValContentV1 {
+    pub fn val_content_v1(&self) -> ValContentV1<'_> {
         ValContentV1 { base: self }
     }
     /// Returns a handle for accessing [ValMatchV1](crate::endpoints::ValMatchV1) endpoints.
@@ -267,7 +267,7 @@ impl RiotApi {
     ///
     /// Note: this method is automatically generated.
     #[inline]
-    pub fn val_match_v1(&self) -> ValMatchV1 {
+    pub fn val_match_v1(&self) -> ValMatchV1<'_> {
         ValMatchV1 { base: self }
     }
     /// Returns a handle for accessing [ValRankedV1](crate::endpoints::ValRankedV1) endpoints.
@@ -276,7 +276,7 @@ impl RiotApi {
     ///
     /// Note: this method is automatically generated.
     #[inline]
-    pub fn val_ranked_v1(&self) -> ValRankedV1 {
+    pub fn val_ranked_v1(&self) -> ValRankedV1<'_> {
         ValRankedV1 { base: self }
     }
     /// Returns a handle for accessing [ValStatusV1](crate::endpoints::ValStatusV1) endpoints.
@@ -285,7 +285,7 @@ impl RiotApi {
     ///
     /// Note: this method is automatically generated.
     #[inline]
-    pub fn val_status_v1(&self) -> ValStatusV1 {
+    pub fn val_status_v1(&self) -> ValStatusV1<'_> {
         ValStatusV1 { base: self }
     }
 }
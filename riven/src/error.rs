@@ -1,6 +1,13 @@
 use std::fmt;
+use std::sync::Arc;
 
 use reqwest::{Error, Response, StatusCode};
+use serde::{Deserialize, Serialize};
+
+use crate::cache::CachedResponse;
+use crate::metrics::RateLimitCause;
+use crate::time::Duration;
+use crate::transport::TransportError;
 
 /// Result containing RiotApiError on failure.
 pub type Result<T> = std::result::Result<T, RiotApiError>;
@@ -8,14 +15,130 @@ pub type Result<T> = std::result::Result<T, RiotApiError>;
 /// An error that occurred while processing a Riot API request.
 ///
 /// Although Riven may make multiple requests due to retries, this will always
-/// contain exactly one reqwest::Error for the final request which failed.
+/// contain at most one reqwest::Error for the final request which failed.
 #[derive(Debug)]
 pub struct RiotApiError {
-    reqwest_error: Error,
+    source: ErrorSource,
+    kind: ErrorKind,
     retries: u8,
     response: Option<Response>,
     status_code: Option<StatusCode>,
+    riot_status: Option<RiotErrorStatus>,
+}
+
+/// The category of a [`RiotApiError`], see [`RiotApiError::kind`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// `429`, rate limited after exhausting retries.
+    RateLimited {
+        /// Which rate limit was exceeded, from the `X-Rate-Limit-Type` header.
+        cause: RateLimitCause,
+        /// The `Retry-After` header, if present.
+        retry_after: Option<Duration>,
+    },
+    /// `404`, for requests which do not treat not found as `None`.
+    NotFound,
+    /// `401` or `403`, the API key is missing, invalid, expired, or lacks access to the
+    /// endpoint.
+    Unauthorized,
+    /// `400`, the request parameters were invalid.
+    BadRequest,
+    /// `5xx`, the Riot API failed, after exhausting retries.
+    ServerError,
+    /// Any other HTTP error status.
+    OtherStatus,
+    /// The request failed without a response, e.g. a connection error or timeout.
+    Network,
+    /// The response body could not be deserialized.
+    Deserialization,
+    /// The request would have had to wait past its [`Deadline`](crate::Deadline), see
+    /// [`RiotApiError::source_deadline_exceeded`].
+    DeadlineExceeded,
+    /// The request was not sent because the method's circuit breaker is open, see
+    /// [`RiotApiError::source_circuit_open`].
+    CircuitOpen,
+    /// The request was not sent because the API key was rejected and is invalid, see
+    /// [`RiotApiError::source_key_invalid`].
+    KeyInvalid,
+    /// The error was created by [`Middleware`](crate::middleware::Middleware), see
+    /// [`RiotApiError::source_middleware_error`].
+    Middleware,
+}
+
+impl ErrorKind {
+    /// Categorizes an HTTP error status. `response` is used for rate limit details.
+    fn from_status(status: StatusCode, response: Option<&Response>) -> Option<Self> {
+        let kind = match status {
+            StatusCode::TOO_MANY_REQUESTS => {
+                let header = |name| {
+                    response
+                        .and_then(|response| response.headers().get(name))
+                        .and_then(|value| value.to_str().ok())
+                };
+                Self::RateLimited {
+                    cause: RateLimitCause::from_header(header("X-Rate-Limit-Type")),
+                    retry_after: header("Retry-After")
+                        .and_then(|value| value.parse().ok())
+                        .map(Duration::from_secs),
+                }
+            }
+            StatusCode::NOT_FOUND => Self::NotFound,
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => Self::Unauthorized,
+            StatusCode::BAD_REQUEST => Self::BadRequest,
+            status if status.is_server_error() => Self::ServerError,
+            status if status.is_client_error() => Self::OtherStatus,
+            _ => return None,
+        };
+        Some(kind)
+    }
+}
+
+/// Riot's JSON error body, `{"status":{"message":"...","status_code":403}}`, see
+/// [`RiotApiError::riot_status`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RiotErrorStatus {
+    /// Error message, e.g. `"Forbidden"`.
+    pub message: String,
+    /// HTTP status code, e.g. `403`.
+    pub status_code: u16,
 }
+
+impl RiotErrorStatus {
+    /// Parses a response body, `None` if it is not a Riot error body.
+    pub(crate) fn from_body(body: &[u8]) -> Option<Self> {
+        #[derive(Deserialize)]
+        struct Body {
+            status: RiotErrorStatus,
+        }
+        serde_json::from_slice::<Body>(body)
+            .ok()
+            .map(|body| body.status)
+    }
+    /// If this is Riot's status for a missing, invalid, or expired API key.
+    pub(crate) fn is_key_rejection(&self) -> bool {
+        matches!(
+            &*self.message,
+            "Unauthorized" | "Forbidden" | "Unknown apikey"
+        )
+    }
+}
+
+/// The underlying cause of a [`RiotApiError`].
+#[derive(Debug)]
+enum ErrorSource {
+    Reqwest(Error),
+    DeadlineExceeded(DeadlineExceeded),
+    CircuitOpen(CircuitOpen),
+    KeyInvalid(KeyInvalid),
+    Deserialize(DeserializeError),
+    Middleware(Box<dyn std::error::Error + Send + Sync>),
+    /// A non-reqwest error from an [`HttpTransport`](crate::transport::HttpTransport).
+    Transport(TransportError),
+    /// An error shared between coalesced requests.
+    Shared(Arc<RiotApiError>),
+}
+
 impl RiotApiError {
     pub(crate) fn new(
         reqwest_error: Error,
@@ -23,16 +146,241 @@ impl RiotApiError {
         response: Option<Response>,
         status_code: Option<StatusCode>,
     ) -> Self {
+        let kind = status_code
+            .and_then(|status| ErrorKind::from_status(status, response.as_ref()))
+            .unwrap_or(if reqwest_error.is_decode() {
+                ErrorKind::Deserialization
+            } else {
+                ErrorKind::Network
+            });
         Self {
-            reqwest_error,
+            source: ErrorSource::Reqwest(reqwest_error),
+            kind,
             retries,
             response,
             status_code,
+            riot_status: None,
         }
     }
-    /// The reqwest::Error for the final failed request.
-    pub fn source_reqwest_error(&self) -> &Error {
-        &self.reqwest_error
+    /// Creates an error for a failed `response`, reading the body to parse Riot's JSON error
+    /// status.
+    pub(crate) async fn from_response(
+        reqwest_error: Error,
+        retries: u8,
+        response: Response,
+    ) -> Self {
+        let status = response.status();
+        let url = response.url().clone();
+        match CachedResponse::from_response(response).await {
+            Ok(cached) => {
+                let riot_status = RiotErrorStatus::from_body(&cached.body);
+                let response = cached.into_response_with_url(url);
+                Self {
+                    riot_status,
+                    ..Self::new(reqwest_error, retries, Some(response), Some(status))
+                }
+            }
+            Err(_) => Self::new(reqwest_error, retries, None, Some(status)),
+        }
+    }
+    /// Creates an error for a request which failed without a response.
+    pub(crate) fn from_transport(transport_error: TransportError, retries: u8) -> Self {
+        let source = match transport_error.downcast::<Error>() {
+            Ok(reqwest_error) => ErrorSource::Reqwest(*reqwest_error),
+            Err(transport_error) => ErrorSource::Transport(transport_error),
+        };
+        Self {
+            source,
+            kind: ErrorKind::Network,
+            retries,
+            response: None,
+            status_code: None,
+            riot_status: None,
+        }
+    }
+    pub(crate) fn deadline_exceeded(
+        deadline_exceeded: DeadlineExceeded,
+        retries: u8,
+        status_code: Option<StatusCode>,
+    ) -> Self {
+        Self {
+            source: ErrorSource::DeadlineExceeded(deadline_exceeded),
+            kind: ErrorKind::DeadlineExceeded,
+            retries,
+            response: None,
+            status_code,
+            riot_status: None,
+        }
+    }
+    pub(crate) fn deserialize(
+        deserialize_error: DeserializeError,
+        retries: u8,
+        status_code: Option<StatusCode>,
+    ) -> Self {
+        Self {
+            source: ErrorSource::Deserialize(deserialize_error),
+            kind: status_code
+                .and_then(|status| ErrorKind::from_status(status, None))
+                .unwrap_or(ErrorKind::Deserialization),
+            retries,
+            response: None,
+            status_code,
+            riot_status: None,
+        }
+    }
+    pub(crate) fn circuit_open(circuit_open: CircuitOpen, retries: u8) -> Self {
+        Self {
+            source: ErrorSource::CircuitOpen(circuit_open),
+            kind: ErrorKind::CircuitOpen,
+            retries,
+            response: None,
+            status_code: None,
+            riot_status: None,
+        }
+    }
+    pub(crate) fn key_invalid(key_invalid: KeyInvalid, retries: u8) -> Self {
+        Self {
+            source: ErrorSource::KeyInvalid(key_invalid),
+            kind: ErrorKind::KeyInvalid,
+            retries,
+            response: None,
+            status_code: None,
+            riot_status: None,
+        }
+    }
+    /// Creates an error returned by a [`Middleware`](crate::middleware::Middleware), for
+    /// example to inject failures or to reject requests.
+    pub fn from_middleware(
+        error: impl Into<Box<dyn std::error::Error + Send + Sync>>,
+        status_code: Option<StatusCode>,
+    ) -> Self {
+        Self {
+            source: ErrorSource::Middleware(error.into()),
+            kind: ErrorKind::Middleware,
+            retries: 0,
+            response: None,
+            status_code,
+            riot_status: None,
+        }
+    }
+    /// Creates an error sharing `error`, for coalesced requests.
+    pub(crate) fn shared(error: Arc<RiotApiError>) -> Self {
+        Self {
+            kind: error.kind,
+            retries: error.retries,
+            response: None,
+            status_code: error.status_code,
+            riot_status: error.riot_status.clone(),
+            source: ErrorSource::Shared(error),
+        }
+    }
+    /// The underlying source, following shared errors.
+    fn root_source(&self) -> &ErrorSource {
+        match &self.source {
+            ErrorSource::Shared(e) => e.root_source(),
+            source => source,
+        }
+    }
+    /// The category of this error, for handling errors without matching on status codes.
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+    /// Riot's JSON error status from the failed response body, e.g.
+    /// `{"status":{"message":"Forbidden","status_code":403}}`.
+    /// `None` if the request was not sent, or if the body was not a Riot error status.
+    pub fn riot_status(&self) -> Option<&RiotErrorStatus> {
+        self.riot_status.as_ref()
+    }
+    /// The reqwest::Error for the final failed request, if any.
+    ///
+    /// # Breaking change
+    /// This used to return `&reqwest::Error`, as every error had one. Errors from opt-in
+    /// features, such as deadlines or circuit breakers, do not, so this now returns an
+    /// `Option`. Renamed to [`Self::reqwest_error`].
+    #[deprecated(note = "renamed to `reqwest_error`")]
+    pub fn source_reqwest_error(&self) -> Option<&Error> {
+        self.reqwest_error()
+    }
+    /// The reqwest::Error for the final failed request, if any.
+    /// `None` if the request failed due to its deadline, see [`Self::is_deadline_exceeded`],
+    /// due to an open circuit breaker, see [`Self::is_circuit_open`], due to an invalid API
+    /// key, see [`Self::is_key_invalid`], if the response body could not be deserialized, see
+    /// [`Self::source_deserialize_error`], if created by middleware, see
+    /// [`Self::source_middleware_error`], or if a custom transport failed, see
+    /// [`Self::source_transport_error`].
+    pub fn reqwest_error(&self) -> Option<&Error> {
+        match self.root_source() {
+            ErrorSource::Reqwest(e) => Some(e),
+            _ => None,
+        }
+    }
+    /// If the request failed because it would have had to wait past its
+    /// [`Deadline`](crate::Deadline).
+    pub fn is_deadline_exceeded(&self) -> bool {
+        matches!(self.root_source(), ErrorSource::DeadlineExceeded(_))
+    }
+    /// Details of the exceeded deadline, if the request failed because it would have had to
+    /// wait past its [`Deadline`](crate::Deadline).
+    pub fn source_deadline_exceeded(&self) -> Option<&DeadlineExceeded> {
+        match self.root_source() {
+            ErrorSource::DeadlineExceeded(e) => Some(e),
+            _ => None,
+        }
+    }
+    /// If the request was not sent because the method's circuit breaker is open, see
+    /// [`circuit_breaker`](crate::circuit_breaker).
+    pub fn is_circuit_open(&self) -> bool {
+        matches!(self.root_source(), ErrorSource::CircuitOpen(_))
+    }
+    /// Details of the open circuit, if the request was not sent because the method's circuit
+    /// breaker is open.
+    pub fn source_circuit_open(&self) -> Option<&CircuitOpen> {
+        match self.root_source() {
+            ErrorSource::CircuitOpen(e) => Some(e),
+            _ => None,
+        }
+    }
+    /// If the request was not sent because the API key was rejected and is invalid, see
+    /// [`key_pool`](crate::key_pool).
+    pub fn is_key_invalid(&self) -> bool {
+        matches!(self.root_source(), ErrorSource::KeyInvalid(_))
+    }
+    /// Details of the invalid key, if the request was not sent because the API key was
+    /// rejected and is invalid.
+    pub fn source_key_invalid(&self) -> Option<&KeyInvalid> {
+        match self.root_source() {
+            ErrorSource::KeyInvalid(e) => Some(e),
+            _ => None,
+        }
+    }
+    /// Details of the failure, including the JSON path and the response body, if the
+    /// response body could not be deserialized.
+    pub fn source_deserialize_error(&self) -> Option<&DeserializeError> {
+        match self.root_source() {
+            ErrorSource::Deserialize(e) => Some(e),
+            _ => None,
+        }
+    }
+    /// The error given to [`Self::from_middleware`], if created by middleware.
+    pub fn source_middleware_error(
+        &self,
+    ) -> Option<&(dyn std::error::Error + Send + Sync + 'static)> {
+        match self.root_source() {
+            ErrorSource::Middleware(e) => Some(&**e),
+            _ => None,
+        }
+    }
+    /// The error returned by a custom [`HttpTransport`](crate::transport::HttpTransport), if
+    /// the final request failed without a response. Errors from the default
+    /// [`ReqwestTransport`](crate::transport::ReqwestTransport) are returned by
+    /// [`Self::reqwest_error`] instead.
+    pub fn source_transport_error(
+        &self,
+    ) -> Option<&(dyn std::error::Error + Send + Sync + 'static)> {
+        match self.root_source() {
+            ErrorSource::Transport(e) => Some(&**e),
+            _ => None,
+        }
     }
     /// The number of retires attempted. Zero means exactly one request, zero retries.
     pub fn retries(&self) -> u8 {
@@ -50,8 +398,13 @@ impl RiotApiError {
     pub fn take_response(&mut self) -> Option<Response> {
         self.response.take()
     }
+    /// Replaces the failed response, e.g. after reading its body.
+    pub(crate) fn set_response(&mut self, response: Response) {
+        self.response = Some(response);
+    }
     /// The failed response's HTTP status code.
-    /// `Some(reqwest::StatusCode)` if the request was sent and failed, OR if parsing the response JSON failed.
+    /// `Some(reqwest::StatusCode)` if the request was sent and failed, OR if parsing the response JSON failed,
+    /// OR if the deadline was exceeded after a retryable failure.
     /// `None` if the request was not sent.
     pub fn status_code(&self) -> Option<StatusCode> {
         self.status_code
@@ -64,6 +417,335 @@ impl fmt::Display for RiotApiError {
 }
 impl std::error::Error for RiotApiError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
-        Some(&self.reqwest_error)
+        match &self.source {
+            ErrorSource::Reqwest(e) => Some(e),
+            ErrorSource::DeadlineExceeded(e) => Some(e),
+            ErrorSource::CircuitOpen(e) => Some(e),
+            ErrorSource::KeyInvalid(e) => Some(e),
+            ErrorSource::Deserialize(e) => Some(e),
+            ErrorSource::Middleware(e) => Some(&**e),
+            ErrorSource::Transport(e) => Some(&**e),
+            ErrorSource::Shared(e) => Some(&**e),
+        }
+    }
+}
+
+/// Error source when a request would have had to wait past its [`Deadline`](crate::Deadline),
+/// see [`RiotApiError::is_deadline_exceeded`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeadlineExceeded {
+    pub(crate) wait: Option<Duration>,
+    pub(crate) remaining: Duration,
+}
+impl DeadlineExceeded {
+    /// How long the request would have needed to wait, for a rate limit token or a retry
+    /// backoff. `None` if unknown, for example if waiting behind higher priority requests.
+    pub fn wait(&self) -> Option<Duration> {
+        self.wait
+    }
+    /// Time remaining until the deadline, when the request failed.
+    pub fn remaining(&self) -> Duration {
+        self.remaining
+    }
+}
+impl fmt::Display for DeadlineExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.wait {
+            Some(wait) => write!(
+                f,
+                "deadline exceeded: would wait {:?} with {:?} remaining",
+                wait, self.remaining
+            ),
+            None => write!(f, "deadline exceeded while waiting for rate limit"),
+        }
+    }
+}
+impl std::error::Error for DeadlineExceeded {}
+
+/// Error source when a request was not sent because the method's circuit breaker is open, see
+/// [`RiotApiError::is_circuit_open`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CircuitOpen {
+    pub(crate) retry_in: Option<Duration>,
+}
+impl CircuitOpen {
+    /// Time until the circuit becomes half-open and allows probe requests. `None` if already
+    /// half-open, with the maximum number of probes in flight.
+    pub fn retry_in(&self) -> Option<Duration> {
+        self.retry_in
+    }
+}
+impl fmt::Display for CircuitOpen {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.retry_in {
+            Some(retry_in) => write!(f, "circuit breaker open, half-open in {:?}", retry_in),
+            None => write!(f, "circuit breaker half-open, waiting for probe"),
+        }
+    }
+}
+impl std::error::Error for CircuitOpen {}
+
+/// Error source when a request was not sent because the API key was rejected and is invalid,
+/// see [`RiotApiError::is_key_invalid`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyInvalid {
+    pub(crate) index: usize,
+    pub(crate) status: StatusCode,
+    pub(crate) probe_in: Duration,
+}
+impl KeyInvalid {
+    /// The index of the invalid key, see
+    /// [`RiotApi::api_key_status`](crate::RiotApi::api_key_status).
+    pub fn index(&self) -> usize {
+        self.index
+    }
+    /// The status of the response which rejected the key, `401` or `403`.
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+    /// Time until the next probe request is allowed.
+    pub fn probe_in(&self) -> Duration {
+        self.probe_in
+    }
+}
+impl fmt::Display for KeyInvalid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "API key {} invalid after {}, probe in {:?}",
+            self.index, self.status, self.probe_in
+        )
+    }
+}
+impl std::error::Error for KeyInvalid {}
+
+/// Error source when a response body could not be deserialized, see
+/// [`RiotApiError::source_deserialize_error`].
+#[derive(Debug)]
+pub struct DeserializeError {
+    error: serde_json::Error,
+    path: String,
+    body: Vec<u8>,
+}
+impl DeserializeError {
+    /// Maximum length of [`Self::body_excerpt`], in bytes.
+    const EXCERPT_LEN: usize = 200;
+
+    pub(crate) fn new(error: serde_json::Error, body: Vec<u8>) -> Self {
+        let path = json_path(&body[..error_offset(&error, &body)]);
+        Self { error, path, body }
+    }
+    /// The JSON path of the value which failed to deserialize, e.g.
+    /// `info.participants[3].challenges.kda`, or `.` for the root value. For missing fields,
+    /// this is the path of the object missing the field.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+    /// The underlying `serde_json` error, whose message includes the expected type.
+    pub fn json_error(&self) -> &serde_json::Error {
+        &self.error
+    }
+    /// The full response body.
+    pub fn body(&self) -> &[u8] {
+        &self.body
+    }
+    /// An excerpt of the response body around the failure, at most
+    /// 200 bytes, with `...` marking truncation.
+    pub fn body_excerpt(&self) -> String {
+        let offset = error_offset(&self.error, &self.body);
+        let start = offset.saturating_sub(Self::EXCERPT_LEN / 2);
+        let end = (start + Self::EXCERPT_LEN).min(self.body.len());
+        format!(
+            "{}{}{}",
+            if 0 < start { "..." } else { "" },
+            String::from_utf8_lossy(&self.body[start..end]),
+            if end < self.body.len() { "..." } else { "" },
+        )
+    }
+}
+impl fmt::Display for DeserializeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "failed to deserialize response at `{}`: {}, body: {}",
+            self.path,
+            self.error,
+            self.body_excerpt()
+        )
+    }
+}
+impl std::error::Error for DeserializeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// Byte offset in `body` where `error` occurred.
+fn error_offset(error: &serde_json::Error, body: &[u8]) -> usize {
+    let line_start = body
+        .split_inclusive(|&b| b'\n' == b)
+        .take(error.line().saturating_sub(1))
+        .map(<[u8]>::len)
+        .sum::<usize>();
+    (line_start + error.column()).min(body.len())
+}
+
+/// Gets the JSON path of the innermost value being parsed at the end of the (truncated)
+/// JSON `prefix`.
+fn json_path(prefix: &[u8]) -> String {
+    enum Frame {
+        Object { key: Option<String>, in_key: bool },
+        Array { index: usize },
+    }
+    let mut stack = Vec::new();
+    let mut bytes = prefix.iter().copied();
+    while let Some(b) = bytes.next() {
+        match b {
+            b'{' => stack.push(Frame::Object {
+                key: None,
+                in_key: true,
+            }),
+            b'[' => stack.push(Frame::Array { index: 0 }),
+            b'}' | b']' => {
+                stack.pop();
+            }
+            b':' => {
+                if let Some(Frame::Object { in_key, .. }) = stack.last_mut() {
+                    *in_key = false;
+                }
+            }
+            b',' => match stack.last_mut() {
+                Some(Frame::Object { in_key, .. }) => *in_key = true,
+                Some(Frame::Array { index }) => *index += 1,
+                None => {}
+            },
+            b'"' => {
+                let mut string = Vec::new();
+                while let Some(b) = bytes.next() {
+                    match b {
+                        b'"' => break,
+                        b'\\' => string.extend(bytes.next()),
+                        b => string.push(b),
+                    }
+                }
+                if let Some(Frame::Object { key, in_key: true }) = stack.last_mut() {
+                    *key = Some(String::from_utf8_lossy(&string).into_owned());
+                }
+            }
+            _ => {}
+        }
+    }
+    let path: String = stack
+        .iter()
+        .filter_map(|frame| match frame {
+            Frame::Object { key: Some(key), .. } => Some(format!(".{}", key)),
+            Frame::Object { key: None, .. } => None,
+            Frame::Array { index } => Some(format!("[{}]", index)),
+        })
+        .collect();
+    match path.strip_prefix('.') {
+        Some(path) => path.to_owned(),
+        None if path.is_empty() => ".".to_owned(),
+        None => path,
+    }
+}
+
+#[cfg(all(test, not(target_family = "wasm")))]
+mod test {
+    use std::collections::HashMap;
+    #[cfg(feature = "tokio")]
+    use std::sync::Arc;
+
+    #[cfg(feature = "tokio")]
+    use reqwest::Method;
+
+    use super::*;
+    #[cfg(feature = "tokio")]
+    use crate::retry::NoRetry;
+    #[cfg(feature = "tokio")]
+    use crate::transport::{MockResponse, MockTransport};
+    #[cfg(feature = "tokio")]
+    use crate::{RiotApi, RiotApiConfig};
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    #[allow(deprecated)]
+    async fn kind_and_riot_status() {
+        let transport = Arc::new(MockTransport::new());
+        transport.push_response(
+            MockResponse::new(StatusCode::FORBIDDEN)
+                .set_body(r#"{"status":{"message":"Forbidden","status_code":403}}"#),
+        );
+        transport.push_response(
+            MockResponse::new(StatusCode::TOO_MANY_REQUESTS).set_retry_after(0, "service"),
+        );
+        transport.push_response(MockResponse::new(StatusCode::OK).set_body("not json"));
+        let riot_api = RiotApi::new(
+            RiotApiConfig::with_key("RGAPI-test")
+                .set_retry_policy(Arc::new(NoRetry))
+                .set_transport(transport),
+        );
+        let get = || {
+            let request = riot_api.request(Method::GET, "na1", "/test");
+            riot_api.execute_val::<u32>("test.method", "NA1", request)
+        };
+
+        let err = get().await.unwrap_err();
+        assert_eq!(ErrorKind::Unauthorized, err.kind());
+        let riot_status = err.riot_status().unwrap();
+        assert_eq!(
+            ("Forbidden", 403),
+            (&*riot_status.message, riot_status.status_code)
+        );
+        assert!(err.response().is_some());
+        assert_eq!(
+            Some(StatusCode::FORBIDDEN),
+            err.reqwest_error().unwrap().status()
+        );
+        assert_eq!(
+            Some(StatusCode::FORBIDDEN),
+            err.source_reqwest_error().unwrap().status()
+        );
+
+        let err = get().await.unwrap_err();
+        assert_eq!(
+            ErrorKind::RateLimited {
+                cause: RateLimitCause::Service,
+                retry_after: Some(Duration::ZERO),
+            },
+            err.kind()
+        );
+        assert_eq!(None, err.riot_status());
+
+        let err = get().await.unwrap_err();
+        assert_eq!(ErrorKind::Deserialization, err.kind());
+        assert!(err.reqwest_error().is_none());
+        assert!(err.source_deserialize_error().is_some());
+
+        let err = get().await.unwrap_err();
+        assert_eq!(ErrorKind::Network, err.kind());
+        assert!(err.reqwest_error().is_none());
+        assert!(err.source_reqwest_error().is_none());
+    }
+
+    #[test]
+    fn deserialize_path() {
+        let body = br#"{"info": {"participants": [{}, {"challenges": {"kda": "high"}}]}}"#;
+        let error = serde_json::from_slice::<
+            HashMap<String, HashMap<String, Vec<HashMap<String, HashMap<String, f64>>>>>,
+        >(body)
+        .unwrap_err();
+        let error = DeserializeError::new(error, body.to_vec());
+        assert_eq!("info.participants[1].challenges.kda", error.path());
+        assert!(error.to_string().contains("expected f64"), "{}", error);
+        assert_eq!(body, error.body());
+
+        // Multiple lines, and an escaped quote in a key.
+        let body = b"[\n  {\"a\\\"\": [3, \"x\"]}]";
+        let error = serde_json::from_slice::<Vec<HashMap<String, Vec<u32>>>>(body).unwrap_err();
+        assert_eq!(
+            "[0].a\"[1]",
+            DeserializeError::new(error, body.to_vec()).path()
+        );
     }
 }
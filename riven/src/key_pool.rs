@@ -0,0 +1,827 @@
+//! Pools of multiple API keys.
+//!
+//! Additional keys are added with
+//! [`RiotApiConfig::add_api_key`](crate::RiotApiConfig::add_api_key). Each key has its own
+//! app and method rate limits, and each request is sent with whichever key has rate limit
+//! capacity soonest.
+//!
+//! If a key receives a `401 Unauthorized` or `403 Forbidden` response while other keys are
+//! still in rotation, it is taken out of rotation and the request is retried with another
+//! key. Requests with an `Authorization` header, such as RSO requests on behalf of a user,
+//! never count against the key, as they may be rejected due to the user's token. Neither
+//! do responses whose Riot error status is not about the API key. Each probe interval (see
+//! [`AuthFailureConfig::set_probe_interval`], 60 seconds by default) one request is sent with
+//! a key out of rotation as a probe; if it is not rejected the key is put back into rotation.
+//! The state of each key can be observed with
+//! [`RiotApi::api_key_status`](crate::RiotApi::api_key_status).
+//!
+//! Riot issues separate keys for each product. Keys added with
+//! [`RiotApiConfig::add_family_api_key`](crate::RiotApiConfig::add_family_api_key) are only
+//! used for endpoints of that [`GameFamily`], and endpoints of a family with no keys of its own
+//! use the other keys. A key is only taken out of rotation if another key for the same
+//! endpoints is still in rotation.
+//!
+//! Keys can be replaced at runtime with
+//! [`RiotApi::set_api_key`](crate::RiotApi::set_api_key), for example when a development key
+//! expires. The replaced key keeps its learned rate limits, and is put back into rotation.
+//! Requests already sent are not affected, but retries use the new key.
+//!
+//! When enabled with
+//! [`RiotApiConfig::set_auth_failure_halt`](crate::RiotApiConfig::set_auth_failure_halt), a
+//! `401` or `403` response on the last key in rotation for an endpoint makes the key
+//! _invalid_, rather than continuing to send doomed requests which use up rate limit tokens.
+//! While the key is invalid, requests using it fail immediately, without being sent, with an
+//! error where [`RiotApiError::is_key_invalid`](crate::RiotApiError::is_key_invalid) is true.
+//! After each probe interval one request is sent as a probe; if it is not rejected the key is
+//! valid again. Replacing the key with [`RiotApi::set_api_key`](crate::RiotApi::set_api_key)
+//! also makes it valid again. [`AuthFailureConfig::set_on_key_invalid`] sets a callback for
+//! when a key becomes invalid, for example to alert someone.
+//!
+//! ```
+//! use riven::key_pool::GameFamily;
+//! use riven::RiotApiConfig;
+//!
+//! let config = RiotApiConfig::with_key("RGAPI-first-key")
+//!     .add_api_key("RGAPI-second-key")
+//!     .add_family_api_key(GameFamily::Tft, "RGAPI-tft-key");
+//! ```
+use std::fmt;
+use std::sync::Arc;
+
+use parking_lot::RwLock;
+use reqwest::header::HeaderValue;
+use reqwest::StatusCode;
+#[cfg(feature = "tracing")]
+use tracing as log;
+
+use crate::time::{Duration, Instant};
+use crate::{KeyInvalid, RiotApiConfig};
+
+/// Callback for when a key becomes invalid, see [`AuthFailureConfig::set_on_key_invalid`].
+type OnKeyInvalid = Arc<dyn Fn(&ApiKeyStatus) + Send + Sync>;
+
+/// Configuration for halting requests when API keys are rejected, see the
+/// [module docs](self).
+#[derive(Clone)]
+pub struct AuthFailureConfig {
+    probe_interval: Duration,
+    on_key_invalid: Option<OnKeyInvalid>,
+}
+
+impl fmt::Debug for AuthFailureConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AuthFailureConfig")
+            .field("probe_interval", &self.probe_interval)
+            .field("on_key_invalid", &self.on_key_invalid.is_some())
+            .finish()
+    }
+}
+
+impl Default for AuthFailureConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AuthFailureConfig {
+    /// Creates a new `AuthFailureConfig` with the following settings:
+    ///
+    /// * `probe_interval = 60 sec`.
+    /// * No `on_key_invalid` callback.
+    pub fn new() -> Self {
+        Self {
+            probe_interval: Duration::from_secs(60),
+            on_key_invalid: None,
+        }
+    }
+
+    /// Sets how often a probe request is sent while a key is invalid or out of rotation.
+    ///
+    /// # Returns
+    /// `self`, for chaining.
+    pub fn set_probe_interval(mut self, probe_interval: Duration) -> Self {
+        self.probe_interval = probe_interval;
+        self
+    }
+
+    /// Sets a callback which is called with the key's status whenever a key becomes invalid.
+    /// The callback is called from within the request, so it should not block.
+    ///
+    /// # Returns
+    /// `self`, for chaining.
+    pub fn set_on_key_invalid(
+        mut self,
+        on_key_invalid: impl Fn(&ApiKeyStatus) + Send + Sync + 'static,
+    ) -> Self {
+        self.on_key_invalid = Some(Arc::new(on_key_invalid));
+        self
+    }
+}
+
+/// A family of endpoints which may use its own API keys, see
+/// [`RiotApiConfig::add_family_api_key`](crate::RiotApiConfig::add_family_api_key).
+///
+/// League of Legends and Riot account endpoints are not part of any family, and use the keys
+/// added with [`RiotApiConfig::with_key`](crate::RiotApiConfig::with_key) and
+/// [`RiotApiConfig::add_api_key`](crate::RiotApiConfig::add_api_key).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum GameFamily {
+    /// Teamfight Tactics, `tft-*` and `spectator-tft-*` endpoints.
+    Tft,
+    /// Legends of Runeterra, `lor-*` endpoints.
+    Lor,
+    /// VALORANT, `val-*` endpoints.
+    Val,
+}
+
+impl GameFamily {
+    /// Returns the family of the endpoint with the given method ID, e.g.
+    /// `"tft-match-v1.getMatch"`, or `None` if it is not part of any family.
+    pub fn from_method_id(method_id: &str) -> Option<Self> {
+        if method_id.starts_with("tft-") || method_id.starts_with("spectator-tft-") {
+            Some(Self::Tft)
+        } else if method_id.starts_with("lor-") {
+            Some(Self::Lor)
+        } else if method_id.starts_with("val-") {
+            Some(Self::Val)
+        } else {
+            None
+        }
+    }
+}
+
+/// The state of an API key in the pool, see [`RiotApi::api_key_status`](crate::RiotApi::api_key_status).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ApiKeyStatus {
+    /// The index of the key, in the order the keys were configured. If no keys outside a
+    /// [`GameFamily`] were configured, index `0` is the client builder's key.
+    pub index: usize,
+    /// The last four characters of the key, to identify it without revealing it.
+    pub hint: String,
+    /// The family of endpoints the key is used for, or `None` for endpoints not in a family
+    /// (or with no keys of their own).
+    pub family: Option<GameFamily>,
+    /// If the key is out of rotation, the status of the response which caused it.
+    pub disabled: Option<StatusCode>,
+    /// If the key is invalid, the status of the response which caused it, see
+    /// [`AuthFailureConfig`].
+    pub invalid: Option<StatusCode>,
+}
+
+/// The API keys used by a [`RiotApi`](crate::RiotApi). Always contains at least one key.
+#[derive(Debug)]
+pub(crate) struct KeyPool {
+    /// The `X-Riot-Token` value of each key, or `None` if the key is supplied by the client
+    /// builder.
+    values: RwLock<Vec<Option<HeaderValue>>>,
+    /// Identifier of each key in rate limit store keys, see [`Self::store_id`].
+    store_ids: Vec<Option<String>>,
+    /// The family of each key.
+    families: Vec<Option<GameFamily>>,
+    /// For each key, set while the key is out of rotation.
+    disabled: RwLock<Vec<Option<DisabledKey>>>,
+    /// How often keys which are invalid or out of rotation are probed.
+    probe_interval: Duration,
+    /// Halting on auth failure, if enabled.
+    auth_failure: Option<AuthFailureConfig>,
+    /// For each key, set while the key is invalid.
+    invalid: RwLock<Vec<Option<InvalidKey>>>,
+}
+
+/// State of a key out of rotation.
+#[derive(Debug, Clone, Copy)]
+struct DisabledKey {
+    /// The status of the response which rejected the key.
+    status: StatusCode,
+    /// When the key was taken out of rotation or the last probe request was allowed.
+    last_probe: Instant,
+}
+
+/// State of an invalid key.
+#[derive(Debug, Clone, Copy)]
+struct InvalidKey {
+    /// The status of the response which rejected the key.
+    status: StatusCode,
+    /// When the key became invalid or the last probe request was allowed.
+    last_probe: Instant,
+}
+
+impl KeyPool {
+    pub fn new(config: &RiotApiConfig) -> Self {
+        let mut values: Vec<_> = config
+            .api_keys
+            .iter()
+            .map(|(_, value)| Some(value.clone()))
+            .collect();
+        let mut families: Vec<_> = config.api_keys.iter().map(|&(family, _)| family).collect();
+        if !families.contains(&None) {
+            // Key from the client builder's default headers.
+            values.insert(0, None);
+            families.insert(0, None);
+        }
+        let store_ids = values
+            .iter()
+            .map(|value| {
+                value
+                    .as_ref()
+                    .map(|value| format!("{:016x}", fnv1a(value.as_bytes())))
+            })
+            .collect();
+        Self {
+            disabled: RwLock::new(vec![None; values.len()]),
+            probe_interval: config
+                .auth_failure
+                .clone()
+                .unwrap_or_default()
+                .probe_interval,
+            auth_failure: config.auth_failure.clone(),
+            invalid: RwLock::new(vec![None; values.len()]),
+            values: RwLock::new(values),
+            store_ids,
+            families,
+        }
+    }
+
+    /// The number of keys.
+    pub fn len(&self) -> usize {
+        self.families.len()
+    }
+
+    /// The `X-Riot-Token` value of the key at `index`, if not supplied by the client builder.
+    pub fn value(&self, index: usize) -> Option<HeaderValue> {
+        self.values.read()[index].clone()
+    }
+
+    /// Identifies the key at `index` in [rate limit store](crate::rate_limit_store) keys, or
+    /// `None` for the client builder's key. Derived from the key as configured, so it does
+    /// not depend on the order keys were added in, and is kept if the key is replaced.
+    pub fn store_id(&self, index: usize) -> Option<&str> {
+        self.store_ids[index].as_deref()
+    }
+
+    /// Replaces the key at `index`, puts it back into rotation, and makes it valid.
+    ///
+    /// # Panics
+    /// If `index` is out of range.
+    pub fn set_value(&self, index: usize, value: HeaderValue) {
+        self.values.write()[index] = Some(value);
+        self.disabled.write()[index] = None;
+        self.invalid.write()[index] = None;
+        log::info!(
+            "API key {} replaced ({}).",
+            index,
+            hint(self.value(index).as_ref())
+        );
+    }
+
+    /// The family of keys used for the endpoint `method_id`.
+    fn family(&self, method_id: &str) -> Option<GameFamily> {
+        GameFamily::from_method_id(method_id)
+            .filter(|family| self.families.contains(&Some(*family)))
+    }
+
+    /// The indices of the keys in rotation for the endpoint `method_id`. Never empty.
+    pub fn active_keys(&self, method_id: &str) -> Vec<usize> {
+        let family = self.family(method_id);
+        let disabled = self.disabled.read();
+        (0..self.len())
+            .filter(|&index| self.families[index] == family && disabled[index].is_none())
+            .collect()
+    }
+
+    /// The indices of the keys to send a request to the endpoint `method_id` with: the keys in
+    /// rotation, plus a key out of rotation once per probe interval, as a probe. Never empty.
+    ///
+    /// # Returns
+    /// The key indices, and the claim on the probe, if any. The claim must be kept with
+    /// [`ProbeClaim::keep`] if the probe key is used, otherwise dropping it allows the next
+    /// request to probe the key instead.
+    pub fn request_keys(&self, method_id: &str) -> (Vec<usize>, Option<ProbeClaim<'_>>) {
+        let mut keys = self.active_keys(method_id);
+        let family = self.family(method_id);
+        // Probe the key which was probed least recently.
+        let probe = {
+            let disabled = self.disabled.read();
+            (0..self.len())
+                .filter(|&index| self.families[index] == family)
+                .filter_map(|index| disabled[index].map(|disabled_key| (index, disabled_key)))
+                .min_by_key(|(_, disabled_key)| disabled_key.last_probe)
+                .map(|(index, _)| index)
+        };
+        let mut claim = None;
+        if let Some(index) = probe {
+            let mut disabled = self.disabled.write();
+            // Check again, in case probed concurrently.
+            if let Some(disabled_key) = &mut disabled[index] {
+                if self.probe_interval <= disabled_key.last_probe.elapsed() {
+                    let claimed = Instant::now();
+                    claim = Some(ProbeClaim {
+                        key_pool: self,
+                        index,
+                        previous: disabled_key.last_probe,
+                        claimed,
+                    });
+                    disabled_key.last_probe = claimed;
+                    keys.push(index);
+                    keys.sort_unstable();
+                }
+            }
+        }
+        (keys, claim)
+    }
+
+    /// Takes the key at `index` out of rotation due to a response with `status`, unless it
+    /// is the last key in rotation for its family. Also used for rejected probe requests of
+    /// keys already out of rotation.
+    ///
+    /// # Returns
+    /// If the key was (or stays) out of rotation.
+    pub fn disable(&self, index: usize, status: StatusCode) -> bool {
+        let mut disabled = self.disabled.write();
+        let family = self.families[index];
+        let others_active = (0..self.len())
+            .any(|i| i != index && self.families[i] == family && disabled[i].is_none());
+        if !others_active {
+            return false;
+        }
+        if disabled[index].is_some() {
+            log::warn!(
+                "API key {} rejected by probe request, keeping it out of rotation.",
+                index
+            );
+        } else {
+            log::error!(
+                "API key {} ({}) received {}, taking it out of rotation.",
+                index,
+                hint(self.value(index).as_ref()),
+                status
+            );
+        }
+        disabled[index] = Some(DisabledKey {
+            status,
+            last_probe: Instant::now(),
+        });
+        true
+    }
+
+    /// Checks that a key in rotation for the endpoint `method_id` is valid. If all are
+    /// invalid, allows one probe request per probe interval.
+    pub fn check_valid(&self, method_id: &str) -> Result<(), KeyInvalid> {
+        let Some(auth_failure) = &self.auth_failure else {
+            return Ok(());
+        };
+        let active_keys = self.active_keys(method_id);
+        if active_keys
+            .iter()
+            .any(|&index| self.invalid.read()[index].is_none())
+        {
+            return Ok(());
+        }
+        let mut invalid = self.invalid.write();
+        // Use the key which was probed least recently.
+        let Some((index, invalid_key)) = active_keys
+            .iter()
+            .map(|&index| (index, invalid[index]))
+            .min_by_key(|(_, invalid_key)| invalid_key.map(|invalid_key| invalid_key.last_probe))
+        else {
+            return Ok(());
+        };
+        let Some(invalid_key) = invalid_key else {
+            // Made valid concurrently.
+            return Ok(());
+        };
+        let elapsed = invalid_key.last_probe.elapsed();
+        if auth_failure.probe_interval <= elapsed {
+            log::info!("API key {} invalid, sending probe request.", index);
+            invalid[index] = Some(InvalidKey {
+                last_probe: Instant::now(),
+                ..invalid_key
+            });
+            return Ok(());
+        }
+        Err(KeyInvalid {
+            index,
+            status: invalid_key.status,
+            probe_in: auth_failure.probe_interval - elapsed,
+        })
+    }
+
+    /// Makes the key at `index` invalid due to a response with `status`, if halting on auth
+    /// failure is enabled and the key is in rotation.
+    pub fn invalidate(&self, index: usize, status: StatusCode) {
+        if self.auth_failure.is_none() || self.disabled.read()[index].is_some() {
+            return;
+        }
+        {
+            let mut invalid = self.invalid.write();
+            if invalid[index].is_some() {
+                return;
+            }
+            invalid[index] = Some(InvalidKey {
+                status,
+                last_probe: Instant::now(),
+            });
+        }
+        log::error!(
+            "API key {} ({}) received {}, halting requests until it is replaced or a probe succeeds.",
+            index,
+            hint(self.value(index).as_ref()),
+            status
+        );
+        let on_key_invalid = self
+            .auth_failure
+            .as_ref()
+            .and_then(|auth_failure| auth_failure.on_key_invalid.as_ref());
+        if let Some(on_key_invalid) = on_key_invalid {
+            on_key_invalid(&self.status()[index]);
+        }
+    }
+
+    /// Makes the key at `index` valid and puts it back into rotation after a response which
+    /// was not rejected.
+    pub fn validate(&self, index: usize) {
+        let disabled = self.disabled.read()[index].is_some();
+        if disabled && self.disabled.write()[index].take().is_some() {
+            log::info!(
+                "API key {} accepted by probe request, back in rotation.",
+                index
+            );
+        }
+        if self.invalid.read()[index].is_none() {
+            return;
+        }
+        if self.invalid.write()[index].take().is_some() {
+            log::info!("API key {} accepted by probe request, valid again.", index);
+        }
+    }
+
+    /// The status of each key.
+    pub fn status(&self) -> Vec<ApiKeyStatus> {
+        let disabled = self.disabled.read();
+        let invalid = self.invalid.read();
+        (0..self.len())
+            .map(|index| ApiKeyStatus {
+                index,
+                hint: hint(self.value(index).as_ref()),
+                family: self.families[index],
+                disabled: disabled[index].map(|disabled_key| disabled_key.status),
+                invalid: invalid[index].map(|invalid_key| invalid_key.status),
+            })
+            .collect()
+    }
+}
+
+/// A probe of a key out of rotation, claimed by [`KeyPool::request_keys`] so that concurrent
+/// requests do not also probe the key. Dropping the claim without [`Self::keep`] releases it.
+pub(crate) struct ProbeClaim<'a> {
+    key_pool: &'a KeyPool,
+    index: usize,
+    /// The key's `last_probe` before it was claimed, restored on release.
+    previous: Instant,
+    /// The key's `last_probe` set by the claim.
+    claimed: Instant,
+}
+
+impl ProbeClaim<'_> {
+    /// The index of the key to probe.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Keeps the claim, as the probe request is sent with the key.
+    pub fn keep(self) {
+        log::info!(
+            "API key {} out of rotation, sending probe request.",
+            self.index
+        );
+        std::mem::forget(self);
+    }
+}
+
+impl Drop for ProbeClaim<'_> {
+    fn drop(&mut self) {
+        let mut disabled = self.key_pool.disabled.write();
+        // Unless the key was put back into rotation or rejected again since.
+        if let Some(disabled_key) = &mut disabled[self.index] {
+            if self.claimed == disabled_key.last_probe {
+                disabled_key.last_probe = self.previous;
+            }
+        }
+    }
+}
+
+/// The last four characters of a key.
+/// 64-bit FNV-1a hash. Unlike the `std` hashers, stable across platforms and Rust versions,
+/// so instances sharing a rate limit store agree on each key's [`KeyPool::store_id`].
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+fn hint(value: Option<&HeaderValue>) -> String {
+    let Some(value) = value else {
+        return "client builder".to_owned();
+    };
+    let bytes = value.as_bytes();
+    String::from_utf8_lossy(&bytes[bytes.len().saturating_sub(4)..]).into_owned()
+}
+
+#[cfg(all(test, not(target_family = "wasm")))]
+mod test {
+    #[cfg(feature = "tokio")]
+    use std::sync::Arc;
+
+    #[cfg(feature = "tokio")]
+    use reqwest::Method;
+
+    use super::*;
+    #[cfg(feature = "tokio")]
+    use crate::transport::{MockResponse, MockTransport};
+    #[cfg(feature = "tokio")]
+    use crate::RiotApi;
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn rejected_key_rotation() {
+        let transport = Arc::new(MockTransport::new());
+        transport.push_response(MockResponse::new(StatusCode::FORBIDDEN));
+        transport.push_response(MockResponse::new(StatusCode::OK).set_body("1"));
+        transport.push_response(MockResponse::new(StatusCode::FORBIDDEN));
+        let riot_api = RiotApi::new(
+            RiotApiConfig::with_key("RGAPI-aaaa")
+                .add_api_key("RGAPI-bbbb")
+                .set_transport(transport.clone()),
+        );
+
+        let request = riot_api.request(Method::GET, "na1", "/a");
+        let value: u32 = riot_api
+            .execute_val("test.method", "NA1", request)
+            .await
+            .unwrap();
+        assert_eq!(1, value);
+
+        let status = riot_api.api_key_status();
+        assert_eq!("aaaa", status[0].hint);
+        assert_eq!(Some(StatusCode::FORBIDDEN), status[0].disabled);
+        assert_eq!("bbbb", status[1].hint);
+        assert_eq!(None, status[1].disabled);
+
+        let keys: Vec<_> = transport
+            .requests()
+            .iter()
+            .map(|request| request.headers[RiotApiConfig::RIOT_KEY_HEADER].clone())
+            .collect();
+        assert_eq!(vec!["RGAPI-aaaa", "RGAPI-bbbb"], keys);
+
+        // The last key in rotation is never taken out.
+        let request = riot_api.request(Method::GET, "na1", "/b");
+        let err = riot_api
+            .execute_val::<u32>("test.method", "NA1", request)
+            .await
+            .unwrap_err();
+        assert_eq!(Some(StatusCode::FORBIDDEN), err.status_code());
+        assert_eq!(None, riot_api.api_key_status()[1].disabled);
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn family_keys() {
+        let transport = Arc::new(
+            MockTransport::new()
+                .set_default_response(MockResponse::new(StatusCode::OK).set_body("1")),
+        );
+        let riot_api = RiotApi::new(
+            RiotApiConfig::with_key("RGAPI-lol")
+                .add_family_api_key(GameFamily::Tft, "RGAPI-tft")
+                .set_transport(transport.clone()),
+        );
+        for method_id in [
+            "summoner-v4.getByPUUID",
+            "tft-summoner-v1.getByPUUID",
+            "lor-match-v1.getMatch",
+        ] {
+            let request = riot_api.request(Method::GET, "na1", "/a");
+            let _: u32 = riot_api
+                .execute_val(method_id, "NA1", request)
+                .await
+                .unwrap();
+        }
+        let keys: Vec<_> = transport
+            .requests()
+            .iter()
+            .map(|request| request.headers[RiotApiConfig::RIOT_KEY_HEADER].clone())
+            .collect();
+        assert_eq!(vec!["RGAPI-lol", "RGAPI-tft", "RGAPI-lol"], keys);
+        assert_eq!(Some(GameFamily::Tft), riot_api.api_key_status()[1].family);
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn set_api_key() {
+        let transport = Arc::new(
+            MockTransport::new()
+                .set_default_response(MockResponse::new(StatusCode::OK).set_body("1")),
+        );
+        let riot_api =
+            RiotApi::new(RiotApiConfig::with_key("RGAPI-old").set_transport(transport.clone()));
+        let request = riot_api.request(Method::GET, "na1", "/a");
+        let _: u32 = riot_api
+            .execute_val("test.method", "NA1", request)
+            .await
+            .unwrap();
+        riot_api.set_api_key("RGAPI-new");
+        let request = riot_api.request(Method::GET, "na1", "/a");
+        let _: u32 = riot_api
+            .execute_val("test.method", "NA1", request)
+            .await
+            .unwrap();
+
+        let keys: Vec<_> = transport
+            .requests()
+            .iter()
+            .map(|request| request.headers[RiotApiConfig::RIOT_KEY_HEADER].clone())
+            .collect();
+        assert_eq!(vec!["RGAPI-old", "RGAPI-new"], keys);
+        assert_eq!("-new", riot_api.api_key_status()[0].hint);
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn auth_failure_halt() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let invalid_count = Arc::new(AtomicUsize::new(0));
+        let transport = Arc::new(
+            MockTransport::new()
+                .set_default_response(MockResponse::new(StatusCode::OK).set_body("1")),
+        );
+        transport.push_response(MockResponse::new(StatusCode::UNAUTHORIZED));
+        let auth_failure = AuthFailureConfig::new()
+            .set_probe_interval(Duration::from_secs(3600))
+            .set_on_key_invalid({
+                let invalid_count = invalid_count.clone();
+                move |status| {
+                    assert_eq!(Some(StatusCode::UNAUTHORIZED), status.invalid);
+                    invalid_count.fetch_add(1, Ordering::SeqCst);
+                }
+            });
+        let riot_api = RiotApi::new(
+            RiotApiConfig::with_key("RGAPI-old")
+                .set_auth_failure_halt(auth_failure)
+                .set_transport(transport.clone()),
+        );
+        let get = || async {
+            let request = riot_api.request(Method::GET, "na1", "/a");
+            riot_api
+                .execute_val::<u32>("test.method", "NA1", request)
+                .await
+        };
+
+        let err = get().await.unwrap_err();
+        assert_eq!(Some(StatusCode::UNAUTHORIZED), err.status_code());
+        // Fails immediately, without sending a request.
+        let err = get().await.unwrap_err();
+        assert!(err.is_key_invalid());
+        assert_eq!(
+            StatusCode::UNAUTHORIZED,
+            err.source_key_invalid().unwrap().status()
+        );
+        assert_eq!(1, transport.requests().len());
+        assert_eq!(1, invalid_count.load(Ordering::SeqCst));
+
+        riot_api.set_api_key("RGAPI-new");
+        assert_eq!(1, get().await.unwrap());
+        assert_eq!(None, riot_api.api_key_status()[0].invalid);
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn user_rejection_keeps_key() {
+        let transport = Arc::new(
+            MockTransport::new()
+                .set_default_response(MockResponse::new(StatusCode::OK).set_body("1")),
+        );
+        transport.push_response(
+            MockResponse::new(StatusCode::UNAUTHORIZED)
+                .set_body(r#"{"status":{"message":"Unauthorized","status_code":401}}"#),
+        );
+        transport.push_response(
+            MockResponse::new(StatusCode::FORBIDDEN)
+                .set_body(r#"{"status":{"message":"Forbidden: no access","status_code":403}}"#),
+        );
+        let riot_api = RiotApi::new(
+            RiotApiConfig::with_key("RGAPI-aaaa")
+                .add_api_key("RGAPI-bbbb")
+                .set_auth_failure_halt(AuthFailureConfig::new())
+                .set_transport(transport.clone()),
+        );
+
+        // RSO request rejected due to the user's token.
+        let request = riot_api
+            .request(Method::GET, "americas", "/riot/account/v1/accounts/me")
+            .bearer_auth("user-token");
+        let err = riot_api
+            .execute_val::<u32>("account-v1.getByAccessToken", "AMERICAS", request)
+            .await
+            .unwrap_err();
+        assert_eq!(Some(StatusCode::UNAUTHORIZED), err.status_code());
+        // Riot error status which is not about the key.
+        let request = riot_api.request(Method::GET, "americas", "/a");
+        let err = riot_api
+            .execute_val::<u32>("test.method", "AMERICAS", request)
+            .await
+            .unwrap_err();
+        assert_eq!(Some(StatusCode::FORBIDDEN), err.status_code());
+        assert!(err.response().is_some());
+
+        for status in riot_api.api_key_status() {
+            assert_eq!((None, None), (status.disabled, status.invalid));
+        }
+        assert_eq!(2, transport.requests().len());
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn disabled_key_probe() {
+        let transport = Arc::new(
+            MockTransport::new()
+                .set_default_response(MockResponse::new(StatusCode::OK).set_body("1")),
+        );
+        transport.push_response(MockResponse::new(StatusCode::FORBIDDEN));
+        let riot_api = RiotApi::new(
+            RiotApiConfig::with_key("RGAPI-aaaa")
+                .add_api_key("RGAPI-bbbb")
+                .set_auth_failure_halt(
+                    AuthFailureConfig::new().set_probe_interval(Duration::from_millis(100)),
+                )
+                .set_transport(transport.clone()),
+        );
+        let get = || async {
+            let request = riot_api.request(Method::GET, "na1", "/a");
+            riot_api
+                .execute_val::<u32>("test.method", "NA1", request)
+                .await
+                .unwrap()
+        };
+
+        assert_eq!(1, get().await);
+        assert_eq!(
+            Some(StatusCode::FORBIDDEN),
+            riot_api.api_key_status()[0].disabled
+        );
+        // Probe the key once the interval has passed, putting it back into rotation.
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        while riot_api.api_key_status()[0].disabled.is_some() {
+            assert_eq!(1, get().await);
+            assert!(transport.requests().len() < 10, "Key was not probed.");
+        }
+    }
+
+    #[test]
+    fn unused_probe_released() {
+        let key_pool = KeyPool::new(
+            &RiotApiConfig::with_key("RGAPI-aaaa")
+                .add_api_key("RGAPI-bbbb")
+                .set_auth_failure_halt(
+                    AuthFailureConfig::new().set_probe_interval(Duration::from_millis(50)),
+                ),
+        );
+        assert!(key_pool.disable(0, StatusCode::FORBIDDEN));
+        assert_eq!(vec![1], key_pool.request_keys("test.method").0);
+        std::thread::sleep(Duration::from_millis(100));
+
+        let (keys, probe) = key_pool.request_keys("test.method");
+        assert_eq!(vec![0, 1], keys);
+        assert_eq!(Some(0), probe.as_ref().map(ProbeClaim::index));
+        // Not used, so the next request probes the key instead.
+        drop(probe);
+        let (keys, probe) = key_pool.request_keys("test.method");
+        assert_eq!(vec![0, 1], keys);
+        probe.unwrap().keep();
+        let (keys, probe) = key_pool.request_keys("test.method");
+        assert_eq!(vec![1], keys);
+        assert!(probe.is_none());
+    }
+
+    #[test]
+    fn store_id_independent_of_order() {
+        let key_pool_ab =
+            KeyPool::new(&RiotApiConfig::with_key("RGAPI-aaaa").add_api_key("RGAPI-bbbb"));
+        let key_pool_ba =
+            KeyPool::new(&RiotApiConfig::with_key("RGAPI-bbbb").add_api_key("RGAPI-aaaa"));
+        assert_eq!(key_pool_ab.store_id(0), key_pool_ba.store_id(1));
+        assert_eq!(key_pool_ab.store_id(1), key_pool_ba.store_id(0));
+        assert_ne!(key_pool_ab.store_id(0), key_pool_ab.store_id(1));
+
+        // Kept when the key is replaced.
+        let store_id = key_pool_ab.store_id(0).map(str::to_owned);
+        key_pool_ab.set_value(0, HeaderValue::from_static("RGAPI-cccc"));
+        assert_eq!(store_id.as_deref(), key_pool_ab.store_id(0));
+
+        let key_pool = KeyPool::new(&RiotApiConfig::with_client_builder(Default::default()));
+        assert_eq!(None, key_pool.store_id(0));
+    }
+}
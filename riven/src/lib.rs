@@ -114,6 +114,21 @@
 //! Riven is additionally able to produce [tracing](https://docs.rs/tracing) spans for requests if the `tracing` feature is enabled.
 //! By default the `tracing` feature is disabled and Riven instead writes to [`log`](https://docs.rs/log).
 //!
+//! ### Blocking
+//!
+//! Enable the `blocking` feature for a synchronous `riven::blocking::RiotApi`, with the same endpoint handles
+//! returning values directly instead of futures. It is not available on wasm.
+//!
+//! ```toml
+//! riven = { version = "...", features = [ "blocking" ] }
+//! ```
+//!
+//! ### Other async runtimes
+//!
+//! By default Riven waits for rate limits using Tokio, through the default `tokio` feature. To use a different
+//! executor, such as async-std or smol, set a custom `Timer` and `HttpTransport` in the `RiotApiConfig`, and
+//! optionally disable the `tokio` feature. See the `riven::timer` module.
+//!
 //! ## Docs
 //!
 //! [On docs.rs](https://docs.rs/riven/).
@@ -182,31 +197,67 @@
 // Re-exported reqwest types.
 pub use reqwest;
 
+pub mod batch;
+
+#[cfg(all(feature = "blocking", not(target_family = "wasm")))]
+pub mod blocking;
+
+pub mod cache;
+
+pub mod cassette;
+
+pub mod circuit_breaker;
+
+pub mod coalesce;
+
 mod config;
-pub use config::RiotApiConfig;
+pub use config::{RateLimitTuning, RiotApiConfig};
 
 pub mod consts;
 
+mod deadline;
+pub use deadline::*;
+
 #[rustfmt::skip]
 pub mod endpoints;
 
 mod error;
 pub use error::*;
 
+pub mod key_pool;
+
 pub mod meta;
 
 #[rustfmt::skip]
 pub mod models;
 mod models_impls;
 
+pub mod metrics;
+
+pub mod middleware;
+
+mod priority;
+pub use priority::*;
+
+pub mod rate_limit_store;
+
 mod req;
 
+mod reservation;
+pub use reservation::*;
+
+pub mod retry;
+
 mod response_info;
 pub use response_info::*;
 
 mod riot_api;
 pub use riot_api::*;
 
+pub mod timer;
+
+pub mod transport;
+
 mod util;
 
 /// Wasm compatibility layer for [`std::time`] or [`web_time`].
@@ -217,7 +268,7 @@ pub mod time {
     #[cfg(target_family = "wasm")]
     pub use web_time::*;
 
-    #[cfg(not(target_family = "wasm"))]
+    #[cfg(all(feature = "tokio", not(target_family = "wasm")))]
     pub use tokio::time::sleep;
     #[cfg(target_family = "wasm")]
     pub use gloo_timers::future::sleep;
@@ -0,0 +1,124 @@
+//! Metrics hooks for monitoring how an API key is used.
+//!
+//! A [`Metrics`] implementation is registered with
+//! [`RiotApiConfig::set_metrics`](crate::RiotApiConfig::set_metrics) and receives events for
+//! each request attempt, labelled with the route and method ID ([`MetricLabels`]).
+//!
+//! [`PrometheusMetrics`] is a ready-made implementation which aggregates events and renders
+//! them in the Prometheus text exposition format.
+use std::fmt;
+
+use reqwest::StatusCode;
+
+use crate::rate_limit_store::BucketConfig;
+use crate::time::Duration;
+
+mod prometheus;
+pub use prometheus::PrometheusMetrics;
+
+/// Labels for a metric event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct MetricLabels {
+    /// The stringified route, e.g. `"KR"`.
+    pub route: &'static str,
+    /// The method ID, e.g. `"match-v5.getMatch"`.
+    pub method_id: &'static str,
+}
+
+/// Which rate limit a 429 response was caused by, from the `X-Rate-Limit-Type` header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RateLimitCause {
+    /// `"application"`, the app (API key) rate limit was exceeded.
+    Application,
+    /// `"method"`, the method rate limit was exceeded.
+    Method,
+    /// `"service"`, the underlying service is rate limiting, not caused by this app.
+    Service,
+    /// The header was missing or had an unknown value.
+    Unknown,
+}
+
+impl RateLimitCause {
+    /// Parses the value of the `X-Rate-Limit-Type` header.
+    pub(crate) fn from_header(header: Option<&str>) -> Self {
+        match header {
+            Some("application") => Self::Application,
+            Some("method") => Self::Method,
+            Some("service") => Self::Service,
+            _ => Self::Unknown,
+        }
+    }
+
+    /// Lowercase name of this cause, e.g. `"application"`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Application => "application",
+            Self::Method => "method",
+            Self::Service => "service",
+            Self::Unknown => "unknown",
+        }
+    }
+}
+
+/// Which kind of rate limit changed, see [`Metrics::record_rate_limit_update`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RateLimitKind {
+    /// The app (API key) rate limit for the route.
+    Application,
+    /// The rate limit for the method on the route.
+    Method,
+}
+
+impl RateLimitKind {
+    /// Lowercase name of this kind, e.g. `"application"`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Application => "application",
+            Self::Method => "method",
+        }
+    }
+}
+
+/// Receives metric events from Riven. All methods do nothing by default.
+///
+/// Methods are called inline while handling requests, so they should be fast and must not
+/// block.
+pub trait Metrics: fmt::Debug + Send + Sync {
+    /// Time a request attempt spent waiting to obtain rate limit tokens. Called once per
+    /// attempt, including attempts which did not need to wait.
+    fn record_rate_limit_wait(&self, _labels: MetricLabels, _wait: Duration) {}
+
+    /// A request attempt completed. `status` is `None` if the request failed without a
+    /// response, e.g. a connection error. `latency` is the HTTP latency, not including rate
+    /// limit waits.
+    fn record_response(
+        &self,
+        _labels: MetricLabels,
+        _status: Option<StatusCode>,
+        _latency: Duration,
+    ) {
+    }
+
+    /// A request is being retried. `retries` is the number of retries so far, including this
+    /// one.
+    fn record_retry(&self, _labels: MetricLabels, _retries: u8) {}
+
+    /// A request received a 429 response.
+    fn record_rate_limited(&self, _labels: MetricLabels, _cause: RateLimitCause) {}
+
+    /// A response had malformed rate limit headers. Depending on the problem, the headers were
+    /// ignored and the previous buckets kept, or buckets were updated without counts.
+    fn record_malformed_rate_limit_header(&self, _labels: MetricLabels, _kind: RateLimitKind) {}
+
+    /// A rate limit's buckets changed, according to the response headers. For
+    /// [`RateLimitKind::Application`] limits, `labels.method_id` is the method whose response
+    /// contained the new limits.
+    fn record_rate_limit_update(
+        &self,
+        _labels: MetricLabels,
+        _kind: RateLimitKind,
+        _buckets: &[BucketConfig],
+    ) {
+    }
+}
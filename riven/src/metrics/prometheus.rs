@@ -0,0 +1,243 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use parking_lot::Mutex;
+use reqwest::StatusCode;
+
+use super::{MetricLabels, Metrics, RateLimitCause, RateLimitKind};
+use crate::rate_limit_store::BucketConfig;
+use crate::time::Duration;
+
+/// Label names and values of a single sample.
+type Labels = Vec<(&'static str, String)>;
+
+/// Metric families: name, type, help, and sample name suffixes.
+const FAMILIES: [(&str, &str, &str, &[&str]); 7] = [
+    (
+        "riven_requests_total",
+        "counter",
+        "Request attempts by response status, or \"error\" if there was no response.",
+        &[""],
+    ),
+    (
+        "riven_request_duration_seconds",
+        "summary",
+        "HTTP latency of request attempts.",
+        &["_sum", "_count"],
+    ),
+    (
+        "riven_rate_limit_wait_seconds",
+        "summary",
+        "Time request attempts spent waiting for rate limit tokens.",
+        &["_sum", "_count"],
+    ),
+    (
+        "riven_retries_total",
+        "counter",
+        "Retried request attempts.",
+        &[""],
+    ),
+    (
+        "riven_rate_limited_total",
+        "counter",
+        "429 responses by X-Rate-Limit-Type.",
+        &[""],
+    ),
+    (
+        "riven_malformed_rate_limit_headers_total",
+        "counter",
+        "Responses with malformed rate limit headers.",
+        &[""],
+    ),
+    (
+        "riven_rate_limit",
+        "gauge",
+        "Current rate limit bucket limits, by bucket window.",
+        &[""],
+    ),
+];
+
+/// [`Metrics`] which aggregates events in memory and renders them in the
+/// [Prometheus text format](https://prometheus.io/docs/instrumenting/exposition_formats/),
+/// see [`Self::render`]. The rendered text can be served on a `/metrics` endpoint.
+///
+/// Metrics are labelled with `route` and `method_id`, except for application rate limits
+/// which are per route.
+#[derive(Debug, Default)]
+pub struct PrometheusMetrics {
+    /// Values of each sample, by sample name (e.g. `riven_request_duration_seconds_sum`).
+    samples: Mutex<BTreeMap<String, BTreeMap<Labels, f64>>>,
+}
+
+impl PrometheusMetrics {
+    /// Creates a new, empty `PrometheusMetrics`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn add(&self, name: &str, labels: Labels, value: f64) {
+        *self
+            .samples
+            .lock()
+            .entry(name.to_owned())
+            .or_default()
+            .entry(labels)
+            .or_default() += value;
+    }
+
+    fn set(&self, name: &str, labels: Labels, value: f64) {
+        self.samples
+            .lock()
+            .entry(name.to_owned())
+            .or_default()
+            .insert(labels, value);
+    }
+
+    fn observe(&self, name: &str, labels: Labels, value: Duration) {
+        self.add(
+            &format!("{}_sum", name),
+            labels.clone(),
+            value.as_secs_f64(),
+        );
+        self.add(&format!("{}_count", name), labels, 1.0);
+    }
+
+    /// Renders all metrics in the Prometheus text format.
+    pub fn render(&self) -> String {
+        let samples = self.samples.lock();
+        let mut out = String::new();
+        for (family, kind, help, suffixes) in FAMILIES {
+            let _ = writeln!(out, "# HELP {} {}", family, help);
+            let _ = writeln!(out, "# TYPE {} {}", family, kind);
+            for suffix in suffixes {
+                let name = format!("{}{}", family, suffix);
+                for (labels, value) in samples.get(&name).into_iter().flatten() {
+                    let labels: Vec<String> = labels
+                        .iter()
+                        .map(|(name, value)| format!("{}=\"{}\"", name, escape(value)))
+                        .collect();
+                    let _ = writeln!(out, "{}{{{}}} {}", name, labels.join(","), value);
+                }
+            }
+        }
+        out
+    }
+}
+
+fn labels(labels: MetricLabels) -> Labels {
+    vec![
+        ("route", labels.route.to_owned()),
+        ("method_id", labels.method_id.to_owned()),
+    ]
+}
+
+/// Escapes a label value.
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+impl Metrics for PrometheusMetrics {
+    fn record_rate_limit_wait(&self, metric_labels: MetricLabels, wait: Duration) {
+        self.observe("riven_rate_limit_wait_seconds", labels(metric_labels), wait);
+    }
+
+    fn record_response(
+        &self,
+        metric_labels: MetricLabels,
+        status: Option<StatusCode>,
+        latency: Duration,
+    ) {
+        let mut status_labels = labels(metric_labels);
+        let status = status.map_or_else(|| "error".to_owned(), |status| status.as_str().to_owned());
+        status_labels.push(("status", status));
+        self.add("riven_requests_total", status_labels, 1.0);
+        self.observe(
+            "riven_request_duration_seconds",
+            labels(metric_labels),
+            latency,
+        );
+    }
+
+    fn record_retry(&self, metric_labels: MetricLabels, _retries: u8) {
+        self.add("riven_retries_total", labels(metric_labels), 1.0);
+    }
+
+    fn record_rate_limited(&self, metric_labels: MetricLabels, cause: RateLimitCause) {
+        let mut labels = labels(metric_labels);
+        labels.push(("cause", cause.as_str().to_owned()));
+        self.add("riven_rate_limited_total", labels, 1.0);
+    }
+
+    fn record_malformed_rate_limit_header(&self, metric_labels: MetricLabels, kind: RateLimitKind) {
+        let mut labels = labels(metric_labels);
+        labels.push(("kind", kind.as_str().to_owned()));
+        self.add("riven_malformed_rate_limit_headers_total", labels, 1.0);
+    }
+
+    fn record_rate_limit_update(
+        &self,
+        metric_labels: MetricLabels,
+        kind: RateLimitKind,
+        buckets: &[BucketConfig],
+    ) {
+        let mut labels = labels(metric_labels);
+        if RateLimitKind::Application == kind {
+            labels.retain(|&(name, _)| "method_id" != name);
+        }
+        labels.push(("kind", kind.as_str().to_owned()));
+        for bucket in buckets {
+            let mut labels = labels.clone();
+            labels.push(("window_seconds", bucket.duration.as_secs().to_string()));
+            self.set("riven_rate_limit", labels, bucket.limit as f64);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn render() {
+        let metrics = PrometheusMetrics::new();
+        let labels = MetricLabels {
+            route: "KR",
+            method_id: "match-v5.getMatch",
+        };
+        metrics.record_response(labels, Some(StatusCode::OK), Duration::from_millis(250));
+        metrics.record_response(labels, Some(StatusCode::OK), Duration::from_millis(750));
+        metrics.record_response(labels, None, Duration::from_millis(100));
+        metrics.record_rate_limited(labels, RateLimitCause::Service);
+        metrics.record_rate_limit_update(
+            labels,
+            RateLimitKind::Application,
+            &[BucketConfig {
+                duration: Duration::from_secs(120),
+                limit: 100,
+                ..BucketConfig::INITIAL
+            }],
+        );
+
+        let text = metrics.render();
+        let lines: Vec<&str> = text.lines().collect();
+        for expected in [
+            "# TYPE riven_requests_total counter",
+            r#"riven_requests_total{route="KR",method_id="match-v5.getMatch",status="200"} 2"#,
+            r#"riven_requests_total{route="KR",method_id="match-v5.getMatch",status="error"} 1"#,
+            r#"riven_request_duration_seconds_sum{route="KR",method_id="match-v5.getMatch"} 1.1"#,
+            r#"riven_request_duration_seconds_count{route="KR",method_id="match-v5.getMatch"} 3"#,
+            r#"riven_rate_limited_total{route="KR",method_id="match-v5.getMatch",cause="service"} 1"#,
+            r#"riven_rate_limit{route="KR",kind="application",window_seconds="120"} 100"#,
+        ] {
+            assert!(
+                lines.contains(&expected),
+                "missing {:?} in:\n{}",
+                expected,
+                text
+            );
+        }
+    }
+}
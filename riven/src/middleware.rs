@@ -0,0 +1,150 @@
+//! Middleware which wraps every request sent through [`RiotApi::execute_raw`].
+//!
+//! Middleware is added with
+//! [`RiotApiConfig::add_middleware`](crate::RiotApiConfig::add_middleware). Similar to tower
+//! layers, each middleware wraps the rest of the stack: it receives the request, along with
+//! its method ID and route, and a [`Next`] handle for the inner layers. It may modify the
+//! request before passing it on, inspect or modify the [`ResponseInfo`] or error returned, or
+//! skip the inner layers entirely and return its own result. The innermost layer is Riven's
+//! own rate limiting and retry logic, so middleware sees each request once, not once per
+//! retry.
+//!
+//! Middleware added first is outermost.
+//!
+//! ```
+//! use riven::middleware::{Middleware, MiddlewareFuture, MiddlewareRequest, Next};
+//!
+//! /// Logs every failed request.
+//! #[derive(Debug)]
+//! struct Audit;
+//! impl Middleware for Audit {
+//!     fn handle<'a>(
+//!         &'a self,
+//!         request: MiddlewareRequest,
+//!         next: Next<'a>,
+//!     ) -> MiddlewareFuture<'a> {
+//!         Box::pin(async move {
+//!             let (method_id, route) = (request.method_id, request.route);
+//!             let result = next.run(request).await;
+//!             if let Err(e) = &result {
+//!                 eprintln!("{} {} failed: {}", route, method_id, e);
+//!             }
+//!             result
+//!         })
+//!     }
+//! }
+//! ```
+use std::fmt;
+use std::sync::Arc;
+
+use reqwest::RequestBuilder;
+
+use crate::{ResponseInfo, Result, RiotApi};
+
+/// Future returned by [`Middleware::handle`] and [`Next::run`].
+#[cfg(not(target_family = "wasm"))]
+pub type MiddlewareFuture<'a> = futures::future::BoxFuture<'a, Result<ResponseInfo>>;
+/// Future returned by [`Middleware::handle`] and [`Next::run`].
+#[cfg(target_family = "wasm")]
+pub type MiddlewareFuture<'a> = futures::future::LocalBoxFuture<'a, Result<ResponseInfo>>;
+
+/// A layer wrapping every request, see the [module docs](self).
+pub trait Middleware: fmt::Debug + Send + Sync {
+    /// Handles `request`, usually by passing it (possibly modified) to `next`.
+    fn handle<'a>(&'a self, request: MiddlewareRequest, next: Next<'a>) -> MiddlewareFuture<'a>;
+}
+
+/// A request passing through the [`Middleware`] stack.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct MiddlewareRequest {
+    /// The method ID, e.g. `"match-v5.getMatch"`.
+    pub method_id: &'static str,
+    /// The stringified route, e.g. `"AMERICAS"`.
+    pub route: &'static str,
+    /// The request to send.
+    pub request: RequestBuilder,
+}
+
+/// The remaining inner layers of the [`Middleware`] stack, passed to [`Middleware::handle`].
+pub struct Next<'a> {
+    riot_api: &'a RiotApi,
+    middlewares: &'a [Arc<dyn Middleware>],
+}
+
+impl<'a> Next<'a> {
+    pub(crate) fn new(riot_api: &'a RiotApi, middlewares: &'a [Arc<dyn Middleware>]) -> Self {
+        Self {
+            riot_api,
+            middlewares,
+        }
+    }
+
+    /// The `RiotApi` handling the request.
+    pub fn riot_api(&self) -> &'a RiotApi {
+        self.riot_api
+    }
+
+    /// Passes `request` to the inner layers.
+    pub fn run(self, request: MiddlewareRequest) -> MiddlewareFuture<'a> {
+        match self.middlewares.split_first() {
+            Some((middleware, middlewares)) => {
+                middleware.handle(request, Self::new(self.riot_api, middlewares))
+            }
+            None => Box::pin(self.riot_api.execute_inner(
+                request.method_id,
+                request.route,
+                request.request,
+            )),
+        }
+    }
+}
+
+impl fmt::Debug for Next<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Next")
+            .field("middlewares", &self.middlewares)
+            .finish()
+    }
+}
+
+#[cfg(all(test, feature = "tokio", not(target_family = "wasm")))]
+mod test {
+    use reqwest::{Method, StatusCode};
+
+    use super::*;
+    use crate::transport::{MockResponse, MockTransport};
+    use crate::RiotApiConfig;
+
+    /// Adds a header with the method ID and route.
+    #[derive(Debug)]
+    struct AddHeader;
+    impl Middleware for AddHeader {
+        fn handle<'a>(
+            &'a self,
+            mut request: MiddlewareRequest,
+            next: Next<'a>,
+        ) -> MiddlewareFuture<'a> {
+            let value = format!("{} {}", request.route, request.method_id);
+            request.request = request.request.header("X-Test", value);
+            next.run(request)
+        }
+    }
+
+    #[tokio::test]
+    async fn add_header() {
+        let transport = Arc::new(MockTransport::new());
+        transport.push_response(MockResponse::new(StatusCode::OK).set_body("1"));
+        let config = RiotApiConfig::with_key("RGAPI-test")
+            .set_transport(transport.clone())
+            .add_middleware(Arc::new(AddHeader));
+        let riot_api = RiotApi::new(config);
+        let request = riot_api.request(Method::GET, "NA1", "/");
+        let value: u32 = riot_api
+            .execute_val("test.method", "NA1", request)
+            .await
+            .unwrap();
+        assert_eq!(1, value);
+        assert_eq!("NA1 test.method", transport.requests()[0].headers["X-Test"]);
+    }
+}
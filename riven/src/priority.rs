@@ -0,0 +1,75 @@
+use std::cell::Cell;
+use std::future::Future;
+
+use crate::util::{get_scoped, Scoped};
+
+thread_local! {
+    static PRIORITY: Cell<Option<Priority>> = const { Cell::new(None) };
+}
+
+/// Priority class of a request, which determines the order in which waiting requests obtain
+/// rate limit tokens.
+///
+/// When requests are waiting on the same exhausted app or method rate limit, lower priority
+/// requests will wait until all higher priority requests have obtained their tokens. Requests
+/// which only share rate limits with capacity, for example the app rate limit while a higher
+/// priority request waits on a different method's rate limit, do not wait. Additionally,
+/// [`Priority::Bulk`] requests may be limited to a fraction of the app rate limit with
+/// [`RiotApiConfig::set_bulk_rate_usage_factor`](crate::RiotApiConfig::set_bulk_rate_usage_factor).
+///
+/// Priorities only apply within a single [`RiotApi`](crate::RiotApi) instance.
+///
+/// Requests use [`Priority::Normal`] by default. To set a different priority, wrap the request
+/// future using [`Priority::scope`]:
+/// ```ignore
+/// let summoner = Priority::Interactive
+///     .scope(riot_api.summoner_v4().get_by_puuid(PlatformRoute::NA1, puuid))
+///     .await?;
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum Priority {
+    /// Background work, such as crawling matches.
+    Bulk,
+    /// The default priority.
+    #[default]
+    Normal,
+    /// User-facing work, which should not wait behind other requests.
+    Interactive,
+}
+
+impl Priority {
+    /// All priorities, lowest first.
+    pub const ALL: [Self; 3] = [Self::Bulk, Self::Normal, Self::Interactive];
+
+    /// Runs the given future with this priority. All requests sent within `future` will use
+    /// this priority, unless overridden by a nested `scope`.
+    pub fn scope<F: Future>(self, future: F) -> impl Future<Output = F::Output> {
+        Scoped::new(&PRIORITY, self, future)
+    }
+
+    /// Gets the priority of the current request, set by [`Self::scope`].
+    pub(crate) fn current() -> Self {
+        get_scoped(&PRIORITY).unwrap_or_default()
+    }
+}
+
+#[cfg(all(test, not(target_family = "wasm")))]
+mod test {
+    use super::*;
+
+    #[tokio::test]
+    async fn nested_scope() {
+        assert_eq!(Priority::Normal, Priority::current());
+        Priority::Bulk
+            .scope(async {
+                assert_eq!(Priority::Bulk, Priority::current());
+                Priority::Interactive
+                    .scope(async { assert_eq!(Priority::Interactive, Priority::current()) })
+                    .await;
+                tokio::task::yield_now().await;
+                assert_eq!(Priority::Bulk, Priority::current());
+            })
+            .await;
+        assert_eq!(Priority::Normal, Priority::current());
+    }
+}
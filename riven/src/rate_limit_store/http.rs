@@ -0,0 +1,174 @@
+use futures::future::BoxFuture;
+use reqwest::Client;
+
+use super::{BucketConfig, BucketStatus, RateLimitStore, StoreRequest, StoreResponse, StoreResult};
+use crate::time::Duration;
+
+/// Reference networked [`RateLimitStore`], which sends each operation as a JSON
+/// [`StoreRequest`] `POST`ed to a coordinating server, which replies with a JSON
+/// [`StoreResponse`].
+///
+/// The server can be implemented by deserializing the body into a [`StoreRequest`], calling
+/// [`StoreRequest::apply`] on a shared store (such as a
+/// [`MemoryRateLimitStore`](super::MemoryRateLimitStore)), and serializing the result.
+///
+/// Does not support [snapshots](RateLimitStore::snapshot), as the buckets are kept by the
+/// server.
+#[derive(Debug, Clone)]
+pub struct HttpRateLimitStore {
+    client: Client,
+    url: String,
+}
+
+impl HttpRateLimitStore {
+    /// Creates a new `HttpRateLimitStore` which sends requests to the given server `url`.
+    pub fn new(url: impl Into<String>) -> Self {
+        Self::with_client(Client::new(), url)
+    }
+
+    /// Creates a new `HttpRateLimitStore` which uses the given `client` to send requests to
+    /// the server `url`.
+    ///
+    /// The client should _not_ include the Riot API key header.
+    pub fn with_client(client: Client, url: impl Into<String>) -> Self {
+        Self {
+            client,
+            url: url.into(),
+        }
+    }
+
+    async fn send(&self, request: &StoreRequest) -> StoreResult<StoreResponse> {
+        let response = self
+            .client
+            .post(&self.url)
+            .json(request)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        Ok(response)
+    }
+}
+
+impl RateLimitStore for HttpRateLimitStore {
+    fn try_acquire<'a>(
+        &'a self,
+        keys: &'a [&'a str],
+    ) -> BoxFuture<'a, StoreResult<Option<Duration>>> {
+        Box::pin(async move {
+            let request = StoreRequest::TryAcquire {
+                keys: keys.iter().map(|&key| key.to_owned()).collect(),
+            };
+            Ok(self.send(&request).await?.delay)
+        })
+    }
+
+    fn set_buckets<'a>(
+        &'a self,
+        key: &'a str,
+        buckets: &'a [BucketConfig],
+    ) -> BoxFuture<'a, StoreResult<()>> {
+        Box::pin(async move {
+            let request = StoreRequest::SetBuckets {
+                key: key.to_owned(),
+                buckets: buckets.to_vec(),
+            };
+            self.send(&request).await?;
+            Ok(())
+        })
+    }
+
+    fn retune_buckets<'a>(
+        &'a self,
+        key: &'a str,
+        buckets: &'a [BucketConfig],
+    ) -> BoxFuture<'a, StoreResult<()>> {
+        Box::pin(async move {
+            let request = StoreRequest::RetuneBuckets {
+                key: key.to_owned(),
+                buckets: buckets.to_vec(),
+            };
+            self.send(&request).await?;
+            Ok(())
+        })
+    }
+
+    fn status<'a>(&'a self, key: &'a str) -> BoxFuture<'a, StoreResult<Vec<BucketStatus>>> {
+        Box::pin(async move {
+            let request = StoreRequest::Status {
+                key: key.to_owned(),
+            };
+            Ok(self.send(&request).await?.buckets)
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::convert::Infallible;
+    use std::sync::Arc;
+
+    use hyper::service::{make_service_fn, service_fn};
+    use hyper::{Body, Request, Response, Server};
+
+    use super::*;
+    use crate::rate_limit_store::MemoryRateLimitStore;
+
+    /// Starts a stand-in store server backed by a [`MemoryRateLimitStore`], returning its URL.
+    fn start_server() -> String {
+        let store = Arc::new(MemoryRateLimitStore::new());
+        let make_svc = make_service_fn(move |_conn| {
+            let store = Arc::clone(&store);
+            async move {
+                Ok::<_, Infallible>(service_fn(move |req: Request<Body>| {
+                    let store = Arc::clone(&store);
+                    async move {
+                        let body = hyper::body::to_bytes(req.into_body()).await.unwrap();
+                        let request: StoreRequest = serde_json::from_slice(&body).unwrap();
+                        let response = request.apply(&*store).await.unwrap();
+                        let body = serde_json::to_vec(&response).unwrap();
+                        Ok::<_, Infallible>(Response::new(Body::from(body)))
+                    }
+                }))
+            }
+        });
+        let server = Server::bind(&([127, 0, 0, 1], 0).into()).serve(make_svc);
+        let url = format!("http://{}/", server.local_addr());
+        tokio::spawn(server);
+        url
+    }
+
+    #[tokio::test]
+    async fn shared_between_clients() {
+        let url = start_server();
+        let store_a = HttpRateLimitStore::new(&*url);
+        let store_b = HttpRateLimitStore::new(&*url);
+
+        let bucket = BucketConfig {
+            duration: Duration::from_secs(10),
+            limit: 3,
+            count: 1,
+            ..BucketConfig::INITIAL
+        };
+        store_a.set_buckets("na1", &[bucket]).await.unwrap();
+
+        assert_eq!(None, store_a.try_acquire(&["na1"]).await.unwrap());
+        assert_eq!(None, store_b.try_acquire(&["na1"]).await.unwrap());
+        // Limit of 3 used up by the initial count plus one token from each client.
+        assert!(store_a.try_acquire(&["na1"]).await.unwrap().is_some());
+        assert!(store_b.try_acquire(&["na1"]).await.unwrap().is_some());
+
+        let status = store_b.status("na1").await.unwrap();
+        assert_eq!(1, status.len());
+        assert_eq!(3, status[0].used);
+        assert!(status[0].delay.is_some());
+
+        // Retuning through one client keeps the tokens taken by both.
+        let retuned = BucketConfig { limit: 6, ..bucket };
+        store_a.retune_buckets("na1", &[retuned]).await.unwrap();
+        let status = store_b.status("na1").await.unwrap();
+        assert_eq!((6, 3), (status[0].limit, status[0].used));
+        assert_eq!(None, store_b.try_acquire(&["na1"]).await.unwrap());
+    }
+}
@@ -0,0 +1,318 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+use futures::future::{self, BoxFuture};
+use parking_lot::RwLock;
+#[cfg(feature = "tracing")]
+use tracing as log;
+
+use super::{
+    epoch_millis_to_instant, instant_to_epoch_millis, BucketConfig, BucketSnapshot, BucketStatus,
+    RateLimitStore, StoreResult,
+};
+use crate::req::{TokenBucket, VectorTokenBucket};
+use crate::time::Duration;
+
+/// Buckets for a single key, along with the config each bucket was created from.
+type Buckets = Arc<RwLock<Vec<(BucketConfig, VectorTokenBucket)>>>;
+
+/// [`RateLimitStore`] which keeps buckets in process memory. This is the default store.
+#[derive(Default)]
+pub struct MemoryRateLimitStore {
+    // Buckets for each rate limit key (synchronized).
+    // Almost always read, written only when rate limit rates are updated
+    // from API response.
+    buckets: RwLock<HashMap<String, Buckets>>,
+}
+
+impl MemoryRateLimitStore {
+    /// Creates a new, empty `MemoryRateLimitStore`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get or create the buckets for the given key.
+    fn get_buckets(&self, key: &str) -> Buckets {
+        if let Some(buckets) = self.buckets.read().get(key) {
+            return Arc::clone(buckets);
+        }
+        let mut map = self.buckets.write();
+        let buckets = map.entry(key.to_owned()).or_insert_with(|| {
+            Arc::new(RwLock::new(vec![bucket_from_config(BucketConfig::INITIAL)]))
+        });
+        Arc::clone(buckets)
+    }
+
+    /// Synchronous version of [`RateLimitStore::try_acquire`].
+    fn try_acquire_sync(&self, keys: &[&str]) -> Option<Duration> {
+        // Lock every key's buckets for both the check and the take, so no other acquire can
+        // take tokens in between. Locks are taken in sorted key order to avoid deadlocks.
+        let mut sorted_keys = keys.to_vec();
+        sorted_keys.sort_unstable();
+        sorted_keys.dedup();
+        let buckets: Vec<Buckets> = sorted_keys
+            .iter()
+            .map(|key| self.get_buckets(key))
+            .collect();
+        let buckets: Vec<_> = buckets.iter().map(|b| b.write()).collect();
+
+        // Check buckets.
+        for (_, bucket) in buckets.iter().flat_map(|b| b.iter()) {
+            let delay = bucket.get_delay();
+            if delay.is_some() {
+                return delay;
+            }
+        }
+        // Success.
+        for (_, bucket) in buckets.iter().flat_map(|b| b.iter()) {
+            bucket.get_tokens(1);
+        }
+
+        log::trace!(
+            "Tokens obtained, keys: {:?} buckets: {:?}",
+            keys,
+            buckets
+                .iter()
+                .map(|b| b.iter().map(|(_, bucket)| bucket).collect::<Vec<_>>())
+                .collect::<Vec<_>>()
+        );
+        None
+    }
+
+    /// Synchronous version of [`RateLimitStore::set_buckets`].
+    fn set_buckets_sync(&self, key: &str, buckets: &[BucketConfig]) {
+        let new_buckets = buckets.iter().copied().map(bucket_from_config).collect();
+        *self.get_buckets(key).write() = new_buckets;
+    }
+
+    /// Synchronous version of [`RateLimitStore::retune_buckets`].
+    fn retune_buckets_sync(&self, key: &str, buckets: &[BucketConfig]) {
+        let old_buckets = self.get_buckets(key);
+        // Hold the lock while rebuilding, so no tokens taken in the meantime are lost.
+        let mut old_buckets = old_buckets.write();
+        let new_buckets = buckets
+            .iter()
+            .map(|&config| {
+                let (config, bucket) = bucket_from_config(BucketConfig { count: 0, ..config });
+                if let Some((_, old_bucket)) = old_buckets
+                    .iter()
+                    .find(|(old_config, _)| old_config.duration == config.duration)
+                {
+                    bucket.put_timestamps(old_bucket.get_timestamps());
+                }
+                (config, bucket)
+            })
+            .collect();
+        *old_buckets = new_buckets;
+    }
+}
+
+impl RateLimitStore for MemoryRateLimitStore {
+    fn try_acquire<'a>(
+        &'a self,
+        keys: &'a [&'a str],
+    ) -> BoxFuture<'a, StoreResult<Option<Duration>>> {
+        Box::pin(future::ready(Ok(self.try_acquire_sync(keys))))
+    }
+
+    fn set_buckets<'a>(
+        &'a self,
+        key: &'a str,
+        buckets: &'a [BucketConfig],
+    ) -> BoxFuture<'a, StoreResult<()>> {
+        self.set_buckets_sync(key, buckets);
+        Box::pin(future::ready(Ok(())))
+    }
+
+    fn retune_buckets<'a>(
+        &'a self,
+        key: &'a str,
+        buckets: &'a [BucketConfig],
+    ) -> BoxFuture<'a, StoreResult<()>> {
+        self.retune_buckets_sync(key, buckets);
+        Box::pin(future::ready(Ok(())))
+    }
+
+    fn status<'a>(&'a self, key: &'a str) -> BoxFuture<'a, StoreResult<Vec<BucketStatus>>> {
+        let status = self
+            .get_buckets(key)
+            .read()
+            .iter()
+            .map(|(config, bucket)| BucketStatus {
+                duration: config.duration,
+                limit: config.limit,
+                effective_limit: bucket.get_total_limit(),
+                used: bucket.get_timestamps().len(),
+                delay: bucket.get_delay(),
+            })
+            .collect();
+        Box::pin(future::ready(Ok(status)))
+    }
+
+    fn snapshot(&self) -> BTreeMap<String, Vec<BucketSnapshot>> {
+        self.buckets
+            .read()
+            .iter()
+            .map(|(key, buckets)| {
+                let buckets = buckets
+                    .read()
+                    .iter()
+                    .map(|(config, bucket)| BucketSnapshot {
+                        config: BucketConfig {
+                            count: 0,
+                            ..*config
+                        },
+                        timestamps: bucket
+                            .get_timestamps()
+                            .into_iter()
+                            .map(instant_to_epoch_millis)
+                            .collect(),
+                    })
+                    .collect();
+                (key.clone(), buckets)
+            })
+            .collect()
+    }
+
+    fn restore(&self, key: &str, buckets: &[BucketSnapshot]) {
+        let new_buckets = buckets
+            .iter()
+            .map(|snapshot| {
+                let (config, bucket) = bucket_from_config(snapshot.config);
+                bucket.put_timestamps(
+                    snapshot
+                        .timestamps
+                        .iter()
+                        .filter_map(|&millis| epoch_millis_to_instant(millis)),
+                );
+                (config, bucket)
+            })
+            .collect();
+        *self.get_buckets(key).write() = new_buckets;
+    }
+}
+
+impl std::fmt::Debug for MemoryRateLimitStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map()
+            .entries(self.buckets.read().iter().map(|(key, buckets)| {
+                let buckets = buckets.read();
+                let buckets: Vec<_> = buckets.iter().map(|(_, bucket)| bucket).collect();
+                (key.clone(), format!("{:?}", buckets))
+            }))
+            .finish()
+    }
+}
+
+fn bucket_from_config(config: BucketConfig) -> (BucketConfig, VectorTokenBucket) {
+    let bucket = VectorTokenBucket::new(
+        config.duration,
+        config.limit,
+        config.duration_overhead,
+        config.burst_factor,
+        config.rate_usage_factor,
+    );
+    bucket.get_tokens(config.count);
+    (config, bucket)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn acquire_all_or_nothing() {
+        let store = MemoryRateLimitStore::new();
+        let bucket = BucketConfig {
+            duration: Duration::from_secs(10),
+            limit: 2,
+            ..BucketConfig::INITIAL
+        };
+        store.set_buckets_sync("na1", &[bucket]);
+        store.set_buckets_sync("na1/method", &[BucketConfig { limit: 3, ..bucket }]);
+
+        assert_eq!(None, store.try_acquire_sync(&["na1", "na1/method"]));
+        assert_eq!(None, store.try_acquire_sync(&["na1", "na1/method"]));
+        // App limit exhausted, method limit should not be used.
+        assert!(store.try_acquire_sync(&["na1", "na1/method"]).is_some());
+        assert_eq!(None, store.try_acquire_sync(&["na1/method"]));
+        assert!(store.try_acquire_sync(&["na1/method"]).is_some());
+    }
+
+    #[test]
+    fn acquire_concurrent() {
+        let store = MemoryRateLimitStore::new();
+        let bucket = BucketConfig {
+            duration: Duration::from_secs(10),
+            limit: 5,
+            ..BucketConfig::INITIAL
+        };
+        store.set_buckets_sync("na1", &[bucket]);
+        let method_bucket = BucketConfig {
+            limit: 100,
+            ..bucket
+        };
+        store.set_buckets_sync("na1/method0", &[method_bucket]);
+        store.set_buckets_sync("na1/method1", &[method_bucket]);
+
+        let barrier = std::sync::Barrier::new(8);
+        let acquired: usize = std::thread::scope(|scope| {
+            let threads: Vec<_> = (0..8)
+                .map(|i| {
+                    let (store, barrier) = (&store, &barrier);
+                    scope.spawn(move || {
+                        let method = format!("na1/method{}", i % 2);
+                        barrier.wait();
+                        (0..10)
+                            .filter(|_| store.try_acquire_sync(&[&method, "na1"]).is_none())
+                            .count()
+                    })
+                })
+                .collect();
+            threads.into_iter().map(|t| t.join().unwrap()).sum()
+        });
+        assert_eq!(5, acquired);
+    }
+
+    #[test]
+    fn retune_keeps_tokens() {
+        let store = MemoryRateLimitStore::new();
+        let bucket = BucketConfig {
+            duration: Duration::from_secs(10),
+            limit: 3,
+            ..BucketConfig::INITIAL
+        };
+        store.set_buckets_sync("na1", &[bucket]);
+        assert_eq!(None, store.try_acquire_sync(&["na1"]));
+        assert_eq!(None, store.try_acquire_sync(&["na1"]));
+
+        // Two of the four tokens remain used.
+        store.retune_buckets_sync("na1", &[BucketConfig { limit: 4, ..bucket }]);
+        assert_eq!(None, store.try_acquire_sync(&["na1"]));
+        assert_eq!(None, store.try_acquire_sync(&["na1"]));
+        assert!(store.try_acquire_sync(&["na1"]).is_some());
+    }
+
+    #[test]
+    fn snapshot_restore() {
+        let store = MemoryRateLimitStore::new();
+        let bucket = BucketConfig {
+            duration: Duration::from_secs(10),
+            limit: 3,
+            count: 1,
+            ..BucketConfig::INITIAL
+        };
+        store.set_buckets_sync("na1", &[bucket]);
+        assert_eq!(None, store.try_acquire_sync(&["na1"]));
+
+        let snapshot = store.snapshot();
+        assert_eq!(2, snapshot["na1"][0].timestamps.len());
+
+        let restored = MemoryRateLimitStore::new();
+        for (key, buckets) in snapshot.iter() {
+            restored.restore(key, buckets);
+        }
+        assert_eq!(None, restored.try_acquire_sync(&["na1"]));
+        assert!(restored.try_acquire_sync(&["na1"]).is_some());
+    }
+}
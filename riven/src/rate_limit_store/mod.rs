@@ -0,0 +1,320 @@
+//! Pluggable storage for rate limit token buckets.
+//!
+//! By default each [`RiotApi`](crate::RiotApi) keeps its rate limit buckets in process memory
+//! ([`MemoryRateLimitStore`]). If multiple `RiotApi` instances (possibly in different processes
+//! or on different machines) share a single API key, they can instead share a single
+//! [`RateLimitStore`] so that all instances see one accurate view of the key's rate limits,
+//! rather than statically splitting the limit via
+//! [`RiotApiConfig::set_rate_usage_factor`](crate::RiotApiConfig::set_rate_usage_factor).
+//!
+//! [`HttpRateLimitStore`] is a reference networked implementation which forwards each
+//! operation to a coordinating server as JSON. The server side may wrap any other store
+//! (e.g. a [`MemoryRateLimitStore`]) using [`StoreRequest::apply`].
+//!
+//! A store should only be shared between instances using the _same_ API keys.
+//!
+//! The state of a `RiotApi`'s rate limits can be saved with
+//! [`RiotApi::rate_limit_snapshot`](crate::RiotApi::rate_limit_snapshot) and restored on
+//! construction with
+//! [`RiotApiConfig::set_rate_limit_snapshot`](crate::RiotApiConfig::set_rate_limit_snapshot),
+//! for example across restarts. Snapshots only include buckets from local stores, such as
+//! [`MemoryRateLimitStore`], see [`RateLimitStore::snapshot`].
+//!
+//! The current state of a route's rate limits can be inspected with
+//! [`RiotApi::rate_limit_status`](crate::RiotApi::rate_limit_status).
+use std::collections::BTreeMap;
+use std::fmt;
+
+use futures::future::BoxFuture;
+use serde::{Deserialize, Serialize};
+
+use crate::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+mod memory;
+pub use memory::MemoryRateLimitStore;
+
+#[cfg(not(target_family = "wasm"))]
+mod http;
+#[cfg(not(target_family = "wasm"))]
+pub use http::HttpRateLimitStore;
+
+/// Error returned by a [`RateLimitStore`] operation.
+pub type StoreError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Result returned by a [`RateLimitStore`] operation.
+pub type StoreResult<T> = std::result::Result<T, StoreError>;
+
+/// Storage backend for rate limit token buckets.
+///
+/// Each rate limit is identified by a string key. The application rate limit for a route uses
+/// the route's name as its key (e.g. `"NA1"`), and method rate limits append the method ID
+/// (e.g. `"NA1/summoner-v4.getByPUUID"`). With multiple API keys (see
+/// [`key_pool`](crate::key_pool)), each key's rate limits have `#key-{id}` after the route,
+/// where `id` is a hash of the key as configured (e.g. `"NA1#key-5f0c2e9a1b3d4c78"` and
+/// `"NA1#key-5f0c2e9a1b3d4c78/summoner-v4.getByPUUID"`), so instances sharing a store agree
+/// regardless of the order their keys were added in. The key from a client builder's
+/// default headers keeps the plain route.
+/// Each key has a list of buckets, for example
+/// `"20:1,100:120"` (20 requests per second and 100 requests per two minutes) corresponds to
+/// two buckets. Keys which have not been set yet should be treated as a single bucket of one
+/// request per second, [`BucketConfig::INITIAL`].
+///
+/// Store implementations should be cheap to call; [`Self::try_acquire`] is called at least
+/// once for every request sent.
+pub trait RateLimitStore: fmt::Debug + Send + Sync {
+    /// Atomically takes one token from every bucket of every rate limit in `keys`, but only
+    /// if _all_ of the buckets have a token available.
+    ///
+    /// # Returns
+    /// `None` if the tokens were obtained, otherwise `Some(delay)` where `delay` is the
+    /// duration to wait before trying again.
+    fn try_acquire<'a>(
+        &'a self,
+        keys: &'a [&'a str],
+    ) -> BoxFuture<'a, StoreResult<Option<Duration>>>;
+
+    /// Replaces the buckets for the rate limit `key`, based on new rate limit headers.
+    fn set_buckets<'a>(
+        &'a self,
+        key: &'a str,
+        buckets: &'a [BucketConfig],
+    ) -> BoxFuture<'a, StoreResult<()>>;
+
+    /// Atomically replaces the buckets for the rate limit `key` with `buckets`, keeping the
+    /// tokens already taken from each existing bucket of the same duration. The `count` of
+    /// each new bucket is ignored.
+    ///
+    /// Used when the rate limit tuning changes, see
+    /// [`RiotApi::set_rate_limit_tuning`](crate::RiotApi::set_rate_limit_tuning). Unlike
+    /// reading [`Self::status`] then calling [`Self::set_buckets`], tokens taken in between,
+    /// by this or any other instance sharing the store, must not be lost.
+    fn retune_buckets<'a>(
+        &'a self,
+        key: &'a str,
+        buckets: &'a [BucketConfig],
+    ) -> BoxFuture<'a, StoreResult<()>>;
+
+    /// Returns the current status of each bucket of the rate limit `key`.
+    ///
+    /// Only used for [`RiotApi::rate_limit_status`](crate::RiotApi::rate_limit_status). By
+    /// default returns no buckets.
+    fn status<'a>(&'a self, _key: &'a str) -> BoxFuture<'a, StoreResult<Vec<BucketStatus>>> {
+        Box::pin(futures::future::ready(Ok(Vec::new())))
+    }
+
+    /// Returns the state of all buckets in this store, for [`RateLimitSnapshot`].
+    ///
+    /// Snapshots are local-only: unlike the other methods this is synchronous, and is meant
+    /// for stores which keep their state in process memory, such as
+    /// [`MemoryRateLimitStore`]. Stores which keep their state externally, such as
+    /// `HttpRateLimitStore`, do not lose it when the process restarts and should not
+    /// implement this; the external state should be persisted on its own side instead.
+    /// By default returns an empty map.
+    fn snapshot(&self) -> BTreeMap<String, Vec<BucketSnapshot>> {
+        BTreeMap::new()
+    }
+
+    /// Replaces the buckets for the rate limit `key` with the given saved state, from
+    /// [`Self::snapshot`].
+    ///
+    /// Local-only, and by default does nothing, see [`Self::snapshot`].
+    fn restore(&self, _key: &str, _buckets: &[BucketSnapshot]) {}
+}
+
+/// Configuration for a single token bucket, corresponding to one entry of a rate limit header
+/// such as `"100:120"` (100 requests per 120 seconds).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BucketConfig {
+    /// Duration of the bucket, `120` seconds in the example above.
+    pub duration: Duration,
+    /// Limit of the bucket, `100` in the example above, unadjusted by `rate_usage_factor`.
+    pub limit: usize,
+    /// Extra duration to be considered on top of `duration`. See
+    /// [`RiotApiConfig::set_duration_overhead`](crate::RiotApiConfig::set_duration_overhead).
+    pub duration_overhead: Duration,
+    /// Burst factor, in range (0, 1\]. See
+    /// [`RiotApiConfig::set_burst_factor`](crate::RiotApiConfig::set_burst_factor).
+    pub burst_factor: f32,
+    /// Rate usage factor, in range (0, 1\]. See
+    /// [`RiotApiConfig::set_rate_usage_factor`](crate::RiotApiConfig::set_rate_usage_factor).
+    pub rate_usage_factor: f32,
+    /// Number of tokens already used, from the rate limit count header.
+    pub count: usize,
+}
+
+impl BucketConfig {
+    /// Bucket used before any rate limit headers are received: one request per second.
+    pub const INITIAL: Self = Self {
+        duration: Duration::from_secs(1),
+        limit: 1,
+        duration_overhead: Duration::from_secs(0),
+        burst_factor: 1.0,
+        rate_usage_factor: 1.0,
+        count: 0,
+    };
+}
+
+/// Current status of a single token bucket, see [`RateLimitStore::status`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BucketStatus {
+    /// Duration of the bucket.
+    pub duration: Duration,
+    /// Limit of the bucket, as given by the rate limit header.
+    pub limit: usize,
+    /// Limit of the bucket actually used, after applying `rate_usage_factor`.
+    pub effective_limit: usize,
+    /// Number of tokens used within the bucket's duration.
+    pub used: usize,
+    /// Time until the next token is available, or `None` if a token is available now.
+    pub delay: Option<Duration>,
+}
+
+/// Current status of a single rate limit (application or method), see
+/// [`KeyRateLimitStatus`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RateLimitStatus {
+    /// Status of each bucket.
+    pub buckets: Vec<BucketStatus>,
+    /// Time remaining until the `retry-after` from a 429 response expires, if any.
+    pub retry_after: Option<Duration>,
+    /// Number of tasks currently waiting for this rate limit.
+    pub waiting: usize,
+}
+
+/// Current status of all rate limits for a route, from
+/// [`RiotApi::rate_limit_status`](crate::RiotApi::rate_limit_status).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RouteRateLimitStatus {
+    /// Status of each API key's rate limits, in the same order as
+    /// [`RiotApi::api_key_status`](crate::RiotApi::api_key_status).
+    pub keys: Vec<KeyRateLimitStatus>,
+}
+
+/// Current status of a single API key's rate limits for a route, see
+/// [`RouteRateLimitStatus`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct KeyRateLimitStatus {
+    /// The application rate limit.
+    pub app: RateLimitStatus,
+    /// Method rate limits, by method ID.
+    pub methods: BTreeMap<&'static str, RateLimitStatus>,
+}
+
+/// Saved state of a single token bucket, see [`RateLimitStore::snapshot`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BucketSnapshot {
+    /// Config the bucket was created with. `count` is unused, see `timestamps` instead.
+    pub config: BucketConfig,
+    /// Wall-clock times when tokens in the bucket were taken, newest first, in milliseconds
+    /// since the Unix epoch.
+    pub timestamps: Vec<u64>,
+}
+
+/// Saved state of all of a [`RiotApi`](crate::RiotApi)'s rate limits, from
+/// [`RiotApi::rate_limit_snapshot`](crate::RiotApi::rate_limit_snapshot).
+///
+/// All times are stored as wall-clock times, so time spent between saving and restoring the
+/// snapshot is accounted for.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RateLimitSnapshot {
+    /// Buckets for each rate limit key.
+    pub buckets: BTreeMap<String, Vec<BucketSnapshot>>,
+    /// Retry-after deadline for each rate limit key which received a 429 with a `Retry-After`
+    /// header, in milliseconds since the Unix epoch.
+    pub retry_after: BTreeMap<String, u64>,
+}
+
+/// A single [`RateLimitStore`] operation, as sent over the network by [`HttpRateLimitStore`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum StoreRequest {
+    /// See [`RateLimitStore::try_acquire`].
+    TryAcquire {
+        /// Rate limit keys.
+        keys: Vec<String>,
+    },
+    /// See [`RateLimitStore::set_buckets`].
+    SetBuckets {
+        /// Rate limit key.
+        key: String,
+        /// New bucket configs.
+        buckets: Vec<BucketConfig>,
+    },
+    /// See [`RateLimitStore::retune_buckets`].
+    RetuneBuckets {
+        /// Rate limit key.
+        key: String,
+        /// New bucket configs.
+        buckets: Vec<BucketConfig>,
+    },
+    /// See [`RateLimitStore::status`].
+    Status {
+        /// Rate limit key.
+        key: String,
+    },
+}
+
+/// Response to a [`StoreRequest`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StoreResponse {
+    /// For [`StoreRequest::TryAcquire`], the returned delay. Otherwise `None`.
+    pub delay: Option<Duration>,
+    /// For [`StoreRequest::Status`], the returned bucket statuses. Otherwise empty.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub buckets: Vec<BucketStatus>,
+}
+
+impl StoreRequest {
+    /// Applies this request to `store`. This is used to implement the server side of
+    /// [`HttpRateLimitStore`].
+    pub async fn apply(&self, store: &dyn RateLimitStore) -> StoreResult<StoreResponse> {
+        match self {
+            Self::TryAcquire { keys } => {
+                let keys: Vec<&str> = keys.iter().map(String::as_str).collect();
+                let delay = store.try_acquire(&keys).await?;
+                Ok(StoreResponse {
+                    delay,
+                    ..Default::default()
+                })
+            }
+            Self::SetBuckets { key, buckets } => {
+                store.set_buckets(key, buckets).await?;
+                Ok(StoreResponse::default())
+            }
+            Self::RetuneBuckets { key, buckets } => {
+                store.retune_buckets(key, buckets).await?;
+                Ok(StoreResponse::default())
+            }
+            Self::Status { key } => {
+                let buckets = store.status(key).await?;
+                Ok(StoreResponse {
+                    buckets,
+                    ..Default::default()
+                })
+            }
+        }
+    }
+}
+
+/// Converts an `Instant` to wall-clock milliseconds since the Unix epoch.
+pub(crate) fn instant_to_epoch_millis(instant: Instant) -> u64 {
+    let now = SystemTime::now();
+    let system_time = match Instant::now().checked_duration_since(instant) {
+        Some(age) => now - age,
+        None => now + instant.duration_since(Instant::now()),
+    };
+    system_time
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+/// Converts wall-clock milliseconds since the Unix epoch to an `Instant`. Times in the future
+/// are kept in the future. Returns `None` if the time is too far in the past to represent.
+pub(crate) fn epoch_millis_to_instant(millis: u64) -> Option<Instant> {
+    let system_time = UNIX_EPOCH + Duration::from_millis(millis);
+    match SystemTime::now().duration_since(system_time) {
+        Ok(age) => Instant::now().checked_sub(age),
+        Err(e) => Some(Instant::now() + e.duration()),
+    }
+}
@@ -1,27 +1,42 @@
-use std::cmp;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
 
+use futures::future::select_all;
 use futures::FutureExt;
-use parking_lot::{RwLock, RwLockUpgradableReadGuard};
+use parking_lot::RwLock;
 use reqwest::{Response, StatusCode};
-use scan_fmt::scan_fmt;
 #[cfg(feature = "tracing")]
 use tracing as log;
 
-use super::{RateLimitType, TokenBucket, VectorTokenBucket};
-use crate::time::{sleep, Duration, Instant};
+use super::RateLimitType;
+use crate::metrics::{MetricLabels, RateLimitKind};
+use crate::rate_limit_store::{
+    epoch_millis_to_instant, instant_to_epoch_millis, BucketConfig, RateLimitStatus,
+    RateLimitStore, StoreResult,
+};
+use crate::time::{Duration, Instant};
+use crate::timer::Timer;
 use crate::util::Notify;
-use crate::RiotApiConfig;
+use crate::{Deadline, DeadlineExceeded, Priority, RateLimitTuning, RiotApiConfig};
 
 pub struct RateLimit {
     rate_limit_type: RateLimitType,
-    // Buckets for this rate limit (synchronized).
+    // Key for this rate limit's buckets in `store`.
+    key: String,
+    // Store containing the buckets for this rate limit.
+    store: Arc<dyn RateLimitStore>,
+    // The limit header which the buckets were last set from (synchronized).
     // Almost always read, written only when rate limit rates are updated
     // from API response.
-    buckets: RwLock<Vec<VectorTokenBucket>>,
+    limit_header: RwLock<Option<String>>,
     // Set to when we can retry if a retry-after header is received.
     retry_after: RwLock<Option<Instant>>,
-    // Notifies waiters when rate limits are updated.
+    // Factor to scale the configured rate usage factor by, for the bulk rate limit.
+    rate_usage_factor_scale: f32,
+    // Notifies waiters when rate limits are updated, or when higher priority waiters finish.
     update_notify: Notify,
+    // Number of tasks currently waiting in `acquire`, for each priority.
+    waiting: [AtomicUsize; Priority::ALL.len()],
 }
 
 impl RateLimit {
@@ -29,7 +44,7 @@ impl RateLimit {
     /// This header specifies which rate limit is violated in a 429 (if any).
     /// There are three possible values, see [HEADER_XRATELIMITTYPE_APPLICATION],
     /// [HEADER_XRATELIMITTYPE_METHOD], and [HEADER_XRATELIMITTYPE_SERVICE].
-    const HEADER_XRATELIMITTYPE: &'static str = "X-Rate-Limit-Type";
+    pub const HEADER_XRATELIMITTYPE: &'static str = "X-Rate-Limit-Type";
 
     /// `"application"` - Entire app/key is rate limited due to violation.
     const HEADER_XRATELIMITTYPE_APPLICATION: &'static str = "application";
@@ -38,77 +53,235 @@ impl RateLimit {
     /// `"service"` - Service backend is rate-limiting (no violation).
     const HEADER_XRATELIMITTYPE_SERVICE: &'static str = "service";
 
-    pub fn new(rate_limit_type: RateLimitType) -> Self {
-        let initial_bucket =
-            VectorTokenBucket::new(Duration::from_secs(1), 1, Duration::new(0, 0), 1.0, 1.0);
+    /// How long to wait before trying again if the store fails.
+    const STORE_ERROR_DELAY: Duration = Duration::from_secs(1);
+
+    pub fn new(rate_limit_type: RateLimitType, key: String, config: &RiotApiConfig) -> Self {
+        Self::new_scaled(rate_limit_type, key, config, 1.0)
+    }
+
+    /// Creates a rate limit which only uses `rate_usage_factor_scale` of the usual rate limit.
+    /// Used to limit [`Priority::Bulk`] requests.
+    pub fn new_scaled(
+        rate_limit_type: RateLimitType,
+        key: String,
+        config: &RiotApiConfig,
+        rate_usage_factor_scale: f32,
+    ) -> Self {
+        // Restore retry-after from snapshot, if any.
+        let retry_after = config
+            .rate_limit_snapshot
+            .as_ref()
+            .and_then(|snapshot| snapshot.retry_after.get(&key))
+            .and_then(|&millis| epoch_millis_to_instant(millis));
         RateLimit {
             rate_limit_type,
-            // Rate limit before getting from response: 1/s.
-            buckets: RwLock::new(vec![initial_bucket]),
-            retry_after: RwLock::new(None),
+            key,
+            store: Arc::clone(&config.rate_limit_store),
+            // Rate limit before getting from response: 1/s, `BucketConfig::INITIAL`.
+            limit_header: RwLock::new(None),
+            retry_after: RwLock::new(retry_after),
+            rate_usage_factor_scale,
             update_notify: Notify::new(),
+            waiting: Default::default(),
+        }
+    }
+
+    /// The key for this rate limit's buckets in the store.
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// The retry-after deadline as wall-clock milliseconds since the Unix epoch, if set and
+    /// not yet passed.
+    pub fn get_retry_after_epoch_millis(&self) -> Option<u64> {
+        self.retry_after
+            .read()
+            .filter(|&i| Instant::now() < i)
+            .map(instant_to_epoch_millis)
+    }
+
+    /// Returns the current status of this rate limit.
+    pub async fn status(&self) -> StoreResult<RateLimitStatus> {
+        Ok(RateLimitStatus {
+            buckets: self.store.status(&self.key).await?,
+            retry_after: self.get_retry_after_delay(),
+            waiting: self
+                .waiting
+                .iter()
+                .map(|waiting| waiting.load(Ordering::Relaxed))
+                .sum(),
+        })
+    }
+
+    /// If any tasks with priority higher than `priority` are waiting on this rate limit.
+    fn has_higher_waiting(&self, priority: Priority) -> bool {
+        self.waiting[priority as usize + 1..]
+            .iter()
+            .any(|waiting| 0 < waiting.load(Ordering::Relaxed))
+    }
+
+    /// If this rate limit has no capacity right now, due to a retry-after or an empty bucket.
+    /// `None` if unknown, because the store does not report bucket status.
+    async fn is_exhausted(&self) -> Option<bool> {
+        if self.get_retry_after_delay().is_some() {
+            return Some(true);
+        }
+        match self.store.status(&self.key).await {
+            Ok(buckets) if !buckets.is_empty() => {
+                Some(buckets.iter().any(|bucket| bucket.delay.is_some()))
+            }
+            _ => None,
         }
     }
 
-    pub async fn acquire_both(app_rate_limit: &Self, method_rate_limit: &Self) {
-        while let Some(delay) = Self::acquire_both_or_duration(app_rate_limit, method_rate_limit) {
+    /// The rate limits among the `candidates` which a waiting task of `priority` competes
+    /// for: those which are exhausted (or may be), or which are contested by higher priority
+    /// waiting tasks. Lower priority tasks only defer to this task on these rate limits.
+    async fn contested<'a>(candidates: &[Vec<&'a Self>], priority: Priority) -> Vec<&'a Self> {
+        let mut contested = Vec::new();
+        for &rate_limit in candidates.iter().flatten() {
+            if rate_limit.has_higher_waiting(priority)
+                || Some(false) != rate_limit.is_exhausted().await
+            {
+                contested.push(rate_limit);
+            }
+        }
+        contested
+    }
+
+    /// Waits to acquire a token from all of the given `rate_limits` (app, method, and possibly
+    /// bulk) of one of the `candidates` (one per API key), after any waiting tasks with
+    /// higher `priority` competing for the same exhausted rate limits. Waits using `timer`.
+    ///
+    /// Fails if the wait would pass the `deadline`.
+    ///
+    /// # Returns
+    /// The index of the candidate which was acquired.
+    pub async fn acquire(
+        candidates: &[Vec<&Self>],
+        priority: Priority,
+        deadline: Option<Deadline>,
+        timer: &dyn Timer,
+    ) -> Result<usize, DeadlineExceeded> {
+        debug_assert!(candidates.iter().all(|rate_limits| !rate_limits.is_empty()));
+        let all_rate_limits = || candidates.iter().flatten();
+        // Counts this task as waiting on the rate limits it competes for, once it needs to wait.
+        let mut _waiting: Vec<WaitingGuard<'_>> = Vec::new();
+        loop {
+            let delay = match Self::try_acquire(candidates, priority).await {
+                Ok(index) => return Ok(index),
+                Err(delay) => delay,
+            };
+            if let Some(deadline) = deadline {
+                deadline.check(delay)?;
+            }
+            // Which rate limits are exhausted may change, so update each time. The new guards
+            // are created before the old ones are dropped.
+            _waiting = Self::contested(candidates, priority)
+                .await
+                .into_iter()
+                .map(|rate_limit| WaitingGuard::new(rate_limit, priority))
+                .collect();
+            // When deferring, wake at the deadline (if any) to fail.
+            let sleep = match delay.or_else(|| deadline.map(Deadline::remaining)) {
+                Some(delay) => timer.sleep(delay).left_future(),
+                None => futures::future::pending().right_future(),
+            };
             futures::select_biased! {
-                _ = sleep(delay).fuse() => continue,
-                _ = method_rate_limit.update_notify.notified() => {}
-                _ = app_rate_limit.update_notify.notified() => {}
+                _ = sleep.fuse() => continue,
+                _ = select_all(all_rate_limits().map(|rate_limit| rate_limit.update_notify.notified())).fuse() => {}
             };
             log::trace!("Task awoken due to rate limit update.");
         }
     }
 
-    fn acquire_both_or_duration(
-        app_rate_limit: &Self,
-        method_rate_limit: &Self,
-    ) -> Option<Duration> {
+    /// Tries to acquire a token from all of the given `rate_limits` of one of the
+    /// `candidates`, without waiting. Candidates with waiting tasks of higher `priority` are
+    /// skipped.
+    ///
+    /// # Returns
+    /// The index of the candidate which was acquired, otherwise the shortest delay until a
+    /// candidate may have capacity, or `None` if all candidates are deferring to higher
+    /// priority tasks.
+    pub async fn try_acquire(
+        candidates: &[Vec<&Self>],
+        priority: Priority,
+    ) -> Result<usize, Option<Duration>> {
+        let mut delay: Option<Duration> = None;
+        for (index, rate_limits) in candidates.iter().enumerate() {
+            // Defer to higher priority tasks, until they notify us.
+            if rate_limits
+                .iter()
+                .any(|rate_limit| rate_limit.has_higher_waiting(priority))
+            {
+                continue;
+            }
+            match Self::acquire_or_duration(rate_limits).await {
+                None => return Ok(index),
+                Some(candidate_delay) => {
+                    delay = Some(delay.map_or(candidate_delay, |d| d.min(candidate_delay)))
+                }
+            }
+        }
+        Err(delay)
+    }
+
+    async fn acquire_or_duration(rate_limits: &[&Self]) -> Option<Duration> {
         // Check retry after.
         {
-            let retry_after_delay = app_rate_limit.get_retry_after_delay().and_then(|a| {
-                method_rate_limit
-                    .get_retry_after_delay()
-                    .map(|m| cmp::max(a, m))
-            });
+            let retry_after_delay = rate_limits
+                .iter()
+                .map(|rate_limit| rate_limit.get_retry_after_delay())
+                .max()
+                .flatten();
             if retry_after_delay.is_some() {
                 return retry_after_delay;
             }
         }
         // Check buckets.
-        let app_buckets = app_rate_limit.buckets.read();
-        let method_buckets = method_rate_limit.buckets.read();
-        for bucket in app_buckets.iter().chain(method_buckets.iter()) {
-            let delay = bucket.get_delay();
-            if delay.is_some() {
-                return delay;
+        let store = &rate_limits[0].store;
+        debug_assert!(rate_limits
+            .iter()
+            .all(|rate_limit| Arc::ptr_eq(store, &rate_limit.store)));
+        let keys: Vec<&str> = rate_limits
+            .iter()
+            .map(|rate_limit| &*rate_limit.key)
+            .collect();
+        match store.try_acquire(&keys).await {
+            Ok(delay) => delay,
+            Err(e) => {
+                log::warn!(
+                    "Rate limit store failed to acquire {:?}, retrying after {:?}: {}",
+                    keys,
+                    Self::STORE_ERROR_DELAY,
+                    e
+                );
+                Some(Self::STORE_ERROR_DELAY)
             }
         }
-        // Success.
-        for bucket in app_buckets.iter().chain(method_buckets.iter()) {
-            bucket.get_tokens(1);
-        }
-
-        log::trace!(
-            "Tokens obtained, buckets: APP {:?} METHOD {:?}",
-            app_buckets,
-            method_buckets
-        );
-        None
     }
 
     pub fn get_retry_after_delay(&self) -> Option<Duration> {
         self.retry_after
             .read()
-            .and_then(|i| Instant::now().checked_duration_since(i))
+            .and_then(|i| i.checked_duration_since(Instant::now()))
     }
 
     /// Update retry-after and rate limits based on an API response.
     /// Returns the retry-after delay if set.
-    pub fn on_response(&self, config: &RiotApiConfig, response: &Response) -> Option<Duration> {
+    ///
+    /// Rate limit changes are recorded to the configured metrics with `metric_labels`, if
+    /// given.
+    pub async fn on_response(
+        &self,
+        config: &RiotApiConfig,
+        response: &Response,
+        metric_labels: Option<MetricLabels>,
+    ) -> Option<Duration> {
         let retry_after = self.on_response_retry_after(response);
-        self.on_response_rate_limits(config, response);
+        self.on_response_rate_limits(config, response, metric_labels)
+            .await;
         retry_after
     }
 
@@ -195,113 +368,477 @@ impl RateLimit {
         Some(delay)
     }
 
-    #[inline]
-    fn on_response_rate_limits(&self, config: &RiotApiConfig, response: &Response) {
+    async fn on_response_rate_limits(
+        &self,
+        config: &RiotApiConfig,
+        response: &Response,
+        metric_labels: Option<MetricLabels>,
+    ) {
         // Check if rate limits changed.
         let headers = response.headers();
-        let limit_header_opt = headers
-            .get(self.rate_limit_type.limit_header())
-            .and_then(|h| {
-                h.to_str()
-                    .map_err(|e| {
-                        log::error!(
-                            "Failed to read limit header as visible ASCII string: {:?}.",
-                            e
-                        )
-                    })
-                    .ok()
-            });
-        let count_header_opt = headers
-            .get(self.rate_limit_type.count_header())
-            .and_then(|h| {
-                h.to_str()
-                    .map_err(|e| {
-                        log::error!(
-                            "Failed to read count header as visible ASCII string: {:?}.",
-                            e
-                        )
-                    })
-                    .ok()
-            });
+        let header_str = |name: &str| {
+            let header = headers.get(name)?;
+            header
+                .to_str()
+                .map_err(|e| {
+                    log::error!(
+                        "Failed to read {} header as visible ASCII string: {:?}.",
+                        name,
+                        e
+                    );
+                    self.record_malformed_header(config, metric_labels);
+                })
+                .ok()
+        };
+        let limit_header_opt = header_str(self.rate_limit_type.limit_header());
+        let count_header_opt = header_str(self.rate_limit_type.count_header());
 
         if let (Some(limit_header), Some(count_header)) = (limit_header_opt, count_header_opt) {
-            {
-                let buckets = self.buckets.upgradable_read();
-                if !buckets_require_updating(limit_header, &buckets) {
+            if Some(limit_header) == self.limit_header.read().as_deref() {
+                return;
+            }
+
+            // Buckets require updating.
+            let parsed = match buckets_from_header(
+                config,
+                limit_header,
+                count_header,
+                self.rate_limit_type,
+                self.rate_usage_factor_scale,
+            ) {
+                Ok(parsed) => parsed,
+                Err(e) => {
+                    // Keep the previous buckets.
+                    log::warn!("Ignoring rate limit headers for {:?}, {}.", self.key, e);
+                    self.record_malformed_header(config, metric_labels);
                     return;
                 }
-
-                // Buckets require updating. Upgrade to write lock.
-                let mut buckets = RwLockUpgradableReadGuard::upgrade(buckets);
-                *buckets =
-                    buckets_from_header(config, limit_header, count_header, self.rate_limit_type);
+            };
+            if parsed.counts_mismatched {
+                self.record_malformed_header(config, metric_labels);
+            }
+            let buckets = parsed.buckets;
+            if let Err(e) = self.store.set_buckets(&self.key, &buckets).await {
+                log::warn!(
+                    "Rate limit store failed to set buckets for {:?}: {}",
+                    self.key,
+                    e
+                );
+                return;
             }
-            // Notify waiters that buckets have updated (after unlocking).
+            *self.limit_header.write() = Some(limit_header.to_owned());
+            if let (Some(metrics), Some(metric_labels)) = (&config.metrics, metric_labels) {
+                metrics.record_rate_limit_update(metric_labels, self.metric_kind(), &buckets);
+            }
+            // Notify waiters that buckets have updated.
             self.update_notify.notify_waiters();
         }
     }
+
+    /// Rebuilds the buckets with the config's current rate limit tuning, keeping the requests
+    /// used in each bucket. See [`RateLimitStore::retune_buckets`].
+    pub async fn retune(&self, config: &RiotApiConfig) -> StoreResult<()> {
+        let limit_header = self.limit_header.read().clone();
+        let Some(limit_header) = limit_header else {
+            // Still using the initial buckets, which are not tuned.
+            return Ok(());
+        };
+        let Ok(limits) = parse_header_entries(&limit_header) else {
+            return Ok(());
+        };
+        let tuning = *config.rate_limit_tuning.read();
+        let buckets: Vec<BucketConfig> = limits
+            .into_iter()
+            .map(|(limit, duration)| {
+                bucket_config(
+                    &tuning,
+                    self.rate_limit_type,
+                    self.rate_usage_factor_scale,
+                    limit,
+                    duration,
+                    0,
+                )
+            })
+            .collect();
+        self.store.retune_buckets(&self.key, &buckets).await?;
+        log::debug!("Retuned rate limit {:?} to {:?}.", self.key, tuning);
+        // Notify waiters that buckets have updated.
+        self.update_notify.notify_waiters();
+        Ok(())
+    }
+
+    fn metric_kind(&self) -> RateLimitKind {
+        match self.rate_limit_type {
+            RateLimitType::Application => RateLimitKind::Application,
+            RateLimitType::Method => RateLimitKind::Method,
+        }
+    }
+
+    /// Records malformed rate limit headers to the configured metrics, if any.
+    fn record_malformed_header(&self, config: &RiotApiConfig, metric_labels: Option<MetricLabels>) {
+        if let (Some(metrics), Some(metric_labels)) = (&config.metrics, metric_labels) {
+            metrics.record_malformed_rate_limit_header(metric_labels, self.metric_kind());
+        }
+    }
+}
+
+/// Increments a rate limit's waiting count for a priority, decrementing it again when dropped.
+struct WaitingGuard<'a> {
+    rate_limit: &'a RateLimit,
+    priority: Priority,
 }
 
-fn buckets_require_updating(limit_header: &str, buckets: &[VectorTokenBucket]) -> bool {
-    if buckets.len() != limit_header.split(',').count() {
-        return true;
+impl<'a> WaitingGuard<'a> {
+    fn new(rate_limit: &'a RateLimit, priority: Priority) -> Self {
+        rate_limit.waiting[priority as usize].fetch_add(1, Ordering::Relaxed);
+        Self {
+            rate_limit,
+            priority,
+        }
     }
-    for (limit_header_entry, bucket) in limit_header.split(',').zip(buckets) {
-        // limit_header_entry "100:60" means 100 req per 60 sec.
-        let bucket_entry = format!(
-            "{}:{}",
-            bucket.get_total_limit(),
-            bucket.get_bucket_duration().as_secs()
-        );
-        if limit_header_entry != bucket_entry {
-            return true;
+}
+
+impl Drop for WaitingGuard<'_> {
+    fn drop(&mut self) {
+        let waiting = &self.rate_limit.waiting[self.priority as usize];
+        // Wake lower priority tasks deferring to us, once no more tasks of this priority wait.
+        if 1 == waiting.fetch_sub(1, Ordering::Relaxed) && Priority::Bulk != self.priority {
+            self.rate_limit.update_notify.notify_waiters();
         }
     }
-    false
 }
 
+/// Buckets parsed from rate limit headers, see [`buckets_from_header`].
+struct ParsedBuckets {
+    buckets: Vec<BucketConfig>,
+    /// If the count header did not match the limit header. Buckets without a matching count
+    /// start at a count of zero.
+    counts_mismatched: bool,
+}
+
+/// Parses a rate limit or count header, e.g. `"20000:10,1200000:600"`, into `(value, window)`
+/// entries. Windows may be fractional seconds, but must be positive.
+fn parse_header_entries(header: &str) -> Result<Vec<(usize, Duration)>, String> {
+    header
+        .split(',')
+        .map(|entry| {
+            let (value, window) = entry
+                .split_once(':')
+                .ok_or_else(|| format!("entry {:?} is not `value:window`", entry))?;
+            let value = value
+                .trim()
+                .parse::<usize>()
+                .map_err(|e| format!("entry {:?} has invalid value: {}", entry, e))?;
+            let window = window
+                .trim()
+                .parse::<f64>()
+                .ok()
+                .filter(|window| 0.0 < *window)
+                // Also rejects non-finite windows, and windows too large for a `Duration`.
+                .and_then(|window| Duration::try_from_secs_f64(window).ok())
+                .ok_or_else(|| format!("entry {:?} has invalid window", entry))?;
+            Ok((value, window))
+        })
+        .collect()
+}
+
+/// Creates the config for a bucket allowing `limit` requests per `duration`, with `count`
+/// requests already used, adjusted by `tuning`.
+fn bucket_config(
+    tuning: &RateLimitTuning,
+    rate_limit_type: RateLimitType,
+    rate_usage_factor_scale: f32,
+    limit: usize,
+    duration: Duration,
+    count: usize,
+) -> BucketConfig {
+    let rate_usage_factor = if RateLimitType::Application == rate_limit_type {
+        tuning.app_rate_usage_factor()
+    } else {
+        tuning.method_rate_usage_factor()
+    } * rate_usage_factor_scale;
+
+    let limit_f32 = limit as f32;
+    let scaled_burst_factor = tuning.burst_factor() * limit_f32 / (limit_f32 + 1.0);
+
+    BucketConfig {
+        duration,
+        limit,
+        duration_overhead: tuning.duration_overhead(),
+        burst_factor: scaled_burst_factor,
+        rate_usage_factor,
+        count,
+    }
+}
+
+/// Parses the limit and count headers into buckets.
+///
+/// Returns an error if the limit header is malformed. A malformed count header, or counts
+/// which do not match the limits' windows, are tolerated (and reported in the result).
 fn buckets_from_header(
     config: &RiotApiConfig,
     limit_header: &str,
     count_header: &str,
     rate_limit_type: RateLimitType,
-) -> Vec<VectorTokenBucket> {
+    rate_usage_factor_scale: f32,
+) -> Result<ParsedBuckets, String> {
     // Limits: "20000:10,1200000:600"
     // Counts: "7:10,58:600"
-    let size = limit_header.split(',').count();
-    debug_assert!(size == count_header.split(',').count());
-    let mut out = Vec::with_capacity(size);
-
-    for (limit_entry, count_entry) in limit_header.split(',').zip(count_header.split(',')) {
-        let (limit, limit_secs) = scan_fmt!(limit_entry, "{d}:{d}", usize, u64)
-            .unwrap_or_else(|_| panic!("Failed to parse limit entry \"{}\".", limit_entry));
-        let (count, count_secs) = scan_fmt!(count_entry, "{d}:{d}", usize, u64)
-            .unwrap_or_else(|_| panic!("Failed to parse count entry \"{}\".", count_entry));
-        debug_assert!(limit_secs == count_secs);
-
-        let rate_usage_factor = if RateLimitType::Application == rate_limit_type {
-            config.app_rate_usage_factor
-        } else {
-            config.method_rate_usage_factor
-        };
+    let limits = parse_header_entries(limit_header)
+        .map_err(|e| format!("invalid limit header {:?}: {}", limit_header, e))?;
+    let counts = parse_header_entries(count_header).unwrap_or_else(|e| {
+        log::warn!("Invalid count header {:?}: {}", count_header, e);
+        Vec::new()
+    });
+    let mut counts_mismatched = limits.len() != counts.len();
+    let tuning = *config.rate_limit_tuning.read();
+    let mut out = Vec::with_capacity(limits.len());
 
-        let limit_f32 = limit as f32;
-        let scaled_burst_factor = config.burst_factor * limit_f32 / (limit_f32 + 1.0);
+    for (limit, duration) in limits {
+        // Match counts to limits by window, in case they are in different orders.
+        let count = counts
+            .iter()
+            .find(|&&(_, count_duration)| duration == count_duration)
+            .map(|&(count, _)| count)
+            .unwrap_or_else(|| {
+                counts_mismatched = true;
+                0
+            });
 
-        let bucket = VectorTokenBucket::new(
-            Duration::from_secs(limit_secs),
+        out.push(bucket_config(
+            &tuning,
+            rate_limit_type,
+            rate_usage_factor_scale,
             limit,
-            config.duration_overhead,
-            scaled_burst_factor,
-            rate_usage_factor,
+            duration,
+            count,
+        ));
+    }
+    if counts_mismatched {
+        log::warn!(
+            "Count header {:?} does not match limit header {:?}, assuming zero count for unmatched buckets.",
+            count_header,
+            limit_header
         );
-        bucket.get_tokens(count);
-        out.push(bucket);
     }
     log::debug!(
         "Set buckets to {} limit, {} count.",
         limit_header,
         count_header
     );
-    out
+    Ok(ParsedBuckets {
+        buckets: out,
+        counts_mismatched,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn parse(limit_header: &str, count_header: &str) -> Result<ParsedBuckets, String> {
+        let config = RiotApiConfig::with_key("RGAPI-test");
+        buckets_from_header(
+            &config,
+            limit_header,
+            count_header,
+            RateLimitType::Method,
+            1.0,
+        )
+    }
+
+    #[test]
+    fn malformed_headers() {
+        let parsed = parse("20:1,100:120", "1:1,5:120").unwrap();
+        assert!(!parsed.counts_mismatched);
+        let buckets: Vec<_> = parsed
+            .buckets
+            .iter()
+            .map(|b| (b.limit, b.duration, b.count))
+            .collect();
+        assert_eq!(
+            vec![
+                (20, Duration::from_secs(1), 1),
+                (100, Duration::from_secs(120), 5)
+            ],
+            buckets
+        );
+
+        // Counts in a different order, or missing.
+        let parsed = parse("20:1,100:120", "5:120").unwrap();
+        assert!(parsed.counts_mismatched);
+        assert_eq!(0, parsed.buckets[0].count);
+        assert_eq!(5, parsed.buckets[1].count);
+        assert!(parse("20:1", "garbage").unwrap().counts_mismatched);
+
+        // Fractional windows.
+        let parsed = parse("10:0.5", "1:0.5").unwrap();
+        assert_eq!(Duration::from_millis(500), parsed.buckets[0].duration);
+
+        for limit_header in [
+            "",
+            "20",
+            "20:1,x:120",
+            "20:0",
+            "20:-1",
+            "20:inf",
+            "-5:1",
+            "20:1e300",
+            "20:1e20",
+        ] {
+            assert!(parse(limit_header, "1:1").is_err(), "{:?}", limit_header);
+        }
+    }
+
+    /// Creates a rate limit with a single 200 ms bucket of `limit`, with `count` used.
+    #[cfg(all(feature = "tokio", not(target_family = "wasm")))]
+    async fn rate_limit(
+        config: &RiotApiConfig,
+        key: &str,
+        limit: usize,
+        count: usize,
+    ) -> RateLimit {
+        let bucket = BucketConfig {
+            duration: Duration::from_millis(200),
+            limit,
+            count,
+            ..BucketConfig::INITIAL
+        };
+        config
+            .rate_limit_store
+            .set_buckets(key, &[bucket])
+            .await
+            .unwrap();
+        RateLimit::new(RateLimitType::Method, key.to_owned(), config)
+    }
+
+    #[cfg(all(feature = "tokio", not(target_family = "wasm")))]
+    #[tokio::test]
+    async fn priority_defers_only_on_exhausted() {
+        let config = RiotApiConfig::with_key("RGAPI-test");
+        let app = rate_limit(&config, "NA1", 10, 0).await;
+        let method_a = rate_limit(&config, "NA1/a", 1, 1).await;
+        let method_b = rate_limit(&config, "NA1/b", 10, 0).await;
+        let timer = crate::timer::TokioTimer;
+
+        let candidates = [vec![&app, &method_a]];
+        let interactive = RateLimit::acquire(&candidates, Priority::Interactive, None, &timer);
+        futures::pin_mut!(interactive);
+        assert!(futures::poll!(&mut interactive).is_pending());
+        // Method B has capacity, so does not defer to the request waiting on method A.
+        assert_eq!(
+            Ok(0),
+            RateLimit::try_acquire(&[vec![&app, &method_b]], Priority::Normal).await
+        );
+        // Method A is exhausted, so defers.
+        assert_eq!(
+            Err(None),
+            RateLimit::try_acquire(&[vec![&app, &method_a]], Priority::Normal).await
+        );
+        assert_eq!(Ok(0), interactive.await);
+    }
+
+    #[cfg(all(feature = "tokio", not(target_family = "wasm")))]
+    #[tokio::test]
+    async fn priority_order() {
+        use futures::future::Either;
+
+        let config = RiotApiConfig::with_key("RGAPI-test");
+        let app = rate_limit(&config, "NA1", 1, 1).await;
+        let timer = crate::timer::TokioTimer;
+        let candidates = [vec![&app]];
+
+        let bulk = RateLimit::acquire(&candidates, Priority::Bulk, None, &timer);
+        futures::pin_mut!(bulk);
+        assert!(futures::poll!(&mut bulk).is_pending());
+        let interactive = RateLimit::acquire(&candidates, Priority::Interactive, None, &timer);
+        futures::pin_mut!(interactive);
+        assert!(futures::poll!(&mut interactive).is_pending());
+
+        // The bulk request waited first, but the interactive request gets the next token.
+        match futures::future::select(&mut bulk, &mut interactive).await {
+            Either::Right((result, _)) => assert_eq!(Ok(0), result),
+            Either::Left(_) => panic!("Bulk request acquired before interactive request."),
+        }
+        assert!(futures::poll!(&mut bulk).is_pending());
+        assert_eq!(Ok(0), bulk.await);
+    }
+
+    #[cfg(all(feature = "tokio", not(target_family = "wasm")))]
+    #[tokio::test]
+    async fn bulk_rate_share() {
+        use reqwest::Method;
+
+        use crate::transport::{MockResponse, MockTransport};
+        use crate::RiotApi;
+
+        let transport = Arc::new(
+            MockTransport::new().set_default_response(
+                MockResponse::new(StatusCode::OK)
+                    .set_app_rate_limit("10:10", "1:10")
+                    .set_method_rate_limit("100:10", "1:10"),
+            ),
+        );
+        let riot_api = RiotApi::new(
+            RiotApiConfig::with_key("RGAPI-test")
+                .set_transport(transport)
+                .set_bulk_rate_usage_factor(0.5),
+        );
+        let request = riot_api.request(Method::GET, "na1", "/a");
+        riot_api
+            .execute("test.method", "NA1", request)
+            .await
+            .unwrap();
+
+        // Bulk may use half of the app limit of 10, with a 0.99 burst factor: 4 tokens, one
+        // of which was used by the request above.
+        let reserve = || riot_api.try_reserve("NA1", "test.method");
+        let mut reservations = Vec::new();
+        for _ in 0..3 {
+            reservations.push(Priority::Bulk.scope(reserve()).await.unwrap());
+        }
+        assert!(matches!(
+            Priority::Bulk.scope(reserve()).await,
+            Err(Some(_))
+        ));
+        // Other priorities may still use the rest of the app limit.
+        reservations.push(reserve().await.unwrap());
+    }
+
+    #[cfg(all(feature = "tokio", not(target_family = "wasm")))]
+    #[tokio::test]
+    async fn retune() {
+        use reqwest::Method;
+
+        use crate::transport::{MockResponse, MockTransport};
+        use crate::RiotApi;
+
+        let transport = Arc::new(
+            MockTransport::new().set_default_response(
+                MockResponse::new(StatusCode::OK)
+                    .set_body("1")
+                    .set_app_rate_limit("100:10", "3:10")
+                    .set_method_rate_limit("50:10", "3:10"),
+            ),
+        );
+        let riot_api = RiotApi::new(RiotApiConfig::with_key("RGAPI-test").set_transport(transport));
+        let request = riot_api.request(Method::GET, "na1", "/a");
+        let _: u32 = riot_api
+            .execute_val("test.method", "NA1", request)
+            .await
+            .unwrap();
+
+        let status = riot_api.rate_limit_status("NA1").await.unwrap();
+        assert_eq!(100, status.keys[0].app.buckets[0].effective_limit);
+        assert_eq!(3, status.keys[0].app.buckets[0].used);
+
+        let tuning = riot_api.rate_limit_tuning().set_app_rate_usage_factor(0.5);
+        riot_api.set_rate_limit_tuning(tuning).await.unwrap();
+
+        let status = riot_api.rate_limit_status("NA1").await.unwrap();
+        assert_eq!(50, status.keys[0].app.buckets[0].effective_limit);
+        assert_eq!(3, status.keys[0].app.buckets[0].used);
+        let method = &status.keys[0].methods["test.method"].buckets[0];
+        assert_eq!((50, 3), (method.effective_limit, method.used));
+    }
 }
@@ -1,153 +1,661 @@
-use std::future::Future;
+use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
 
-use reqwest::{RequestBuilder, StatusCode};
+use parking_lot::Mutex;
+use reqwest::header::AUTHORIZATION;
+use reqwest::{Method, RequestBuilder, Response, StatusCode};
 #[cfg(feature = "tracing")]
 use tracing::{self as log, Instrument};
 
 use super::{RateLimit, RateLimitType};
-use crate::time::{sleep, Duration};
+use crate::cache::CachedResponse;
+use crate::circuit_breaker::{CircuitBreaker, CircuitBreakerStatus};
+use crate::key_pool::KeyPool;
+use crate::metrics::{MetricLabels, RateLimitCause};
+use crate::rate_limit_store::{KeyRateLimitStatus, RouteRateLimitStatus, StoreResult};
+use crate::retry::RetryContext;
+use crate::time::{Duration, Instant};
+use crate::timer::Timer;
+use crate::transport::HttpTransport;
 use crate::util::InsertOnlyCHashMap;
-use crate::{ResponseInfo, Result, RiotApiConfig, RiotApiError};
+use crate::{
+    Deadline, Priority, Reservation, ResponseInfo, Result, RiotApiConfig, RiotApiError,
+    RiotErrorStatus,
+};
 
 pub struct RegionalRequester {
+    /// The region platform route.
+    route: &'static str,
+    /// Rate limits for each API key in the key pool.
+    keys: Vec<KeyRateLimits>,
+    /// Method circuit breakers, if enabled.
+    circuit_breakers: InsertOnlyCHashMap<&'static str, CircuitBreaker>,
+    /// Unused reservations, by reservation ID, with their method ID and API key index.
+    reservations: Mutex<HashMap<u64, (&'static str, usize)>>,
+}
+
+/// The rate limits of one API key on a route.
+struct KeyRateLimits {
+    /// The app rate limit key: the route, followed by `#key-{id}` with multiple keys, see
+    /// [`KeyPool::store_id`].
+    app_key: String,
     /// The app rate limit.
     app_rate_limit: RateLimit,
+    /// Fraction of the app rate limit usable by bulk priority requests, if limited.
+    bulk_rate_limit: Option<RateLimit>,
     /// Method rate limits.
     method_rate_limits: InsertOnlyCHashMap<&'static str, RateLimit>,
 }
 
+impl KeyRateLimits {
+    fn new(app_key: String, config: &RiotApiConfig) -> Self {
+        Self {
+            app_rate_limit: RateLimit::new(RateLimitType::Application, app_key.clone(), config),
+            bulk_rate_limit: (config.bulk_rate_usage_factor < 1.0).then(|| {
+                RateLimit::new_scaled(
+                    RateLimitType::Application,
+                    format!("{}#bulk", app_key),
+                    config,
+                    config.bulk_rate_usage_factor,
+                )
+            }),
+            method_rate_limits: InsertOnlyCHashMap::new(),
+            app_key,
+        }
+    }
+
+    fn method_rate_limit(&self, method_id: &'static str, config: &RiotApiConfig) -> Arc<RateLimit> {
+        self.method_rate_limits.get_or_insert_with(method_id, || {
+            RateLimit::new(
+                RateLimitType::Method,
+                format!("{}/{}", self.app_key, method_id),
+                config,
+            )
+        })
+    }
+}
+
 impl RegionalRequester {
     /// HTTP status codes which are considered a success but will results in `None`.
-    const NONE_STATUS_CODES: [StatusCode; 2] = [
+    pub(crate) const NONE_STATUS_CODES: [StatusCode; 2] = [
         StatusCode::NO_CONTENT, // 204
         StatusCode::NOT_FOUND,  // 404
     ];
 
-    pub fn new() -> Self {
+    pub fn new(route: &'static str, config: &RiotApiConfig, key_pool: &KeyPool) -> Self {
         Self {
-            app_rate_limit: RateLimit::new(RateLimitType::Application),
-            method_rate_limits: InsertOnlyCHashMap::new(),
+            route,
+            keys: (0..key_pool.len())
+                .map(|index| {
+                    // With a single key, keep the rate limit keys the same as without a pool.
+                    let app_key = match key_pool.store_id(index) {
+                        Some(id) if 1 < key_pool.len() => format!("{}#key-{}", route, id),
+                        _ => route.to_owned(),
+                    };
+                    KeyRateLimits::new(app_key, config)
+                })
+                .collect(),
+            circuit_breakers: InsertOnlyCHashMap::new(),
+            reservations: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// The region platform route.
+    pub fn route(&self) -> &'static str {
+        self.route
+    }
+
+    /// Returns the current status of this requester's app and method rate limits, for each
+    /// API key.
+    pub async fn status(&self) -> StoreResult<RouteRateLimitStatus> {
+        let mut status = RouteRateLimitStatus::default();
+        for key in self.keys.iter() {
+            let mut key_status = KeyRateLimitStatus {
+                app: key.app_rate_limit.status().await?,
+                ..Default::default()
+            };
+            for (method_id, method_rate_limit) in key.method_rate_limits.entries() {
+                key_status
+                    .methods
+                    .insert(method_id, method_rate_limit.status().await?);
+            }
+            status.keys.push(key_status);
+        }
+        Ok(status)
+    }
+
+    /// Reads the body of a `401` or `403` `response` to check if Riot rejected the API key,
+    /// rather than something else. Responses without Riot's error body are assumed to have
+    /// rejected the key.
+    ///
+    /// # Returns
+    /// If the key was rejected, and the response rebuilt after reading its body.
+    async fn key_rejected(response: Response) -> reqwest::Result<(bool, Response)> {
+        let url = response.url().clone();
+        let response = CachedResponse::from_response(response).await?;
+        let key_rejected = match RiotErrorStatus::from_body(&response.body) {
+            Some(riot_status) => riot_status.is_key_rejection(),
+            None => true,
+        };
+        Ok((key_rejected, response.into_response_with_url(url)))
+    }
+
+    /// Rebuilds the buckets of all rate limits with the config's current rate limit tuning.
+    pub async fn retune(&self, config: &RiotApiConfig) -> StoreResult<()> {
+        for key in self.keys.iter() {
+            key.app_rate_limit.retune(config).await?;
+            if let Some(bulk_rate_limit) = &key.bulk_rate_limit {
+                bulk_rate_limit.retune(config).await?;
+            }
+            for method_rate_limit in key.method_rate_limits.values() {
+                method_rate_limit.retune(config).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the current status of this requester's circuit breakers, by method ID.
+    pub fn circuit_breaker_status(&self) -> BTreeMap<&'static str, CircuitBreakerStatus> {
+        self.circuit_breakers
+            .entries()
+            .into_iter()
+            .map(|(method_id, circuit_breaker)| (method_id, circuit_breaker.status()))
+            .collect()
+    }
+
+    /// Gets the retry-after deadlines of this requester's rate limits, as wall-clock
+    /// milliseconds since the Unix epoch, keyed by rate limit key.
+    pub fn retry_after_snapshot(&self) -> Vec<(String, u64)> {
+        let mut snapshot = Vec::new();
+        for key in self.keys.iter() {
+            let method_rate_limits = key.method_rate_limits.values();
+            let rate_limits = std::iter::once(&key.app_rate_limit)
+                .chain(key.bulk_rate_limit.as_ref())
+                .chain(method_rate_limits.iter().map(|rate_limit| &**rate_limit));
+            snapshot.extend(rate_limits.filter_map(|rate_limit| {
+                rate_limit
+                    .get_retry_after_epoch_millis()
+                    .map(|millis| (rate_limit.key().to_owned(), millis))
+            }));
+        }
+        snapshot
+    }
+
+    /// Tries to take a rate limit token for `method_id` without waiting, to be used later by a
+    /// request within [`Reservation::scope`].
+    ///
+    /// # Returns
+    /// The reservation, otherwise the shortest delay until a token may be available, or
+    /// `None` if unknown due to higher priority requests waiting.
+    pub async fn try_reserve(
+        self: Arc<Self>,
+        config: &RiotApiConfig,
+        key_pool: &KeyPool,
+        method_id: &'static str,
+    ) -> std::result::Result<Reservation, Option<Duration>> {
+        let priority = Priority::current();
+        let key_indices = key_pool.active_keys(method_id);
+        let method_rate_limits = self.method_rate_limits(config, &key_indices, method_id);
+        let candidates = self.candidates(&key_indices, &method_rate_limits, priority);
+        let candidate = RateLimit::try_acquire(&candidates, priority).await?;
+        let key_index = key_indices[candidate];
+        let reservation = Reservation::new(method_id, Arc::clone(&self));
+        self.reservations
+            .lock()
+            .insert(reservation.id(), (method_id, key_index));
+        Ok(reservation)
+    }
+
+    /// Removes a reservation, if it has not been used.
+    pub fn cancel_reservation(&self, id: u64) {
+        self.reservations.lock().remove(&id);
+    }
+
+    /// Takes the current scoped reservation if it is for `method_id`, see [`Self::try_reserve`].
+    ///
+    /// # Returns
+    /// The index into `key_indices` of the reserved API key, or `None` if there is no
+    /// reservation or if the reserved key is no longer in rotation.
+    fn take_reservation(&self, method_id: &'static str, key_indices: &[usize]) -> Option<usize> {
+        let id = Reservation::current()?;
+        let mut reservations = self.reservations.lock();
+        let &(reserved_method_id, key_index) = reservations.get(&id)?;
+        if reserved_method_id != method_id {
+            return None;
         }
+        reservations.remove(&id);
+        key_indices.iter().position(|&index| index == key_index)
+    }
+
+    /// Gets the method rate limits for `method_id` of each of the keys in `key_indices`.
+    fn method_rate_limits(
+        &self,
+        config: &RiotApiConfig,
+        key_indices: &[usize],
+        method_id: &'static str,
+    ) -> Vec<Arc<RateLimit>> {
+        key_indices
+            .iter()
+            .map(|&index| self.keys[index].method_rate_limit(method_id, config))
+            .collect()
+    }
+
+    /// Gets the rate limits to acquire for each of the keys in `key_indices`, given their
+    /// `method_rate_limits`.
+    fn candidates<'a>(
+        &'a self,
+        key_indices: &[usize],
+        method_rate_limits: &'a [Arc<RateLimit>],
+        priority: Priority,
+    ) -> Vec<Vec<&'a RateLimit>> {
+        key_indices
+            .iter()
+            .zip(method_rate_limits.iter())
+            .map(|(&index, method_rate_limit)| {
+                let key = &self.keys[index];
+                let mut rate_limits = vec![&key.app_rate_limit, &**method_rate_limit];
+                if Priority::Bulk == priority {
+                    rate_limits.extend(key.bulk_rate_limit.as_ref());
+                }
+                rate_limits
+            })
+            .collect()
     }
 
-    pub fn execute<'a>(
+    pub async fn execute(
         self: Arc<Self>,
-        config: &'a RiotApiConfig,
+        config: &RiotApiConfig,
+        transport: &dyn HttpTransport,
+        timer: &dyn Timer,
+        key_pool: &KeyPool,
         method_id: &'static str,
         request: RequestBuilder,
-    ) -> impl Future<Output = Result<ResponseInfo>> + 'a {
-        async move {
-            let mut retries: u8 = 0;
-            loop {
-                let method_rate_limit: Arc<RateLimit> = self
-                    .method_rate_limits
-                    .get_or_insert_with(method_id, || RateLimit::new(RateLimitType::Method));
-
-                // Rate limit.
-                let rate_limit = RateLimit::acquire_both(&self.app_rate_limit, &method_rate_limit);
-                #[cfg(feature = "tracing")]
-                let rate_limit = rate_limit.instrument(tracing::info_span!("rate_limit"));
-                rate_limit.await;
-
-                // Send request.
-                let request_clone = request
-                    .try_clone()
-                    .expect("Failed to clone request.")
-                    .send();
-                #[cfg(feature = "tracing")]
-                let request_clone = request_clone.instrument(tracing::info_span!("request"));
-                let response = request_clone.await;
-                let response = match response {
-                    Ok(response) => response,
-                    // Check for lower level errors, like connection errors.
-                    Err(e) => {
-                        if retries >= config.retries {
-                            log::debug!(
-                                "Request failed (retried {} times), failure, returning error.",
-                                retries
-                            );
-                            break Err(RiotApiError::new(e, retries, None, None));
-                        }
-                        let delay = Duration::from_secs(2_u64.pow(retries as u32));
-                        log::debug!("Request failed with cause \"{}\", (retried {} times), using exponential backoff, retrying after {:?}.", e.to_string(), retries, delay);
-                        let backoff = sleep(delay);
-                        #[cfg(feature = "tracing")]
-                        let backoff = backoff.instrument(tracing::info_span!("backoff"));
-                        backoff.await;
-                        retries += 1;
-                        continue;
+    ) -> Result<ResponseInfo> {
+        let priority = Priority::current();
+        let deadline = Deadline::current(config);
+        let built_request = request.try_clone().and_then(|request| request.build().ok());
+        let http_method = built_request
+            .as_ref()
+            .map_or(Method::GET, |request| request.method().clone());
+        // Requests on behalf of a user, e.g. RSO, may be rejected due to the user's token.
+        let user_authorized =
+            built_request.is_some_and(|request| request.headers().contains_key(AUTHORIZATION));
+        let circuit_breaker = config.circuit_breaker.as_ref().map(|circuit_breaker| {
+            self.circuit_breakers
+                .get_or_insert_with(method_id, || CircuitBreaker::new(circuit_breaker.clone()))
+        });
+        let metric_labels = MetricLabels {
+            route: self.route,
+            method_id,
+        };
+        let mut retries: u8 = 0;
+        loop {
+            if 0 < retries {
+                if let Some(metrics) = &config.metrics {
+                    metrics.record_retry(metric_labels, retries);
+                }
+            }
+
+            // Fail immediately if the API key is invalid.
+            if let Err(e) = key_pool.check_valid(method_id) {
+                log::debug!("API key invalid for {} {}.", self.route, method_id);
+                break Err(RiotApiError::key_invalid(e, retries));
+            }
+
+            // Fail immediately if the circuit is open, without waiting for a rate limit token.
+            if let Some(Err(e)) = circuit_breaker
+                .as_deref()
+                .map(CircuitBreaker::check_not_open)
+            {
+                log::debug!("Circuit breaker open for {} {}.", self.route, method_id);
+                break Err(RiotApiError::circuit_open(e, retries));
+            }
+
+            // Rate limit, using whichever API key in rotation has capacity soonest.
+            let (key_indices, probe) = key_pool.request_keys(method_id);
+            let method_rate_limits = self.method_rate_limits(config, &key_indices, method_id);
+            let candidates = self.candidates(&key_indices, &method_rate_limits, priority);
+            let rate_limit_start = Instant::now();
+            // Use the scoped reservation's token, if any.
+            let rate_limit = match self.take_reservation(method_id, &key_indices) {
+                Some(candidate) => Ok(candidate),
+                None => {
+                    let rate_limit = RateLimit::acquire(&candidates, priority, deadline, timer);
+                    #[cfg(feature = "tracing")]
+                    let rate_limit = rate_limit.instrument(tracing::info_span!("rate_limit"));
+                    rate_limit.await
+                }
+            };
+            if let Some(metrics) = &config.metrics {
+                metrics.record_rate_limit_wait(metric_labels, rate_limit_start.elapsed());
+            }
+            let candidate = match rate_limit {
+                Ok(candidate) => candidate,
+                Err(e) => break Err(RiotApiError::deadline_exceeded(e, retries, None)),
+            };
+            let key_index = key_indices[candidate];
+            let key = &self.keys[key_index];
+            let method_rate_limit = &method_rate_limits[candidate];
+
+            // Take a circuit permit, which may be a half-open probe slot, after the rate limit
+            // wait so the slot is only held while the request is sent.
+            let circuit_permit = match circuit_breaker
+                .as_deref()
+                .map(CircuitBreaker::try_acquire)
+                .transpose()
+            {
+                Ok(circuit_permit) => circuit_permit,
+                Err(e) => {
+                    log::debug!("Circuit breaker open for {} {}.", self.route, method_id);
+                    break Err(RiotApiError::circuit_open(e, retries));
+                }
+            };
+
+            // Send request.
+            let request_clone = match request
+                .try_clone()
+                .expect("Failed to clone request.")
+                .build()
+            {
+                Ok(mut request_clone) => {
+                    if let Some(api_key) = key_pool.value(key_index) {
+                        request_clone
+                            .headers_mut()
+                            .insert(RiotApiConfig::RIOT_KEY_HEADER, api_key);
                     }
-                };
-                // Maybe update rate limits (based on response headers).
-                // Use single bar for no short circuiting.
-                let retry_after_app = self.app_rate_limit.on_response(config, &response);
-                let retry_after_method = method_rate_limit.on_response(config, &response);
-                let retry_after = retry_after_app.or(retry_after_method); // Note: Edge case if both are Some(_) not handled.
-
-                let status = response.status();
-                // Handle normal success / failure cases.
-                let status_none = Self::NONE_STATUS_CODES.contains(&status);
-                // Success case.
-                if status.is_success() || status_none {
-                    log::trace!(
-                        "Response {} (retried {} times), success, returning result.",
-                        status,
-                        retries
-                    );
-                    break Ok(ResponseInfo {
-                        response,
-                        retries,
-                        status_none,
-                    });
+                    // Only count the key probe if its key is used, otherwise release it.
+                    if let Some(probe) = probe.filter(|probe| probe.index() == key_index) {
+                        probe.keep();
+                    }
+                    transport.send(request_clone)
                 }
-                let err = response.error_for_status_ref().err().unwrap_or_else(|| {
-                    panic!(
-                        "Unhandlable response status code, neither success nor failure: {}.",
-                        status
-                    )
-                });
-                // Failure, may or may not be retryable.
-                // Not-retryable: no more retries or 4xx or ? (3xx, redirects exceeded).
-                // Retryable: retries remaining, and 429 or 5xx.
-                if retries >= config.retries
-                    || (StatusCode::TOO_MANY_REQUESTS != status && !status.is_server_error())
-                {
-                    log::debug!(
-                        "Response {} (retried {} times), failure, returning error.",
-                        status,
-                        retries
-                    );
-                    break Err(RiotApiError::new(
-                        err,
+                Err(e) => break Err(RiotApiError::new(e, retries, None, None)),
+            };
+            #[cfg(feature = "tracing")]
+            let request_clone = request_clone.instrument(tracing::info_span!("request"));
+            let request_start = Instant::now();
+            let response = request_clone.await;
+            if let Some(metrics) = &config.metrics {
+                let status = response.as_ref().ok().map(Response::status);
+                metrics.record_response(metric_labels, status, request_start.elapsed());
+                if Some(StatusCode::TOO_MANY_REQUESTS) == status {
+                    let cause = response
+                        .as_ref()
+                        .ok()
+                        .and_then(|response| {
+                            response.headers().get(RateLimit::HEADER_XRATELIMITTYPE)
+                        })
+                        .and_then(|header| header.to_str().ok());
+                    metrics.record_rate_limited(metric_labels, RateLimitCause::from_header(cause));
+                }
+            }
+            if let Some(circuit_permit) = circuit_permit {
+                let failure = match &response {
+                    Ok(response) => response.status().is_server_error(),
+                    Err(_) => true,
+                };
+                circuit_permit.record(failure);
+            }
+            let response = match response {
+                Ok(response) => response,
+                // Check for lower level errors, like connection errors.
+                Err(e) => {
+                    let context = RetryContext {
+                        method_id,
+                        method: &http_method,
                         retries,
-                        Some(response),
-                        Some(status),
-                    ));
+                        status: None,
+                        headers: None,
+                        retry_after: None,
+                    };
+                    let Some(delay) = config.retry_policy.retry_delay(&context) else {
+                        log::debug!(
+                            "Request failed (retried {} times), failure, returning error.",
+                            retries
+                        );
+                        break Err(RiotApiError::from_transport(e, retries));
+                    };
+                    if let Some(Err(e)) = deadline.map(|deadline| deadline.check(Some(delay))) {
+                        break Err(RiotApiError::deadline_exceeded(e, retries, None));
+                    }
+                    log::debug!("Request failed with cause \"{}\", (retried {} times), retrying after {:?}.", e, retries, delay);
+                    let backoff = timer.sleep(delay);
+                    #[cfg(feature = "tracing")]
+                    let backoff = backoff.instrument(tracing::info_span!("backoff"));
+                    backoff.await;
+                    retries = retries.saturating_add(1);
+                    continue;
                 }
+            };
+            // Maybe update rate limits (based on response headers).
+            // Use single bar for no short circuiting.
+            let retry_after_app = key
+                .app_rate_limit
+                .on_response(config, &response, Some(metric_labels))
+                .await;
+            let retry_after_method = method_rate_limit
+                .on_response(config, &response, Some(metric_labels))
+                .await;
+            if let Some(bulk_rate_limit) = &key.bulk_rate_limit {
+                bulk_rate_limit.on_response(config, &response, None).await;
+            }
+            let retry_after = retry_after_app.or(retry_after_method); // Note: Edge case if both are Some(_) not handled.
 
-                // Is retryable, do exponential backoff if retry-after wasn't specified.
-                // 1 sec, 2 sec, 4 sec, 8 sec.
-                match retry_after {
-                    None => {
-                        let delay = Duration::from_secs(2_u64.pow(retries as u32));
-                        log::debug!("Response {} (retried {} times), NO `retry-after`, using exponential backoff, retrying after {:?}.", status, retries, delay);
-                        let backoff = sleep(delay);
-                        #[cfg(feature = "tracing")]
-                        let backoff = backoff.instrument(tracing::info_span!("backoff"));
-                        backoff.await;
-                    }
-                    Some(delay) => {
-                        log::debug!("Response {} (retried {} times), `retry-after` set, retrying after {:?}.", status, retries, delay);
+            let status = response.status();
+            // If the key was rejected, take it out of rotation and try another key, or make
+            // it invalid if it is the last key.
+            let mut response = response;
+            if matches!(status, StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN) {
+                if !user_authorized {
+                    let key_rejected;
+                    (key_rejected, response) = match Self::key_rejected(response).await {
+                        Ok(checked) => checked,
+                        Err(e) => break Err(RiotApiError::new(e, retries, None, Some(status))),
+                    };
+                    if key_rejected {
+                        if key_pool.disable(key_index, status) {
+                            continue;
+                        }
+                        key_pool.invalidate(key_index, status);
                     }
                 }
-                retries += 1;
+            } else {
+                key_pool.validate(key_index);
             }
+            // Handle normal success / failure cases.
+            let status_none = Self::NONE_STATUS_CODES.contains(&status);
+            // Success case.
+            if status.is_success() || status_none {
+                log::trace!(
+                    "Response {} (retried {} times), success, returning result.",
+                    status,
+                    retries
+                );
+                break Ok(ResponseInfo {
+                    response,
+                    retries,
+                    status_none,
+                });
+            }
+            let err = response.error_for_status_ref().err().unwrap_or_else(|| {
+                panic!(
+                    "Unhandlable response status code, neither success nor failure: {}.",
+                    status
+                )
+            });
+            // Failure, may or may not be retryable, as decided by the retry policy.
+            let context = RetryContext {
+                method_id,
+                method: &http_method,
+                retries,
+                status: Some(status),
+                headers: Some(response.headers()),
+                retry_after,
+            };
+            let Some(delay) = config.retry_policy.retry_delay(&context) else {
+                log::debug!(
+                    "Response {} (retried {} times), failure, returning error.",
+                    status,
+                    retries
+                );
+                break Err(RiotApiError::from_response(err, retries, response).await);
+            };
+            // Honor retry-after alone if given, only back off without it.
+            let delay = retry_after.unwrap_or(delay);
+            if let Some(Err(e)) = deadline.map(|deadline| deadline.check(Some(delay))) {
+                break Err(RiotApiError::deadline_exceeded(e, retries, Some(status)));
+            }
+            log::debug!(
+                "Response {} (retried {} times), `retry-after` {:?}, retrying after {:?}.",
+                status,
+                retries,
+                retry_after,
+                delay
+            );
+            // Retry-after is waited for by the rate limits.
+            if retry_after.is_none() {
+                let backoff = timer.sleep(delay);
+                #[cfg(feature = "tracing")]
+                let backoff = backoff.instrument(tracing::info_span!("backoff"));
+                backoff.await;
+            }
+            retries = retries.saturating_add(1);
         }
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "tokio", not(target_family = "wasm")))]
 mod tests {
-    // use super::*;
+    use reqwest::Method;
+
+    use super::*;
+    use crate::circuit_breaker::CircuitBreakerConfig;
+    use crate::retry::{ConstantBackoff, NoRetry};
+    use crate::transport::{MockResponse, MockTransport};
+    use crate::{Deadline, RiotApi};
+
+    #[tokio::test]
+    async fn rate_limit_status() {
+        let transport = Arc::new(
+            MockTransport::new().set_default_response(
+                MockResponse::new(StatusCode::OK)
+                    .set_app_rate_limit("20:1,100:120", "1:1,1:120")
+                    .set_method_rate_limit("50:10", "1:10"),
+            ),
+        );
+        let riot_api = RiotApi::new(
+            RiotApiConfig::with_key("RGAPI-aaaa")
+                .add_api_key("RGAPI-bbbb")
+                .set_transport(transport),
+        );
+        assert_eq!(
+            RouteRateLimitStatus::default(),
+            riot_api.rate_limit_status("NA1").await.unwrap()
+        );
+        let request = riot_api.request(Method::GET, "na1", "/a");
+        riot_api
+            .execute("test.method", "NA1", request)
+            .await
+            .unwrap();
+
+        let status = riot_api.rate_limit_status("NA1").await.unwrap();
+        assert_eq!(2, status.keys.len());
+        // Only the key which sent the request has used a token.
+        let mut used_keys = status
+            .keys
+            .iter()
+            .filter(|key| key.app.buckets.iter().any(|bucket| 0 < bucket.used));
+        let key = used_keys.next().unwrap();
+        assert!(used_keys.next().is_none());
+        let app_limits: Vec<usize> = key.app.buckets.iter().map(|b| b.limit).collect();
+        assert_eq!(vec![20, 100], app_limits);
+        assert!(key.app.buckets.iter().all(|bucket| 1 == bucket.used));
+        let method = &key.methods["test.method"];
+        assert_eq!(1, method.buckets.len());
+        assert_eq!(50, method.buckets[0].limit);
+        assert_eq!(None, method.retry_after);
+    }
+
+    #[tokio::test]
+    async fn snapshot_includes_bulk_retry_after() {
+        let transport = Arc::new(MockTransport::new());
+        transport.push_response(
+            MockResponse::new(StatusCode::TOO_MANY_REQUESTS).set_retry_after(10, "application"),
+        );
+        let riot_api = RiotApi::new(
+            RiotApiConfig::with_key("RGAPI-test")
+                .set_transport(transport)
+                .set_retries(0)
+                .set_bulk_rate_usage_factor(0.5),
+        );
+        let request = riot_api.request(Method::GET, "na1", "/a");
+        let Err(err) = riot_api.execute_raw("test.method", "NA1", request).await else {
+            panic!("429 should fail without retries.");
+        };
+        assert_eq!(Some(StatusCode::TOO_MANY_REQUESTS), err.status_code());
+
+        let snapshot = riot_api.rate_limit_snapshot();
+        assert!(snapshot.retry_after.contains_key("NA1"));
+        assert!(snapshot.retry_after.contains_key("NA1#bulk"));
+    }
+
+    #[tokio::test]
+    async fn retry_after_replaces_backoff() {
+        let transport = Arc::new(MockTransport::new().set_default_response(
+            MockResponse::new(StatusCode::TOO_MANY_REQUESTS).set_retry_after(10, "application"),
+        ));
+        let riot_api = RiotApi::new(
+            RiotApiConfig::with_key("RGAPI-test")
+                .set_transport(transport)
+                .set_retry_policy(Arc::new(ConstantBackoff::new(
+                    1,
+                    Duration::from_secs(60 * 60),
+                ))),
+        );
+        let request = riot_api.request(Method::GET, "na1", "/a");
+        let Err(err) = Deadline::after(Duration::from_secs(5))
+            .scope(riot_api.execute_raw("test.method", "NA1", request))
+            .await
+        else {
+            panic!("Retry should exceed the deadline.");
+        };
+        // Waits only the retry-after (plus lag buffer), not the hour of backoff.
+        let wait = err.source_deadline_exceeded().unwrap().wait().unwrap();
+        assert!(Duration::from_secs(10) <= wait && wait <= Duration::from_secs(11));
+    }
+
+    #[tokio::test]
+    async fn open_circuit_fails_immediately() {
+        let transport = Arc::new(
+            MockTransport::new().set_default_response(
+                MockResponse::new(StatusCode::INTERNAL_SERVER_ERROR)
+                    .set_app_rate_limit("1:10", "1:10")
+                    .set_method_rate_limit("1:10", "1:10"),
+            ),
+        );
+        let riot_api = RiotApi::new(
+            RiotApiConfig::with_key("RGAPI-test")
+                .set_transport(transport.clone())
+                .set_retry_policy(Arc::new(NoRetry))
+                .set_circuit_breaker(CircuitBreakerConfig::new().set_min_requests(1)),
+        );
+        let get = || {
+            let request = riot_api.request(Method::GET, "na1", "/a");
+            riot_api.execute_raw("test.method", "NA1", request)
+        };
+        let Err(err) = get().await else {
+            panic!("500 should fail without retries.");
+        };
+        assert!(!err.is_circuit_open());
+
+        // The rate limit is used up for 10 seconds, so any wait would exceed the deadline.
+        let Err(err) = Deadline::after(Duration::from_secs(5)).scope(get()).await else {
+            panic!("Open circuit should fail.");
+        };
+        assert!(err.is_circuit_open());
+        assert_eq!(1, transport.requests().len());
+        let status = riot_api.rate_limit_status("NA1").await.unwrap();
+        assert_eq!(1, status.keys[0].app.buckets[0].used);
+        assert_eq!(1, status.keys[0].methods["test.method"].buckets[0].used);
+    }
 }
@@ -27,11 +27,6 @@ pub trait TokenBucket {
     /// otherwise.
     fn get_tokens(&self, n: usize) -> bool;
 
-    /// Get the duration of this bucket.
-    /// # Returns
-    /// Duration of the bucket.
-    fn get_bucket_duration(&self) -> Duration;
-
     /// Get the total limit of this bucket per timespan.
     /// # Returns
     /// Total limit per timespan.
@@ -110,18 +105,29 @@ impl VectorTokenBucket {
         }
     }
 
-    fn update_get_timestamps(&self) -> MutexGuard<VecDeque<Instant>> {
+    fn update_get_timestamps(&self) -> MutexGuard<'_, VecDeque<Instant>> {
         let mut timestamps = self.timestamps.lock();
         // Only `None` in wasm, for some implementation reason. Probably sets time 0 at the first
         // `Instant::now()` call or something.
         if let Some(cutoff) = Instant::now().checked_sub(self.duration + self.duration_overhead) {
             // Pop off timestamps that are beyound the bucket duration.
-            while timestamps.back().map_or(false, |ts| *ts < cutoff) {
+            while timestamps.back().is_some_and(|ts| *ts < cutoff) {
                 timestamps.pop_back();
             }
         }
         timestamps
     }
+
+    /// Gets the timestamps of tokens currently taken from this bucket, newest first.
+    pub fn get_timestamps(&self) -> Vec<Instant> {
+        self.update_get_timestamps().iter().copied().collect()
+    }
+
+    /// Adds tokens taken at the given timestamps, which must be newest first and older than
+    /// any tokens already in this bucket. Used to restore a bucket from a snapshot.
+    pub fn put_timestamps(&self, timestamps: impl IntoIterator<Item = Instant>) {
+        self.timestamps.lock().extend(timestamps);
+    }
 }
 
 impl TokenBucket for VectorTokenBucket {
@@ -176,10 +182,6 @@ impl TokenBucket for VectorTokenBucket {
         true
     }
 
-    fn get_bucket_duration(&self) -> Duration {
-        self.duration
-    }
-
     fn get_total_limit(&self) -> usize {
         self.total_limit
     }
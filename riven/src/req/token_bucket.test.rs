@@ -30,6 +30,7 @@ mod token_bucket {
 
             let bucket = VectorTokenBucket::new(Duration::from_millis(1000), 100, ZERO, 1.0, 1e-6);
             assert_eq!(1, bucket.total_limit);
+            assert_eq!(1, bucket.get_total_limit());
             assert_eq!(1, bucket.burst_limit);
         }
 
@@ -192,5 +193,27 @@ mod token_bucket {
                 "Should not memory leak."
             );
         }
+
+        #[test]
+        fn test_put_timestamps() {
+            Instant::set_time(50_000);
+            let bucket = VectorTokenBucket::new(Duration::from_millis(1000), 10, ZERO, 1.0, 1.0);
+            assert!(bucket.get_tokens(5), "Should have not violated limit.");
+            Instant::advance_time(600);
+            assert!(bucket.get_tokens(5), "Should have not violated limit.");
+
+            let restored = VectorTokenBucket::new(Duration::from_millis(1000), 10, ZERO, 1.0, 1.0);
+            restored.put_timestamps(bucket.get_timestamps());
+            assert_eq!(10, restored.get_timestamps().len());
+            assert_ne!(None, restored.get_delay(), "Bucket should have delay.");
+
+            Instant::advance_time(401);
+            assert_eq!(
+                5,
+                restored.get_timestamps().len(),
+                "Oldest tokens should expire."
+            );
+            assert_eq!(None, restored.get_delay(), "Can get stuff.");
+        }
     }
 }
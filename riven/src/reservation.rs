@@ -0,0 +1,142 @@
+use std::cell::Cell;
+use std::fmt;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use crate::req::RegionalRequester;
+use crate::util::{get_scoped, Scoped};
+
+thread_local! {
+    static RESERVATION: Cell<Option<u64>> = const { Cell::new(None) };
+}
+
+/// Source of unique reservation IDs.
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+/// A rate limit token obtained ahead of time with
+/// [`RiotApi::try_reserve`](crate::RiotApi::try_reserve), for a single request to a specific
+/// route and method.
+///
+/// To use the reservation, wrap the request future using [`Reservation::scope`]. The first
+/// request within the scope to the reserved route and method uses the reserved token instead
+/// of waiting for the rate limits:
+/// ```ignore
+/// match riot_api.try_reserve(RegionalRoute::EUROPE.into(), "match-v5.getMatch").await {
+///     Ok(reservation) => {
+///         let match_ = reservation
+///             .scope(riot_api.match_v5().get_match(RegionalRoute::EUROPE, match_id))
+///             .await?;
+///     }
+///     Err(delay) => { /* Try another region, or come back after `delay`. */ }
+/// }
+/// ```
+/// If the reservation is dropped without being used, its token is still spent.
+pub struct Reservation {
+    /// Unique ID of this reservation.
+    id: u64,
+    /// The reserved method ID.
+    method_id: &'static str,
+    /// The requester for the reserved route, which holds the reserved token.
+    requester: Arc<RegionalRequester>,
+}
+
+impl Reservation {
+    /// Creates a reservation with a new unique ID. The caller must register the ID with
+    /// `requester`.
+    pub(crate) fn new(method_id: &'static str, requester: Arc<RegionalRequester>) -> Self {
+        Self {
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+            method_id,
+            requester,
+        }
+    }
+
+    /// The unique ID of this reservation.
+    pub(crate) fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// The reserved route.
+    pub fn route(&self) -> &'static str {
+        self.requester.route()
+    }
+
+    /// The reserved method ID.
+    pub fn method_id(&self) -> &'static str {
+        self.method_id
+    }
+
+    /// Runs the given future with this reservation. The first request sent within `future` to
+    /// the reserved route and method will use the reserved token.
+    pub fn scope<F: Future>(self, future: F) -> impl Future<Output = F::Output> {
+        let id = self.id;
+        async move {
+            // Keep the reservation registered until `future` is done.
+            let _reservation = self;
+            Scoped::new(&RESERVATION, id, future).await
+        }
+    }
+
+    /// Gets the ID of the reservation of the current request, set by [`Self::scope`].
+    pub(crate) fn current() -> Option<u64> {
+        get_scoped(&RESERVATION)
+    }
+}
+
+impl Drop for Reservation {
+    fn drop(&mut self) {
+        self.requester.cancel_reservation(self.id);
+    }
+}
+
+impl fmt::Debug for Reservation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Reservation")
+            .field("route", &self.route())
+            .field("method_id", &self.method_id)
+            .finish()
+    }
+}
+
+#[cfg(all(test, feature = "tokio", not(target_family = "wasm")))]
+mod test {
+    use reqwest::{Method, StatusCode};
+
+    use super::*;
+    use crate::time::{Duration, Instant};
+    use crate::transport::{MockResponse, MockTransport};
+    use crate::{RiotApi, RiotApiConfig};
+
+    #[tokio::test]
+    async fn try_reserve() {
+        let transport = Arc::new(
+            MockTransport::new()
+                .set_default_response(MockResponse::new(StatusCode::OK).set_body("1")),
+        );
+        let riot_api =
+            RiotApi::new(RiotApiConfig::with_key("RGAPI-test").set_transport(transport.clone()));
+
+        let reservation = riot_api.try_reserve("EUROPE", "test.method").await.unwrap();
+        assert_eq!("EUROPE", reservation.route());
+        assert_eq!("test.method", reservation.method_id());
+        // The initial rate limit of 1/s is used up by the reservation.
+        let delay = riot_api
+            .try_reserve("EUROPE", "test.method")
+            .await
+            .unwrap_err()
+            .unwrap();
+        assert!(Duration::ZERO < delay && delay <= Duration::from_secs(1));
+
+        // The reserved request does not wait.
+        let start = Instant::now();
+        let request = riot_api.request(Method::GET, "europe", "/a");
+        let value: u32 = reservation
+            .scope(riot_api.execute_val("test.method", "EUROPE", request))
+            .await
+            .unwrap();
+        assert_eq!(1, value);
+        assert!(start.elapsed() < Duration::from_millis(500));
+        assert_eq!(1, transport.requests().len());
+    }
+}
@@ -0,0 +1,279 @@
+//! Policies deciding whether and when to retry failed requests.
+//!
+//! A [`RetryPolicy`] is set with
+//! [`RiotApiConfig::set_retry_policy`](crate::RiotApiConfig::set_retry_policy). The default
+//! policy is an [`ExponentialBackoff`] with
+//! [`RiotApiConfig::DEFAULT_RETRIES`](crate::RiotApiConfig::DEFAULT_RETRIES) retries and no
+//! jitter.
+//!
+//! Policies can be combined in custom implementations, for example to retry `match-v5` reads
+//! more heavily than other methods:
+//! ```
+//! use riven::retry::{ExponentialBackoff, NeverRetryPost, RetryContext, RetryPolicy};
+//! use riven::time::Duration;
+//!
+//! #[derive(Debug)]
+//! struct MyPolicy {
+//!     matches: ExponentialBackoff,
+//!     default: NeverRetryPost<ExponentialBackoff>,
+//! }
+//! impl RetryPolicy for MyPolicy {
+//!     fn retry_delay(&self, context: &RetryContext<'_>) -> Option<Duration> {
+//!         if context.method_id.starts_with("match-v5.") {
+//!             self.matches.retry_delay(context)
+//!         } else {
+//!             self.default.retry_delay(context)
+//!         }
+//!     }
+//! }
+//!
+//! let policy = MyPolicy {
+//!     matches: ExponentialBackoff::new(10).set_jitter(0.5),
+//!     default: NeverRetryPost(ExponentialBackoff::new(3)),
+//! };
+//! ```
+use std::collections::hash_map::RandomState;
+use std::fmt;
+use std::hash::{BuildHasher, Hasher};
+
+use reqwest::header::HeaderMap;
+use reqwest::{Method, StatusCode};
+
+use crate::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Decides whether to retry a failed request, and how long to wait before doing so.
+pub trait RetryPolicy: fmt::Debug + Send + Sync {
+    /// Called after each failed attempt of a request.
+    ///
+    /// # Returns
+    /// `None` to not retry and return the error, otherwise `Some(delay)` to retry after
+    /// waiting `delay`. If the response had a `Retry-After` header
+    /// ([`RetryContext::retry_after`]), the retry waits for exactly that long instead.
+    fn retry_delay(&self, context: &RetryContext<'_>) -> Option<Duration>;
+}
+
+/// Information about a failed attempt, passed to [`RetryPolicy::retry_delay`].
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
+pub struct RetryContext<'a> {
+    /// The method ID of the request, e.g. `"match-v5.getMatch"`.
+    pub method_id: &'static str,
+    /// The HTTP method of the request.
+    pub method: &'a Method,
+    /// The number of retries done so far. Zero for the first attempt.
+    pub retries: u8,
+    /// The response status code, or `None` if the request failed without a response, e.g.
+    /// a connection error.
+    pub status: Option<StatusCode>,
+    /// The response headers, or `None` if there was no response.
+    pub headers: Option<&'a HeaderMap>,
+    /// The delay from the response's `Retry-After` header, if it was a 429 with one.
+    pub retry_after: Option<Duration>,
+}
+
+impl RetryContext<'_> {
+    /// If the failure may succeed when retried: connection errors, 429s, and 5xx server
+    /// errors. Other failures, such as 4xx client errors, are not retryable.
+    pub fn is_retryable(&self) -> bool {
+        match self.status {
+            Some(status) => StatusCode::TOO_MANY_REQUESTS == status || status.is_server_error(),
+            None => true,
+        }
+    }
+}
+
+/// Retries retryable failures ([`RetryContext::is_retryable`]) with exponential backoff:
+/// `1 sec, 2 sec, 4 sec, 8 sec, ...` by default, optionally with random jitter.
+#[derive(Debug, Clone)]
+pub struct ExponentialBackoff {
+    max_retries: u8,
+    initial_delay: Duration,
+    max_delay: Duration,
+    jitter: f32,
+}
+
+impl ExponentialBackoff {
+    /// Creates a new `ExponentialBackoff` which retries up to `max_retries` times, starting
+    /// with a one second delay and doubling each retry up to one hour, without jitter.
+    pub fn new(max_retries: u8) -> Self {
+        Self {
+            max_retries,
+            initial_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(60 * 60),
+            jitter: 0.0,
+        }
+    }
+
+    /// Sets the delay before the first retry.
+    ///
+    /// # Returns
+    /// `self`, for chaining.
+    pub fn set_initial_delay(mut self, initial_delay: Duration) -> Self {
+        self.initial_delay = initial_delay;
+        self
+    }
+
+    /// Sets the maximum delay between retries.
+    ///
+    /// # Returns
+    /// `self`, for chaining.
+    pub fn set_max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// Sets the jitter, the fraction of each delay which is randomized. For example, a jitter
+    /// of `0.5` with a delay of 4 seconds gives a random delay between 2 and 4 seconds. A
+    /// jitter of `1.0` is "full jitter". Jitter spreads out retries from many tasks which
+    /// failed at the same time.
+    ///
+    /// # Panics
+    /// If `jitter` is not in range [0, 1\].
+    ///
+    /// # Returns
+    /// `self`, for chaining.
+    pub fn set_jitter(mut self, jitter: f32) -> Self {
+        if (0.0..=1.0).contains(&jitter) {
+            self.jitter = jitter;
+            return self;
+        }
+        panic!("jitter \"{}\" not in range [0, 1].", jitter);
+    }
+}
+
+impl RetryPolicy for ExponentialBackoff {
+    fn retry_delay(&self, context: &RetryContext<'_>) -> Option<Duration> {
+        if self.max_retries <= context.retries || !context.is_retryable() {
+            return None;
+        }
+        let delay = self
+            .initial_delay
+            .saturating_mul(2_u32.saturating_pow(context.retries.into()))
+            .min(self.max_delay);
+        Some(delay.mul_f32(1.0 - self.jitter * random_fraction()))
+    }
+}
+
+/// Retries retryable failures ([`RetryContext::is_retryable`]) after a constant delay.
+#[derive(Debug, Clone)]
+pub struct ConstantBackoff {
+    max_retries: u8,
+    delay: Duration,
+}
+
+impl ConstantBackoff {
+    /// Creates a new `ConstantBackoff` which retries up to `max_retries` times, waiting
+    /// `delay` before each retry.
+    pub fn new(max_retries: u8, delay: Duration) -> Self {
+        Self { max_retries, delay }
+    }
+}
+
+impl RetryPolicy for ConstantBackoff {
+    fn retry_delay(&self, context: &RetryContext<'_>) -> Option<Duration> {
+        if self.max_retries <= context.retries || !context.is_retryable() {
+            return None;
+        }
+        Some(self.delay)
+    }
+}
+
+/// Wraps another policy to never retry `POST` requests, such as
+/// `tournament_v5().create_tournament_code(...)`, which may not be idempotent. The exception
+/// is 429 responses, as rate limited requests are not processed and are safe to retry.
+#[derive(Debug, Clone)]
+pub struct NeverRetryPost<P>(pub P);
+
+impl<P: RetryPolicy> RetryPolicy for NeverRetryPost<P> {
+    fn retry_delay(&self, context: &RetryContext<'_>) -> Option<Duration> {
+        if Method::POST == context.method && Some(StatusCode::TOO_MANY_REQUESTS) != context.status {
+            return None;
+        }
+        self.0.retry_delay(context)
+    }
+}
+
+/// Never retries.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoRetry;
+
+impl RetryPolicy for NoRetry {
+    fn retry_delay(&self, _context: &RetryContext<'_>) -> Option<Duration> {
+        None
+    }
+}
+
+/// Returns a pseudo-random number in [0, 1), for jitter.
+fn random_fraction() -> f32 {
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u128(
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos(),
+    );
+    // Top 24 bits, the precision of an `f32`.
+    (hasher.finish() >> 40) as f32 / (1 << 24) as f32
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn context(method: &Method, retries: u8, status: Option<StatusCode>) -> RetryContext<'_> {
+        RetryContext {
+            method_id: "tournament-v5.createTournamentCode",
+            method,
+            retries,
+            status,
+            headers: None,
+            retry_after: None,
+        }
+    }
+
+    #[test]
+    fn exponential_backoff() {
+        let policy = ExponentialBackoff::new(3);
+        let server_error = Some(StatusCode::INTERNAL_SERVER_ERROR);
+        let delays: Vec<_> = (0..4)
+            .map(|retries| policy.retry_delay(&context(&Method::GET, retries, server_error)))
+            .collect();
+        let expected = [1, 2, 4].map(|secs| Some(Duration::from_secs(secs)));
+        assert_eq!(&expected[..], &delays[..3]);
+        assert_eq!(None, delays[3]);
+        assert_eq!(
+            None,
+            policy.retry_delay(&context(&Method::GET, 0, Some(StatusCode::BAD_REQUEST)))
+        );
+
+        let jittered = ExponentialBackoff::new(3).set_jitter(0.5);
+        for _ in 0..10 {
+            let delay = jittered
+                .retry_delay(&context(&Method::GET, 2, server_error))
+                .unwrap();
+            assert!(Duration::from_secs(2) <= delay && delay <= Duration::from_secs(4));
+        }
+    }
+
+    #[test]
+    fn never_retry_post() {
+        let policy = NeverRetryPost(ConstantBackoff::new(3, Duration::from_secs(1)));
+        let server_error = Some(StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(
+            None,
+            policy.retry_delay(&context(&Method::POST, 0, server_error))
+        );
+        assert_eq!(
+            Some(Duration::from_secs(1)),
+            policy.retry_delay(&context(
+                &Method::POST,
+                0,
+                Some(StatusCode::TOO_MANY_REQUESTS)
+            ))
+        );
+        assert_eq!(
+            Some(Duration::from_secs(1)),
+            policy.retry_delay(&context(&Method::GET, 0, server_error))
+        );
+    }
+}
@@ -1,13 +1,26 @@
+use std::collections::BTreeMap;
 use std::future::Future;
 use std::sync::Arc;
 
+use reqwest::header::HeaderValue;
 use reqwest::{Client, Method, RequestBuilder};
 #[cfg(feature = "tracing")]
 use tracing as log;
 
+use crate::batch::{Batch, MaybeSend};
+use crate::circuit_breaker::CircuitBreakerStatus;
+use crate::key_pool::{ApiKeyStatus, KeyPool};
+use crate::middleware::{MiddlewareRequest, Next};
+use crate::rate_limit_store::{RateLimitSnapshot, RouteRateLimitStatus, StoreResult};
 use crate::req::RegionalRequester;
+use crate::time::Duration;
+use crate::timer::{self, Timer};
+use crate::transport::{HttpTransport, ReqwestTransport};
 use crate::util::InsertOnlyCHashMap;
-use crate::{ResponseInfo, Result, RiotApiConfig, RiotApiError};
+use crate::{
+    DeserializeError, RateLimitTuning, Reservation, ResponseInfo, Result, RiotApiConfig,
+    RiotApiError,
+};
 
 /// For retrieving data from the Riot Games API.
 ///
@@ -42,8 +55,14 @@ use crate::{ResponseInfo, Result, RiotApiConfig, RiotApiError};
 pub struct RiotApi {
     /// Configuration settings.
     config: RiotApiConfig,
-    /// Client for making requests.
+    /// Client for building requests.
     client: Client,
+    /// Transport for sending requests.
+    transport: Arc<dyn HttpTransport>,
+    /// Timer for rate limits and backoffs.
+    timer: Arc<dyn Timer>,
+    /// API keys to send requests with.
+    key_pool: KeyPool,
 
     /// Per-region requesters.
     regional_requesters: InsertOnlyCHashMap<&'static str, RegionalRequester>,
@@ -51,17 +70,39 @@ pub struct RiotApi {
 
 impl RiotApi {
     /// Constructs a new instance from an API key (e.g. `"RGAPI-01234567-89ab-cdef-0123-456789abcdef"`) or a [RiotApiConfig].
+    ///
+    /// # Panics
+    /// If no timer is set with [`RiotApiConfig::set_timer`] and the `tokio` feature is
+    /// disabled, see the [`timer`](crate::timer) module.
     pub fn new(config: impl Into<RiotApiConfig>) -> Self {
         let mut config = config.into();
         let client_builder = config
             .client_builder
             .take()
             .expect("CLIENT_BUILDER IN CONFIG SHOULD NOT BE NONE.");
+        if let Some(snapshot) = &config.rate_limit_snapshot {
+            for (key, buckets) in snapshot.buckets.iter() {
+                config.rate_limit_store.restore(key, buckets);
+            }
+        }
+        let client = client_builder
+            .build()
+            .expect("Failed to create client from builder.");
+        let transport = config
+            .transport
+            .clone()
+            .unwrap_or_else(|| Arc::new(ReqwestTransport::new(client.clone())));
+        let timer = config
+            .timer
+            .clone()
+            .or_else(timer::default_timer)
+            .expect("No timer, enable the `tokio` feature or use `RiotApiConfig::set_timer`.");
         Self {
+            key_pool: KeyPool::new(&config),
             config,
-            client: client_builder
-                .build()
-                .expect("Failed to create client from builder."),
+            client,
+            transport,
+            timer,
             regional_requesters: InsertOnlyCHashMap::new(),
         }
     }
@@ -100,10 +141,7 @@ impl RiotApi {
         let rinfo = self
             .execute_raw(method_id, region_platform, request)
             .await?;
-        let retries = rinfo.retries;
-        let status = rinfo.response.status();
-        let value = rinfo.response.json::<T>().await;
-        value.map_err(|e| RiotApiError::new(e, retries, None, Some(status)))
+        Self::deserialize(rinfo).await
     }
 
     /// This method should generally not be used directly. Consider using endpoint wrappers instead.
@@ -129,10 +167,7 @@ impl RiotApi {
         if rinfo.status_none {
             return Ok(None);
         }
-        let retries = rinfo.retries;
-        let status = rinfo.response.status();
-        let value = rinfo.response.json::<Option<T>>().await;
-        value.map_err(|e| RiotApiError::new(e, retries, None, Some(status)))
+        Self::deserialize(rinfo).await
     }
 
     /// This method should generally not be used directly. Consider using endpoint wrappers instead.
@@ -175,6 +210,9 @@ impl RiotApi {
     /// * `region_platform` - The stringified platform, used in rate limiting.
     /// * `request` - The request information. Use `request()` to obtain a `RequestBuilder` instance.
     ///
+    /// The request passes through any [`Middleware`](crate::middleware::Middleware) added via
+    /// [`RiotApiConfig::add_middleware`].
+    ///
     /// # Returns
     /// A future resolving to a `Result` containg either a `ResponseInfo` (success) or a `RiotApiError` (failure).
     pub fn execute_raw(
@@ -183,8 +221,228 @@ impl RiotApi {
         region_platform: &'static str,
         request: RequestBuilder,
     ) -> impl Future<Output = Result<ResponseInfo>> + '_ {
+        Next::new(self, &self.config.middlewares).run(MiddlewareRequest {
+            method_id,
+            route: region_platform,
+            request,
+        })
+    }
+
+    /// Sends the request, with rate limiting and retries, after all middleware.
+    pub(crate) fn execute_inner(
+        &self,
+        method_id: &'static str,
+        region_platform: &'static str,
+        request: RequestBuilder,
+    ) -> impl Future<Output = Result<ResponseInfo>> + '_ {
+        self.regional_requester(region_platform).execute(
+            &self.config,
+            &*self.transport,
+            &*self.timer,
+            &self.key_pool,
+            method_id,
+            request,
+        )
+    }
+
+    /// Reads and deserializes the JSON response body.
+    async fn deserialize<T: serde::de::DeserializeOwned>(rinfo: ResponseInfo) -> Result<T> {
+        let retries = rinfo.retries;
+        let status = rinfo.response.status();
+        let body = rinfo
+            .response
+            .bytes()
+            .await
+            .map_err(|e| RiotApiError::new(e, retries, None, Some(status)))?;
+        serde_json::from_slice(&body).map_err(|e| {
+            RiotApiError::deserialize(
+                DeserializeError::new(e, body.to_vec()),
+                retries,
+                Some(status),
+            )
+        })
+    }
+
+    /// Returns the status of each API key in the key pool, including whether it has been taken
+    /// out of rotation. See the [`key_pool`](crate::key_pool) module.
+    pub fn api_key_status(&self) -> Vec<ApiKeyStatus> {
+        self.key_pool.status()
+    }
+
+    /// Replaces the first API key for new requests, e.g. when a development key expires. The
+    /// learned rate limits are kept, and requests already in flight are not affected. See the
+    /// [`key_pool`](crate::key_pool) module.
+    ///
+    /// # Panics
+    /// If `api_key` is not a valid header value.
+    pub fn set_api_key(&self, api_key: impl AsRef<[u8]>) {
+        self.set_pool_api_key(0, api_key)
+    }
+
+    /// Replaces the API key at `index` in the key pool (see [`Self::api_key_status`]) for new
+    /// requests, and puts it back into rotation if it was taken out. The learned rate limits
+    /// are kept, and requests already in flight are not affected.
+    ///
+    /// # Panics
+    /// If `api_key` is not a valid header value, or `index` is out of range.
+    pub fn set_pool_api_key(&self, index: usize, api_key: impl AsRef<[u8]>) {
+        let mut api_key = HeaderValue::from_bytes(api_key.as_ref()).unwrap();
+        api_key.set_sensitive(true);
+        self.key_pool.set_value(index, api_key);
+    }
+
+    /// Returns the current status of the circuit breakers for the given route, by method ID.
+    /// Circuit breakers are enabled with [`RiotApiConfig::set_circuit_breaker`].
+    ///
+    /// # Parameters
+    /// * `region_platform` - The stringified platform, e.g. `PlatformRoute::NA1.into()`.
+    ///
+    /// # Returns
+    /// The status of each method's circuit breaker. Methods which have not been called on the
+    /// route, or all methods if circuit breakers are disabled, are not included.
+    pub fn circuit_breaker_status(
+        &self,
+        region_platform: &'static str,
+    ) -> BTreeMap<&'static str, CircuitBreakerStatus> {
+        self.regional_requesters
+            .get(&region_platform)
+            .map(|regional_requester| regional_requester.circuit_breaker_status())
+            .unwrap_or_default()
+    }
+
+    /// Returns the current status of the rate limits for the given route, including the app
+    /// rate limit and the rate limits of all methods which have been called on the route.
+    ///
+    /// This is useful for monitoring, or for deciding whether to queue more work.
+    ///
+    /// # Parameters
+    /// * `region_platform` - The stringified platform, e.g. `PlatformRoute::NA1.into()`.
+    ///
+    /// # Returns
+    /// A `Result` containing the status, or an error if the rate limit store failed. If no
+    /// requests have been sent on the route, the status will be empty.
+    pub async fn rate_limit_status(
+        &self,
+        region_platform: &'static str,
+    ) -> StoreResult<RouteRateLimitStatus> {
+        match self.regional_requesters.get(&region_platform) {
+            Some(regional_requester) => regional_requester.status().await,
+            None => Ok(RouteRateLimitStatus::default()),
+        }
+    }
+
+    /// Tries to take a rate limit token for a request to `method_id` on the given route,
+    /// without waiting. Useful for schedulers to check which routes have capacity before
+    /// committing to a request.
+    ///
+    /// The token is held by the returned [`Reservation`], and is used by the next request to
+    /// the same route and method within [`Reservation::scope`]. The reservation uses the
+    /// [`Priority`](crate::Priority) of the current scope. Circuit breakers and invalid API
+    /// keys are not checked until the request is sent.
+    ///
+    /// # Parameters
+    /// * `region_platform` - The stringified platform, e.g. `RegionalRoute::EUROPE.into()`.
+    /// * `method_id` - The method ID, e.g. `"match-v5.getMatch"`.
+    ///
+    /// # Returns
+    /// The reservation, otherwise the estimated delay until a token may be available, or
+    /// `None` if the delay is unknown because higher priority requests are waiting.
+    pub async fn try_reserve(
+        &self,
+        region_platform: &'static str,
+        method_id: &'static str,
+    ) -> std::result::Result<Reservation, Option<Duration>> {
         self.regional_requester(region_platform)
-            .execute(&self.config, method_id, request)
+            .try_reserve(&self.config, &self.key_pool, method_id)
+            .await
+    }
+
+    /// Sends a request for each of the `inputs` to the method `method_id` on the given route,
+    /// and returns a stream of each input with its result, in order of completion. New
+    /// requests are started as the rate limits have capacity, up to a maximum concurrency. See
+    /// the [`batch`](crate::batch) module for details.
+    ///
+    /// # Parameters
+    /// * `region_platform` - The stringified platform, e.g. `RegionalRoute::EUROPE.into()`.
+    /// * `method_id` - The method ID of the requests, e.g. `"match-v5.getMatch"`.
+    /// * `inputs` - The inputs, e.g. match IDs.
+    /// * `request` - Creates the request for an input, for example by calling an endpoint
+    ///   method. Must send a single request to the given route and method.
+    pub fn batch<'a, I, T, It, F, Fut>(
+        &'a self,
+        region_platform: &'static str,
+        method_id: &'static str,
+        inputs: It,
+        request: F,
+    ) -> Batch<'a, I, T>
+    where
+        I: MaybeSend + 'a,
+        T: MaybeSend + 'a,
+        It: IntoIterator<Item = I>,
+        It::IntoIter: MaybeSend + 'a,
+        F: FnMut(&I) -> Fut + MaybeSend + 'a,
+        Fut: Future<Output = Result<T>> + MaybeSend + 'a,
+    {
+        Batch::new(
+            self,
+            Arc::clone(&self.timer),
+            region_platform,
+            method_id,
+            inputs,
+            request,
+        )
+    }
+
+    /// Returns the current rate limit tuning, see [`Self::set_rate_limit_tuning`].
+    pub fn rate_limit_tuning(&self) -> RateLimitTuning {
+        *self.config.rate_limit_tuning.read()
+    }
+
+    /// Updates the rate usage factors, burst factor, and duration overhead of this instance,
+    /// for example to re-split an API key's rate limits when instances sharing it are added
+    /// or removed. Existing rate limits are rebuilt in place, keeping the number of requests
+    /// used in each bucket.
+    ///
+    /// Buckets are rebuilt within the rate limit store, see
+    /// [`RateLimitStore::retune_buckets`](crate::rate_limit_store::RateLimitStore::retune_buckets).
+    /// With a shared store, such as `HttpRateLimitStore`, the buckets are shared too, so
+    /// retuning one instance changes the limits for every instance sharing the store, and
+    /// the last tuning applied wins. Instances sharing a store should use the same tuning.
+    ///
+    /// ```
+    /// # async fn example(riot_api: &riven::RiotApi) {
+    /// let tuning = riot_api.rate_limit_tuning().set_rate_usage_factor(0.25);
+    /// riot_api.set_rate_limit_tuning(tuning).await.unwrap();
+    /// # }
+    /// ```
+    ///
+    /// # Returns
+    /// An error if the rate limit store failed, in which case some rate limits may still use
+    /// the previous tuning until their rate limits next change.
+    pub async fn set_rate_limit_tuning(&self, tuning: RateLimitTuning) -> StoreResult<()> {
+        *self.config.rate_limit_tuning.write() = tuning;
+        for regional_requester in self.regional_requesters.values() {
+            regional_requester.retune(&self.config).await?;
+        }
+        Ok(())
+    }
+
+    /// Returns the current state of all rate limits, which can be serialized and later
+    /// restored with [`RiotApiConfig::set_rate_limit_snapshot`].
+    ///
+    /// Only includes buckets if the rate limit store supports snapshots (the default
+    /// [`MemoryRateLimitStore`](crate::rate_limit_store::MemoryRateLimitStore) does, networked
+    /// stores do not). Retry-after deadlines are always included.
+    pub fn rate_limit_snapshot(&self) -> RateLimitSnapshot {
+        RateLimitSnapshot {
+            buckets: self.config.rate_limit_store.snapshot(),
+            retry_after: self
+                .regional_requesters
+                .values()
+                .iter()
+                .flat_map(|regional_requester| regional_requester.retry_after_snapshot())
+                .collect(),
+        }
     }
 
     /// Get or create the RegionalRequester for the given region.
@@ -195,7 +453,7 @@ impl RiotApi {
                     "Creating requester for region platform {}.",
                     region_platform
                 );
-                RegionalRequester::new()
+                RegionalRequester::new(region_platform, &self.config, &self.key_pool)
             })
     }
 }
@@ -0,0 +1,130 @@
+//! Pluggable timers used to wait for rate limits and retry backoffs.
+//!
+//! By default Riven sleeps using a [`TokioTimer`], which requires a Tokio runtime with the
+//! time driver enabled, or a [`GlooTimer`] on wasm. [`TokioTimer`] is only available with the
+//! `tokio` feature, which is enabled by default.
+//!
+//! To run Riven under a different executor, such as async-std or smol, implement [`Timer`]
+//! and set it with [`RiotApiConfig::set_timer`](crate::RiotApiConfig::set_timer). As the
+//! default [`ReqwestTransport`](crate::transport::ReqwestTransport) also requires a Tokio
+//! runtime, a different [`HttpTransport`](crate::transport::HttpTransport) must be set as
+//! well. Then the `tokio` feature can be disabled.
+//!
+//! ```ignore
+//! use std::sync::Arc;
+//!
+//! use riven::time::Duration;
+//! use riven::timer::{Timer, TimerFuture};
+//! use riven::RiotApiConfig;
+//!
+//! #[derive(Debug)]
+//! struct AsyncIoTimer;
+//! impl Timer for AsyncIoTimer {
+//!     fn sleep(&self, duration: Duration) -> TimerFuture {
+//!         Box::pin(async move {
+//!             async_io::Timer::after(duration).await;
+//!         })
+//!     }
+//! }
+//!
+//! let config = RiotApiConfig::with_key("RGAPI-...")
+//!     .set_timer(Arc::new(AsyncIoTimer))
+//!     .set_transport(my_transport);
+//! ```
+use std::fmt;
+use std::sync::Arc;
+
+use crate::time::Duration;
+
+/// Future returned by [`Timer::sleep`].
+#[cfg(not(target_family = "wasm"))]
+pub type TimerFuture = futures::future::BoxFuture<'static, ()>;
+/// Future returned by [`Timer::sleep`].
+#[cfg(target_family = "wasm")]
+pub type TimerFuture = futures::future::LocalBoxFuture<'static, ()>;
+
+/// Sleeps for a duration, see the [module docs](self).
+pub trait Timer: fmt::Debug + Send + Sync {
+    /// Returns a future which completes after `duration` has passed.
+    fn sleep(&self, duration: Duration) -> TimerFuture;
+}
+
+/// [`Timer`] using [`tokio::time::sleep`]. This is the default on native targets.
+#[cfg(all(feature = "tokio", not(target_family = "wasm")))]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TokioTimer;
+#[cfg(all(feature = "tokio", not(target_family = "wasm")))]
+impl Timer for TokioTimer {
+    fn sleep(&self, duration: Duration) -> TimerFuture {
+        Box::pin(tokio::time::sleep(duration))
+    }
+}
+
+/// [`Timer`] using [`gloo_timers`]. This is the default on wasm.
+#[cfg(target_family = "wasm")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GlooTimer;
+#[cfg(target_family = "wasm")]
+impl Timer for GlooTimer {
+    fn sleep(&self, duration: Duration) -> TimerFuture {
+        Box::pin(gloo_timers::future::sleep(duration))
+    }
+}
+
+/// The default timer for the target and features, if any.
+#[cfg(all(feature = "tokio", not(target_family = "wasm")))]
+pub(crate) fn default_timer() -> Option<Arc<dyn Timer>> {
+    Some(Arc::new(TokioTimer))
+}
+/// The default timer for the target and features, if any.
+#[cfg(target_family = "wasm")]
+pub(crate) fn default_timer() -> Option<Arc<dyn Timer>> {
+    Some(Arc::new(GlooTimer))
+}
+/// The default timer for the target and features, if any.
+#[cfg(all(not(feature = "tokio"), not(target_family = "wasm")))]
+pub(crate) fn default_timer() -> Option<Arc<dyn Timer>> {
+    None
+}
+
+#[cfg(all(test, not(target_family = "wasm")))]
+mod test {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use reqwest::{Method, StatusCode};
+
+    use super::*;
+    use crate::transport::{MockResponse, MockTransport};
+    use crate::{RiotApi, RiotApiConfig};
+
+    /// Counts sleeps, without waiting.
+    #[derive(Debug, Default)]
+    struct CountingTimer(AtomicUsize);
+    impl Timer for CountingTimer {
+        fn sleep(&self, _duration: Duration) -> TimerFuture {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            Box::pin(futures::future::ready(()))
+        }
+    }
+
+    #[tokio::test]
+    async fn custom_timer() {
+        let timer = Arc::new(CountingTimer::default());
+        let transport = Arc::new(MockTransport::new());
+        transport.push_response(MockResponse::new(StatusCode::SERVICE_UNAVAILABLE));
+        transport.push_response(MockResponse::new(StatusCode::OK).set_body("1"));
+        let riot_api = RiotApi::new(
+            RiotApiConfig::with_key("RGAPI-test")
+                .set_transport(transport)
+                .set_timer(timer.clone()),
+        );
+        let request = riot_api.request(Method::GET, "na1", "/a");
+        let value: u32 = riot_api
+            .execute_val("test.method", "NA1", request)
+            .await
+            .unwrap();
+        assert_eq!(1, value);
+        // Retry backoff and rate limit waits all use the timer.
+        assert!(0 < timer.0.load(Ordering::SeqCst));
+    }
+}
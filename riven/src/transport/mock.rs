@@ -0,0 +1,226 @@
+use std::collections::VecDeque;
+
+use futures::future;
+use parking_lot::Mutex;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::{Method, Request, ResponseBuilderExt, StatusCode, Url};
+
+use super::{HttpTransport, TransportError, TransportFuture};
+
+/// A canned response returned by a [`MockTransport`].
+#[derive(Debug, Clone)]
+pub struct MockResponse {
+    status: StatusCode,
+    headers: HeaderMap,
+    body: Vec<u8>,
+}
+
+impl MockResponse {
+    /// Creates a new `MockResponse` with the given `status`, no headers, and an empty body.
+    pub fn new(status: StatusCode) -> Self {
+        Self {
+            status,
+            headers: HeaderMap::new(),
+            body: Vec::new(),
+        }
+    }
+
+    /// Sets the response body, e.g. a JSON string.
+    ///
+    /// # Returns
+    /// `self`, for chaining.
+    pub fn set_body(mut self, body: impl Into<Vec<u8>>) -> Self {
+        self.body = body.into();
+        self
+    }
+
+    /// Sets the response header `name` to `value`, replacing any existing value.
+    ///
+    /// # Panics
+    /// If `name` or `value` is not a valid header name or value.
+    ///
+    /// # Returns
+    /// `self`, for chaining.
+    pub fn set_header(mut self, name: &str, value: &str) -> Self {
+        let name = HeaderName::from_bytes(name.as_bytes()).expect("Invalid header name.");
+        let value = HeaderValue::from_str(value).expect("Invalid header value.");
+        self.headers.insert(name, value);
+        self
+    }
+
+    /// Sets the `X-App-Rate-Limit` and `X-App-Rate-Limit-Count` headers, e.g.
+    /// `"20:1,100:120"` and `"1:1,1:120"`.
+    ///
+    /// # Returns
+    /// `self`, for chaining.
+    pub fn set_app_rate_limit(self, limit: &str, count: &str) -> Self {
+        self.set_header("X-App-Rate-Limit", limit)
+            .set_header("X-App-Rate-Limit-Count", count)
+    }
+
+    /// Sets the `X-Method-Rate-Limit` and `X-Method-Rate-Limit-Count` headers, e.g.
+    /// `"2000:60"` and `"1:60"`.
+    ///
+    /// # Returns
+    /// `self`, for chaining.
+    pub fn set_method_rate_limit(self, limit: &str, count: &str) -> Self {
+        self.set_header("X-Method-Rate-Limit", limit)
+            .set_header("X-Method-Rate-Limit-Count", count)
+    }
+
+    /// Sets the `Retry-After` header to `seconds` and the `X-Rate-Limit-Type` header to
+    /// `rate_limit_type`, one of `"application"`, `"method"`, or `"service"`. Usually used
+    /// with a `429` status.
+    ///
+    /// # Returns
+    /// `self`, for chaining.
+    pub fn set_retry_after(self, seconds: u64, rate_limit_type: &str) -> Self {
+        self.set_header("Retry-After", &seconds.to_string())
+            .set_header("X-Rate-Limit-Type", rate_limit_type)
+    }
+
+    /// Converts this into a `reqwest::Response`, with `url` as the response URL.
+    fn into_response(self, url: Url) -> reqwest::Response {
+        let mut response = http::Response::builder()
+            .url(url)
+            .body(self.body)
+            .expect("Failed to build response.");
+        *response.status_mut() = self.status;
+        *response.headers_mut() = self.headers;
+        response.into()
+    }
+}
+
+/// A request received by a [`MockTransport`].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct MockRequest {
+    /// The HTTP method.
+    pub method: Method,
+    /// The full request URL.
+    pub url: String,
+    /// The request headers.
+    pub headers: HeaderMap,
+}
+
+/// [`HttpTransport`] which returns canned responses without sending any requests.
+///
+/// Responses added with [`Self::push_response`] are returned in order, one per request. Once
+/// they run out, the default response set by [`Self::set_default_response`] is returned, or
+/// if there is none, requests fail with a [`TransportError`]. Responses have the request's
+/// URL, like those from a real transport.
+#[derive(Debug, Default)]
+pub struct MockTransport {
+    responses: Mutex<VecDeque<MockResponse>>,
+    default_response: Option<MockResponse>,
+    requests: Mutex<Vec<MockRequest>>,
+}
+
+impl MockTransport {
+    /// Creates a new `MockTransport` with no canned responses.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the response returned when there are no queued responses left.
+    ///
+    /// # Returns
+    /// `self`, for chaining.
+    pub fn set_default_response(mut self, response: MockResponse) -> Self {
+        self.default_response = Some(response);
+        self
+    }
+
+    /// Queues `response` to be returned for a request, after any previously queued
+    /// responses.
+    pub fn push_response(&self, response: MockResponse) {
+        self.responses.lock().push_back(response);
+    }
+
+    /// Returns all requests received so far, in order.
+    pub fn requests(&self) -> Vec<MockRequest> {
+        self.requests.lock().clone()
+    }
+}
+
+impl HttpTransport for MockTransport {
+    fn send(&self, request: Request) -> TransportFuture<'_> {
+        self.requests.lock().push(MockRequest {
+            method: request.method().clone(),
+            url: request.url().to_string(),
+            headers: request.headers().clone(),
+        });
+        let response = self
+            .responses
+            .lock()
+            .pop_front()
+            .or_else(|| self.default_response.clone())
+            .map(|response| response.into_response(request.url().clone()))
+            .ok_or_else(|| {
+                TransportError::from(format!("No mock response for {}.", request.url()))
+            });
+        Box::pin(future::ready(response))
+    }
+}
+
+#[cfg(all(test, feature = "tokio", not(target_family = "wasm")))]
+mod test {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::retry::ConstantBackoff;
+    use crate::time::Duration;
+    use crate::{RiotApi, RiotApiConfig};
+
+    #[tokio::test]
+    async fn canned_responses() {
+        let transport = Arc::new(MockTransport::new());
+        transport.push_response(MockResponse::new(StatusCode::SERVICE_UNAVAILABLE));
+        transport.push_response(
+            MockResponse::new(StatusCode::OK)
+                .set_body("7")
+                .set_app_rate_limit("20:1,100:120", "2:1,2:120")
+                .set_method_rate_limit("30:10", "2:10"),
+        );
+        let config = RiotApiConfig::with_key("RGAPI-test")
+            .set_retry_policy(Arc::new(ConstantBackoff::new(1, Duration::ZERO)))
+            .set_transport(transport.clone());
+        let riot_api = RiotApi::new(config);
+
+        let request = riot_api.request(Method::GET, "na1", "/test");
+        let value = riot_api
+            .execute_val::<u32>("test.method", "NA1", request)
+            .await
+            .unwrap();
+        assert_eq!(7, value);
+
+        let requests = transport.requests();
+        assert_eq!(2, requests.len());
+        assert_eq!("https://na1.api.riotgames.com/test", requests[0].url);
+        assert_eq!("RGAPI-test", requests[0].headers["X-Riot-Token"]);
+
+        let status = riot_api.rate_limit_status("NA1").await.unwrap();
+        let limits: Vec<_> = status.keys[0].app.buckets.iter().map(|b| b.limit).collect();
+        assert_eq!(vec![20, 100], limits);
+        assert_eq!(30, status.keys[0].methods["test.method"].buckets[0].limit);
+
+        transport.push_response(MockResponse::new(StatusCode::OK));
+        let request = riot_api.request(Method::GET, "na1", "/url");
+        let rinfo = riot_api
+            .execute_raw("test.method", "NA1", request)
+            .await
+            .unwrap();
+        assert_eq!(
+            "https://na1.api.riotgames.com/url",
+            rinfo.response.url().as_str()
+        );
+
+        // No responses left.
+        let request = riot_api.request(Method::GET, "na1", "/test");
+        let err = riot_api
+            .execute_val::<u32>("test.method", "NA1", request)
+            .await
+            .unwrap_err();
+        assert!(err.source_transport_error().is_some());
+    }
+}
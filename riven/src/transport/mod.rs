@@ -0,0 +1,78 @@
+//! Pluggable HTTP transport used to send requests.
+//!
+//! Riven builds each request as a [`reqwest::Request`], and, after rate limiting, sends it
+//! through an [`HttpTransport`]. By default this is a [`ReqwestTransport`], which sends
+//! requests using the `reqwest::Client` built from the config's client builder. A different
+//! transport is set with
+//! [`RiotApiConfig::set_transport`](crate::RiotApiConfig::set_transport), for example to use
+//! a different HTTP client.
+//!
+//! [`MockTransport`] returns canned responses without touching the network, which is useful
+//! for testing code built on Riven. Canned responses may include rate limit headers, which
+//! Riven's rate limiting will respect as usual.
+//!
+//! ```
+//! use std::sync::Arc;
+//!
+//! use riven::reqwest::StatusCode;
+//! use riven::transport::{MockResponse, MockTransport};
+//! use riven::RiotApiConfig;
+//!
+//! let transport = Arc::new(MockTransport::new());
+//! transport.push_response(
+//!     MockResponse::new(StatusCode::OK)
+//!         .set_body(r#"{"freeChampionIds":[],"freeChampionIdsForNewPlayers":[],"maxNewPlayerLevel":10}"#)
+//!         .set_app_rate_limit("20:1,100:120", "1:1,1:120")
+//!         .set_method_rate_limit("30:10", "1:10"),
+//! );
+//! let config = RiotApiConfig::with_key("RGAPI-...").set_transport(transport.clone());
+//! ```
+use std::fmt;
+
+use reqwest::{Client, Request, Response};
+
+mod mock;
+pub use mock::{MockRequest, MockResponse, MockTransport};
+
+/// Error returned by an [`HttpTransport`] when a request fails without a response, for
+/// example a connection error. Requests which fail this way may be retried.
+pub type TransportError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Future returned by [`HttpTransport::send`].
+#[cfg(not(target_family = "wasm"))]
+pub type TransportFuture<'a> =
+    futures::future::BoxFuture<'a, std::result::Result<Response, TransportError>>;
+/// Future returned by [`HttpTransport::send`].
+#[cfg(target_family = "wasm")]
+pub type TransportFuture<'a> =
+    futures::future::LocalBoxFuture<'a, std::result::Result<Response, TransportError>>;
+
+/// Sends HTTP requests, see the [module docs](self).
+pub trait HttpTransport: fmt::Debug + Send + Sync {
+    /// Sends `request`, returning the response, or an error if no response was received.
+    ///
+    /// Responses with error status codes should be returned as `Ok`, Riven handles them.
+    /// Responses can be constructed from other HTTP clients' responses via
+    /// `reqwest::Response::from(http::Response<_>)`.
+    fn send(&self, request: Request) -> TransportFuture<'_>;
+}
+
+/// [`HttpTransport`] which sends requests using a [`reqwest::Client`]. This is the default.
+#[derive(Debug, Clone)]
+pub struct ReqwestTransport {
+    client: Client,
+}
+
+impl ReqwestTransport {
+    /// Creates a new `ReqwestTransport` which sends requests using `client`.
+    pub fn new(client: Client) -> Self {
+        Self { client }
+    }
+}
+
+impl HttpTransport for ReqwestTransport {
+    fn send(&self, request: Request) -> TransportFuture<'_> {
+        let response = self.client.execute(request);
+        Box::pin(async move { response.await.map_err(TransportError::from) })
+    }
+}
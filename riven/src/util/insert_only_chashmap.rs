@@ -25,4 +25,26 @@ impl<K: Hash + Eq, V> InsertOnlyCHashMap<K, V> {
                 .or_insert_with(|| Arc::new(default())),
         )
     }
+
+    #[inline]
+    pub fn get(&self, key: &K) -> Option<Arc<V>> {
+        self.base.lock().get(key).map(Arc::clone)
+    }
+
+    #[inline]
+    pub fn entries(&self) -> Vec<(K, Arc<V>)>
+    where
+        K: Clone,
+    {
+        self.base
+            .lock()
+            .iter()
+            .map(|(k, v)| (k.clone(), Arc::clone(v)))
+            .collect()
+    }
+
+    #[inline]
+    pub fn values(&self) -> Vec<Arc<V>> {
+        self.base.lock().values().map(Arc::clone).collect()
+    }
 }
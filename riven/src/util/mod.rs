@@ -3,3 +3,6 @@ pub use insert_only_chashmap::InsertOnlyCHashMap;
 
 mod notify;
 pub use notify::Notify;
+
+mod scoped;
+pub use scoped::{get_scoped, Scoped};
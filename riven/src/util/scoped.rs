@@ -0,0 +1,49 @@
+use std::cell::Cell;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::thread::LocalKey;
+
+/// A future which sets a thread-local `key` to `value` while `future` is being polled, similar
+/// to `tokio::task_local!`. Used to pass per-request options through the endpoint methods.
+pub struct Scoped<T: Copy + 'static, F> {
+    key: &'static LocalKey<Cell<Option<T>>>,
+    value: T,
+    future: Pin<Box<F>>,
+}
+
+impl<T: Copy + 'static, F: Future> Scoped<T, F> {
+    pub fn new(key: &'static LocalKey<Cell<Option<T>>>, value: T, future: F) -> Self {
+        Self {
+            key,
+            value,
+            future: Box::pin(future),
+        }
+    }
+}
+
+// `future` is boxed, so `Scoped` never needs to be pinned.
+impl<T: Copy + 'static, F> Unpin for Scoped<T, F> {}
+
+impl<T: Copy + 'static, F: Future> Future for Scoped<T, F> {
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        /// Restores the previous value on drop, in case `future` panics.
+        struct Reset<T: Copy + 'static>(&'static LocalKey<Cell<Option<T>>>, Option<T>);
+        impl<T: Copy + 'static> Drop for Reset<T> {
+            fn drop(&mut self) {
+                self.0.with(|cell| cell.set(self.1));
+            }
+        }
+
+        let prev = self.key.with(|cell| cell.replace(Some(self.value)));
+        let _reset = Reset(self.key, prev);
+        self.future.as_mut().poll(cx)
+    }
+}
+
+/// Gets the current value of a [`Scoped`] thread-local `key`.
+pub fn get_scoped<T: Copy + 'static>(key: &'static LocalKey<Cell<Option<T>>>) -> Option<T> {
+    key.with(Cell::get)
+}
@@ -0,0 +1,64 @@
+#![cfg(all(feature = "tokio", not(target_family = "wasm")))]
+
+use std::collections::HashSet;
+use std::convert::Infallible;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Request, Response, Server};
+use riven::reqwest::Method;
+use riven::{RiotApi, RiotApiConfig};
+
+/// Starts a server which responds to the first request for each path with a `429` with a
+/// three second `Retry-After`, of the rate limit type named by the path, then with `1`.
+fn start_server() -> String {
+    let limited = Arc::new(Mutex::new(HashSet::new()));
+    let make_svc = make_service_fn(move |_conn| {
+        let limited = Arc::clone(&limited);
+        async move {
+            Ok::<_, Infallible>(service_fn(move |req: Request<Body>| {
+                let path = req.uri().path().to_owned();
+                let first = limited.lock().unwrap().insert(path.clone());
+                async move {
+                    let response = if first {
+                        Response::builder()
+                            .status(429)
+                            .header("Retry-After", "3")
+                            .header("X-Rate-Limit-Type", path.trim_start_matches('/'))
+                            .body(Body::empty())
+                    } else {
+                        Response::builder().body(Body::from("1"))
+                    };
+                    Ok::<_, Infallible>(response.unwrap())
+                }
+            }))
+        }
+    });
+    let server = Server::bind(&([127, 0, 0, 1], 0).into()).serve(make_svc);
+    let url = format!("http://{}", server.local_addr());
+    tokio::spawn(server);
+    url
+}
+
+/// An application or method retry-after alone delays the retry.
+#[tokio::test]
+async fn retry_after_blocks() {
+    let riot_api = RiotApi::new(RiotApiConfig::with_key("RGAPI-test").set_base_url(start_server()));
+    let riot_api = &riot_api;
+    let get = |route: &'static str, path| {
+        let request = riot_api.request(Method::GET, route, path);
+        async move {
+            let start = Instant::now();
+            let value: u32 = riot_api
+                .execute_val("test.method", route, request)
+                .await
+                .unwrap();
+            assert_eq!(1, value);
+            start.elapsed()
+        }
+    };
+    let (app, method) = futures::join!(get("NA1", "/application"), get("EUW1", "/method"));
+    assert!(Duration::from_secs(3) <= app, "{:?}", app);
+    assert!(Duration::from_secs(3) <= method, "{:?}", method);
+}
@@ -3,9 +3,11 @@
 #[cfg(not(target_family = "wasm"))]
 use std::env::var as env_var;
 use std::future::Future;
-use std::sync::OnceLock;
+use std::sync::{Arc, OnceLock};
 
 use futures::try_join;
+#[cfg(not(target_family = "wasm"))]
+use riven::cassette::Cassette;
 use riven::consts::{PlatformRoute, QueueType, RegionalRoute};
 use riven::{RiotApi, RiotApiConfig};
 #[cfg(not(target_family = "wasm"))]
@@ -85,11 +87,49 @@ pub fn riot_api() -> &'static RiotApi {
                     std::path::PathBuf::from_iter([env!("CARGO_MANIFEST_DIR"), "../apikey.txt"]);
                 std::fs::read_to_string(path).ok()
             })
+            // Replaying a cassette does not need a real API key.
+            .or_else(|| {
+                (Ok("replay") == env_var("RIVEN_CASSETTE").as_deref())
+                    .then(|| "RGAPI-replay".to_owned())
+            })
             .expect("Failed to find RGAPI_KEY env var or apikey.txt.");
-        RiotApi::new(RiotApiConfig::with_key(api_key.trim()).preconfig_burst())
+        let config = RiotApiConfig::with_key(api_key.trim()).preconfig_burst();
+        #[cfg(not(target_family = "wasm"))]
+        let config = match cassette() {
+            Some(cassette) => config.add_middleware(Arc::new(cassette)),
+            None => config,
+        };
+        RiotApi::new(config)
     })
 }
 
+/// Gets the cassette for the `RIVEN_CASSETTE` env var mode, `record` or `replay`, if set.
+/// Each test file uses its own fixture file, `tests/cassettes/<test file>.jsonl`.
+#[cfg(not(target_family = "wasm"))]
+fn cassette() -> Option<Cassette> {
+    use std::iter::FromIterator;
+
+    let path = std::path::PathBuf::from_iter([
+        env!("CARGO_MANIFEST_DIR"),
+        "tests/cassettes",
+        concat!(env!("CARGO_CRATE_NAME"), ".jsonl"),
+    ]);
+    match env_var("RIVEN_CASSETTE").ok()?.as_str() {
+        "record" => {
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            Some(Cassette::record(path))
+        }
+        "replay" => Some(
+            Cassette::replay(&path)
+                .unwrap_or_else(|e| panic!("Failed to load cassette {}: {}", path.display(), e)),
+        ),
+        other => panic!(
+            "Unknown RIVEN_CASSETTE mode {:?}, expected \"record\" or \"replay\".",
+            other
+        ),
+    }
+}
+
 pub async fn league_v4_match_v5_latest_combo(route: PlatformRoute) -> Result<(), String> {
     const NUM_MATCHES: usize = 10;
 
@@ -304,13 +344,13 @@ pub async fn spectator_v4_combo(route: PlatformRoute) -> Result<(), String> {
     let summoner_id = participant.summoner_id.as_ref().ok_or_else(|| {
         format!(
             "Summoner in spectator featured game {} missing summoner ID: {}",
-            featured_game.game_id, &participant.summoner_name,
+            featured_game.game_id, participant.summoner_name,
         )
     })?;
 
     let livegame_p = riot_api()
         .spectator_v4()
-        .get_current_game_info_by_summoner(route, &summoner_id);
+        .get_current_game_info_by_summoner(route, summoner_id);
     let livegame_o = livegame_p.await.map_err(|e| {
         format!(
             "Failed to get live game {} for summoner ID {}: {}",
@@ -345,13 +385,13 @@ pub async fn spectator_v5_combo(route: PlatformRoute) -> Result<(), String> {
     let puuid = participant.puuid.as_ref().ok_or_else(|| {
         format!(
             "Summoner in spectator featured game {} missing summoner ID: {}",
-            featured_game.game_id, &participant.summoner_name,
+            featured_game.game_id, participant.summoner_name,
         )
     })?;
 
     let livegame_p = riot_api()
         .spectator_v5()
-        .get_current_game_info_by_puuid(route, &puuid);
+        .get_current_game_info_by_puuid(route, puuid);
     let livegame_o = livegame_p.await.map_err(|e| {
         format!(
             "Failed to get live game {} for summoner PUUID {}: {}",
@@ -386,13 +426,13 @@ pub async fn spectator_tft_v5_combo(route: PlatformRoute) -> Result<(), String>
     let puuid = participant.puuid.as_ref().ok_or_else(|| {
         format!(
             "Summoner in spectator featured game {} missing summoner ID: {}",
-            featured_game.game_id, &participant.summoner_name,
+            featured_game.game_id, participant.summoner_name,
         )
     })?;
 
     let livegame_p = riot_api()
         .spectator_tft_v5()
-        .get_current_game_info_by_puuid(route, &puuid);
+        .get_current_game_info_by_puuid(route, puuid);
     let livegame_o = livegame_p.await.map_err(|e| {
         format!(
             "Failed to get live game {} for summoner PUUID {}: {}",
@@ -6,7 +6,7 @@ cargo +stable check --all-targets --features __proxy
 cargo +stable check --all-targets --features tracing,__proxy
 
 # Ensure nightly builds.
-cargo check --all-targets --features nightly,tracing,__proxy
+cargo check --all-targets --features nightly,tracing,blocking,__proxy
 cargo build --all-targets --features nightly,deny-unknown,__proxy
 
 # Run nightly tests.